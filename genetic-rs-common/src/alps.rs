@@ -0,0 +1,378 @@
+use std::ops::{Deref, DerefMut};
+
+use crate::{
+    builtin::repopulator::{Mitosis, RandomlyMutable},
+    Eliminator, EvaluatedPopulation, FeatureBoundedEliminator, FeatureBoundedGenome,
+    FeatureBoundedRepopulator, GenerateRandom, Repopulator,
+};
+
+#[cfg(feature = "crossover")]
+use crate::builtin::repopulator::Crossover;
+
+/// A genome paired with its age, the number of generations its genetic material has been in the population.
+///
+/// [`Aged`] dereferences to the inner genome, so fitness functions written for `G` can usually be reused
+/// with a closure like `|g: &Aged<MyGenome>| my_fitness(g)`.
+/// Offspring inherit the age of their oldest parent, so an old lineage cannot hide in a young layer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Aged<G> {
+    /// The wrapped genome.
+    pub genome: G,
+
+    /// The age of the genome, in generations.
+    pub age: usize,
+}
+
+impl<G> Aged<G> {
+    /// Wraps a genome with an age of 0.
+    pub fn new(genome: G) -> Self {
+        Self { genome, age: 0 }
+    }
+
+    /// Unwraps the genome, discarding its age.
+    pub fn into_inner(self) -> G {
+        self.genome
+    }
+}
+
+impl<G> From<G> for Aged<G> {
+    fn from(genome: G) -> Self {
+        Self::new(genome)
+    }
+}
+
+impl<G> Deref for Aged<G> {
+    type Target = G;
+
+    fn deref(&self) -> &G {
+        &self.genome
+    }
+}
+
+impl<G> DerefMut for Aged<G> {
+    fn deref_mut(&mut self) -> &mut G {
+        &mut self.genome
+    }
+}
+
+impl<G: GenerateRandom> GenerateRandom for Aged<G> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        Self::new(G::gen_random(rng))
+    }
+}
+
+impl<G: RandomlyMutable> RandomlyMutable for Aged<G> {
+    type Context = G::Context;
+
+    fn mutate(&mut self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) {
+        self.genome.mutate(ctx, rate, rng);
+    }
+}
+
+impl<G: Mitosis> Mitosis for Aged<G> {
+    type Context = G::Context;
+
+    fn divide(&self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        Self {
+            genome: self.genome.divide(ctx, rate, rng),
+            age: self.age,
+        }
+    }
+}
+
+#[cfg(feature = "crossover")]
+impl<G: Crossover> Crossover for Aged<G> {
+    type Context = G::Context;
+
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &Self::Context,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        Self {
+            genome: self.genome.crossover(&other.genome, ctx, rate, rng),
+            age: self.age.max(other.age),
+        }
+    }
+}
+
+/// The age limits that split a population into layers.
+/// A genome belongs to the first layer whose maximum age is at least its own age.
+/// The top layer has no maximum age.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AgeLayers {
+    max_ages: Vec<usize>,
+}
+
+impl AgeLayers {
+    /// Creates a new [`AgeLayers`] from the maximum ages of every layer except the top one.
+    /// Panics if the maximum ages are not strictly increasing.
+    pub fn new(max_ages: Vec<usize>) -> Self {
+        assert!(
+            max_ages.windows(2).all(|w| w[0] < w[1]),
+            "Maximum ages must be strictly increasing"
+        );
+        Self { max_ages }
+    }
+
+    /// Creates `count` layers whose maximum ages grow linearly: `age_gap`, `2 * age_gap`, `3 * age_gap`, ...
+    /// Panics if `age_gap` or `count` is 0.
+    pub fn linear(age_gap: usize, count: usize) -> Self {
+        assert!(age_gap > 0, "Age gap must be greater than 0");
+        assert!(count > 0, "Layer count must be greater than 0");
+        Self::new((1..count).map(|i| age_gap * i).collect())
+    }
+
+    /// Creates `count` layers whose maximum ages grow quadratically: `age_gap`, `4 * age_gap`, `9 * age_gap`, ...
+    /// Panics if `age_gap` or `count` is 0.
+    pub fn polynomial(age_gap: usize, count: usize) -> Self {
+        assert!(age_gap > 0, "Age gap must be greater than 0");
+        assert!(count > 0, "Layer count must be greater than 0");
+        Self::new((1..count).map(|i| age_gap * i * i).collect())
+    }
+
+    /// The maximum ages of every layer except the top one.
+    pub fn max_ages(&self) -> &[usize] {
+        &self.max_ages
+    }
+
+    /// The total number of layers, including the unbounded top layer.
+    pub fn len(&self) -> usize {
+        self.max_ages.len() + 1
+    }
+
+    /// Always false, since there is at least the top layer.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The index of the layer a genome of the given age belongs to.
+    pub fn layer_of(&self, age: usize) -> usize {
+        self.max_ages
+            .iter()
+            .position(|&max| age <= max)
+            .unwrap_or(self.max_ages.len())
+    }
+
+    /// Splits a population into its layers, preserving the relative order of the genomes.
+    pub fn split<G>(&self, genomes: Vec<Aged<G>>) -> Vec<Vec<Aged<G>>> {
+        let mut layers: Vec<Vec<Aged<G>>> = (0..self.len()).map(|_| Vec::new()).collect();
+        for g in genomes {
+            layers[self.layer_of(g.age)].push(g);
+        }
+        layers
+    }
+
+    /// Same as [`AgeLayers::split`], but keeps the metadata of each genome.
+    pub fn split_evaluated<G>(
+        &self,
+        population: EvaluatedPopulation<Aged<G>>,
+    ) -> Vec<EvaluatedPopulation<Aged<G>>> {
+        let EvaluatedPopulation {
+            genomes,
+            fitness,
+            species,
+            ages,
+        } = population;
+
+        let mut layers: Vec<EvaluatedPopulation<Aged<G>>> = (0..self.len())
+            .map(|_| EvaluatedPopulation {
+                genomes: Vec::new(),
+                fitness: fitness.as_ref().map(|_| Vec::new()),
+                species: species.as_ref().map(|_| Vec::new()),
+                ages: ages.as_ref().map(|_| Vec::new()),
+            })
+            .collect();
+
+        for (i, g) in genomes.into_iter().enumerate() {
+            let layer = &mut layers[self.layer_of(g.age)];
+            if let (Some(from), Some(to)) = (&fitness, &mut layer.fitness) {
+                to.push(from[i]);
+            }
+            if let (Some(from), Some(to)) = (&species, &mut layer.species) {
+                to.push(from[i]);
+            }
+            if let (Some(from), Some(to)) = (&ages, &mut layer.ages) {
+                to.push(from[i]);
+            }
+            layer.genomes.push(g);
+        }
+        layers
+    }
+}
+
+/// An eliminator that splits the population into age layers and runs an inner eliminator on each layer separately,
+/// so genomes only compete with genomes of a similar age.
+/// The survivors are returned ordered by layer, from youngest to oldest.
+pub struct AlpsEliminator<E: FeatureBoundedEliminator<Aged<G>>, G: FeatureBoundedGenome> {
+    /// The eliminator run on each layer.
+    pub inner: E,
+
+    /// The age layers.
+    pub layers: AgeLayers,
+
+    _marker: std::marker::PhantomData<G>,
+}
+
+impl<E, G> AlpsEliminator<E, G>
+where
+    E: FeatureBoundedEliminator<Aged<G>>,
+    G: FeatureBoundedGenome,
+{
+    /// Creates a new [`AlpsEliminator`].
+    pub fn new(inner: E, layers: AgeLayers) -> Self {
+        Self {
+            inner,
+            layers,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<E, G> Eliminator<Aged<G>> for AlpsEliminator<E, G>
+where
+    E: FeatureBoundedEliminator<Aged<G>>,
+    G: FeatureBoundedGenome,
+{
+    fn eliminate(&mut self, genomes: Vec<Aged<G>>) -> Vec<Aged<G>> {
+        self.eliminate_evaluated(genomes).genomes
+    }
+
+    /// The survivors are reported with their ages, and with whatever else the inner eliminator reports.
+    fn eliminate_evaluated(&mut self, genomes: Vec<Aged<G>>) -> EvaluatedPopulation<Aged<G>> {
+        let mut survivors = EvaluatedPopulation::new(Vec::with_capacity(genomes.len()));
+        for layer in self.layers.split(genomes) {
+            if layer.is_empty() {
+                continue;
+            }
+            survivors.append(self.inner.eliminate_evaluated(layer));
+        }
+
+        let ages = survivors.genomes.iter().map(|g| g.age).collect();
+        survivors.with_ages(ages)
+    }
+}
+
+/// A repopulator that refills each age layer separately with an inner repopulator, then ages every genome by one generation.
+///
+/// Each layer gets an equal share of the target population. A layer with fewer than two survivors is bred together with
+/// the layer below it (so crossover always has two parents to choose from), and a bottom layer with fewer than two
+/// survivors is topped up with random genomes. Each layer is passed to the inner repopulator along with whatever the
+/// [`AlpsEliminator`] reported about it, so fitness-based parent selection works within a layer.
+/// Every `injection_interval` generations, the offspring of the bottom layer are replaced with new random genomes
+/// (the bottom layer's survivors are kept), which continually introduces fresh genetic material.
+pub struct AlpsRepopulator<R: FeatureBoundedRepopulator<Aged<G>>, G: GenerateRandom> {
+    /// The repopulator run on each layer.
+    pub inner: R,
+
+    /// The age layers. Should match the layers of the [`AlpsEliminator`].
+    pub layers: AgeLayers,
+
+    /// The number of generations between random injections into the bottom layer. 0 disables injection.
+    pub injection_interval: usize,
+
+    /// The number of times this repopulator has been run.
+    pub generation: usize,
+
+    _marker: std::marker::PhantomData<G>,
+}
+
+impl<R, G> AlpsRepopulator<R, G>
+where
+    R: FeatureBoundedRepopulator<Aged<G>>,
+    G: GenerateRandom,
+{
+    /// Creates a new [`AlpsRepopulator`].
+    pub fn new(inner: R, layers: AgeLayers, injection_interval: usize) -> Self {
+        Self {
+            inner,
+            layers,
+            injection_interval,
+            generation: 0,
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    fn layer_quotas(&self, target_size: usize) -> Vec<usize> {
+        let count = self.layers.len();
+        (0..count)
+            .map(|i| target_size / count + usize::from(i < target_size % count))
+            .collect()
+    }
+}
+
+impl<R, G> Repopulator<Aged<G>> for AlpsRepopulator<R, G>
+where
+    R: FeatureBoundedRepopulator<Aged<G>>,
+    G: GenerateRandom,
+{
+    fn repopulate(&mut self, genomes: &mut Vec<Aged<G>>, target_size: usize) {
+        *genomes = self.repopulate_evaluated(
+            EvaluatedPopulation::new(std::mem::take(genomes)),
+            target_size,
+        );
+    }
+
+    fn repopulate_evaluated(
+        &mut self,
+        population: EvaluatedPopulation<Aged<G>>,
+        target_size: usize,
+    ) -> Vec<Aged<G>> {
+        self.generation += 1;
+        let inject =
+            self.injection_interval != 0 && self.generation.is_multiple_of(self.injection_interval);
+
+        let quotas = self.layer_quotas(target_size);
+        let layers = self.layers.split_evaluated(population);
+        let mut rng = rand::rng();
+
+        let mut refilled = Vec::with_capacity(layers.len());
+        let mut carried = EvaluatedPopulation::new(Vec::new());
+        let mut carried_quota = 0;
+        for (i, mut layer) in layers.into_iter().enumerate().rev() {
+            let quota = quotas[i] + carried_quota;
+            layer.append(std::mem::replace(
+                &mut carried,
+                EvaluatedPopulation::new(Vec::new()),
+            ));
+            carried_quota = 0;
+
+            if i != 0 && layer.len() < 2 {
+                carried = layer;
+                carried_quota = quota;
+                continue;
+            }
+
+            layer.truncate(quota);
+            let mut children = if i == 0 && inject {
+                let mut children = layer.genomes;
+                while children.len() < quota {
+                    children.push(Aged::gen_random(&mut rng));
+                }
+                children
+            } else if quota > 0 {
+                if layer.len() < 2 {
+                    let padding = (layer.len()..2)
+                        .map(|_| Aged::gen_random(&mut rng))
+                        .collect();
+                    layer.append(EvaluatedPopulation::new(padding));
+                }
+                self.inner.repopulate_evaluated(layer, quota)
+            } else {
+                layer.genomes
+            };
+
+            // a layer padded up to two parents can come back larger than its quota.
+            children.truncate(quota);
+            refilled.push(children);
+        }
+
+        let mut genomes: Vec<Aged<G>> = refilled.into_iter().rev().flatten().collect();
+        for g in genomes.iter_mut() {
+            g.age += 1;
+        }
+        genomes
+    }
+}
@@ -1,4 +1,5 @@
 use crate::Eliminator;
+use crate::EvaluatedPopulation;
 use crate::FeatureBoundedGenome;
 
 #[cfg(feature = "rayon")]
@@ -9,6 +10,11 @@ use rayon::prelude::*;
 pub trait FitnessFn<G> {
     /// Evaluates a genome's fitness
     fn fitness(&self, genome: &G) -> f32;
+
+    /// Called by the builtin eliminators once per generation with the entire population, before any genome is evaluated.
+    /// Fitness sources that score genomes relative to the rest of the population (such as novelty search) can use this to update their state.
+    /// Does nothing by default.
+    fn prepare(&mut self, _genomes: &[G]) {}
 }
 
 impl<G, F> FitnessFn<G> for F
@@ -268,22 +274,18 @@ where
     G: FeatureBoundedGenome,
     O: FeatureBoundedFitnessObserver<G>,
 {
-    #[cfg(not(feature = "rayon"))]
     fn eliminate(&mut self, genomes: Vec<G>) -> Vec<G> {
-        let mut fitnesses = self.calculate_and_sort(genomes);
-        let median_index = (fitnesses.len() as f32) * self.threshold;
-        self.observer.observe(&fitnesses);
-        fitnesses.truncate(median_index as usize + 1);
-        fitnesses.into_iter().map(|(g, _)| g).collect()
+        self.eliminate_evaluated(genomes).genomes
     }
 
-    #[cfg(feature = "rayon")]
-    fn eliminate(&mut self, genomes: Vec<G>) -> Vec<G> {
+    fn eliminate_evaluated(&mut self, genomes: Vec<G>) -> EvaluatedPopulation<G> {
+        self.fitness_fn.prepare(&genomes);
         let mut fitnesses = self.calculate_and_sort(genomes);
         let median_index = (fitnesses.len() as f32) * self.threshold;
         self.observer.observe(&fitnesses);
         fitnesses.truncate(median_index as usize + 1);
-        fitnesses.into_par_iter().map(|(g, _)| g).collect()
+        let (genomes, fitness) = fitnesses.into_iter().unzip();
+        EvaluatedPopulation::new(genomes).with_fitness(fitness)
     }
 }
 
@@ -413,6 +415,10 @@ mod knockout {
     pub trait KnockoutFn<G> {
         /// Tests the genomes to figure out who wins.
         fn knockout(&self, a: &G, b: &G) -> KnockoutWinner;
+
+        /// Called by [`KnockoutEliminator`] once per generation with the entire population, before any knockouts.
+        /// See [`FitnessFn::prepare`]. Does nothing by default.
+        fn prepare(&mut self, _genomes: &[G]) {}
     }
 
     impl<G, F> KnockoutFn<G> for F
@@ -451,6 +457,10 @@ mod knockout {
             let bfit = self.fitness_fn.fitness(b);
             afit.total_cmp(&bfit).into()
         }
+
+        fn prepare(&mut self, genomes: &[G]) {
+            self.fitness_fn.prepare(genomes);
+        }
     }
 
     #[doc(hidden)]
@@ -535,6 +545,8 @@ mod knockout {
                 return genomes;
             }
 
+            self.knockout_fn.prepare(&genomes);
+
             let mut rng = rand::rng();
             let mut output = Vec::with_capacity(genomes.len() / 2);
 
@@ -647,20 +659,22 @@ mod speciation {
 
         /// Computes raw and species-divided fitness for every genome.
         ///
-        /// Returns `(raw_fitnesses, divided_fitnesses)` where both vecs are indexed
+        /// Returns `(raw_fitnesses, divided_fitnesses, species_indices)` where all vecs are indexed
         /// the same way as `genomes`.  The divided value is used for elimination
         /// (to balance species pressure); the raw value is what observers see.
         #[cfg(not(feature = "rayon"))]
-        fn calculate_fitnesses(&self, genomes: &[G]) -> (Vec<f32>, Vec<f32>) {
+        fn calculate_fitnesses(&self, genomes: &[G]) -> (Vec<f32>, Vec<f32>, Vec<usize>) {
             let population =
                 SpeciatedPopulation::from_genomes(genomes, self.speciation_threshold, &self.ctx);
             let mut raw = vec![0.0_f32; genomes.len()];
             let mut divided = vec![0.0_f32; genomes.len()];
+            let mut species_indices = vec![0; genomes.len()];
 
-            for species in population.species() {
+            for (species_i, species) in population.species().iter().enumerate() {
                 let len = species.len() as f32;
                 debug_assert!(len != 0.0);
                 for &index in species {
+                    species_indices[index] = species_i;
                     let fitness = self.inner.fitness_fn.fitness(&genomes[index]);
                     raw[index] = fitness;
                     divided[index] = if fitness < 0.0 {
@@ -671,7 +685,7 @@ mod speciation {
                 }
             }
 
-            (raw, divided)
+            (raw, divided, species_indices)
         }
 
         /// Computes raw and species-divided fitness for every genome (parallel version).
@@ -679,16 +693,18 @@ mod speciation {
         /// Species membership is determined sequentially first (greedy clustering), then
         /// fitness functions are evaluated in parallel using rayon.
         #[cfg(feature = "rayon")]
-        fn calculate_fitnesses(&self, genomes: &[G]) -> (Vec<f32>, Vec<f32>) {
+        fn calculate_fitnesses(&self, genomes: &[G]) -> (Vec<f32>, Vec<f32>, Vec<usize>) {
             let population =
                 SpeciatedPopulation::from_genomes(genomes, self.speciation_threshold, &self.ctx);
 
             let mut species_lens = vec![0.0_f32; genomes.len()];
-            for species in population.species() {
+            let mut species_indices = vec![0; genomes.len()];
+            for (species_i, species) in population.species().iter().enumerate() {
                 let len = species.len() as f32;
                 debug_assert!(len != 0.0);
                 for &index in species {
                     species_lens[index] = len;
+                    species_indices[index] = species_i;
                 }
             }
 
@@ -710,13 +726,13 @@ mod speciation {
             let raw = results.iter().map(|&(r, _)| r).collect();
             let divided = results.iter().map(|&(_, d)| d).collect();
 
-            (raw, divided)
+            (raw, divided, species_indices)
         }
 
         /// Calculates the fitness of each genome, dividing by the number of genomes in its species, and sorts them by fitness.
         /// Returns a vector of tuples containing the genome and its fitness score.
         pub fn calculate_and_sort(&self, genomes: Vec<G>) -> Vec<(G, f32)> {
-            let (_, divided) = self.calculate_fitnesses(&genomes);
+            let (_, divided, _) = self.calculate_fitnesses(&genomes);
             let mut result: Vec<(G, f32)> = genomes.into_iter().zip(divided).collect();
             result.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
             result
@@ -729,67 +745,467 @@ mod speciation {
         G: Speciated + FeatureBoundedGenome,
         O: FeatureBoundedFitnessObserver<G>,
     {
-        #[cfg(not(feature = "rayon"))]
         fn eliminate(&mut self, genomes: Vec<G>) -> Vec<G> {
-            let (raw, divided) = self.calculate_fitnesses(&genomes);
+            self.eliminate_evaluated(genomes).genomes
+        }
+
+        /// The survivors are reported with their raw (undivided) fitness and their species index.
+        fn eliminate_evaluated(&mut self, genomes: Vec<G>) -> EvaluatedPopulation<G> {
+            self.inner.fitness_fn.prepare(&genomes);
+            let (raw, divided, species) = self.calculate_fitnesses(&genomes);
 
-            let mut data: Vec<(G, f32, f32)> = genomes
+            let mut data: Vec<(G, f32, f32, usize)> = genomes
                 .into_iter()
                 .enumerate()
-                .map(|(i, g)| (g, raw[i], divided[i]))
+                .map(|(i, g)| (g, raw[i], divided[i], species[i]))
                 .collect();
 
             let median_index = (data.len() as f32) * self.inner.threshold;
 
             // Sort by raw fitness so observer inputs are ordered by fitness descending.
-            data.sort_by(|(_, a, _), (_, b, _)| b.partial_cmp(a).unwrap());
+            data.sort_by(|(_, a, _, _), (_, b, _, _)| b.partial_cmp(a).unwrap());
 
             // Split raw-sorted pairs for the observer while retaining divided values.
-            let (observer_pairs, divided_vals): (Vec<(G, f32)>, Vec<f32>) = data
+            let (observer_pairs, extra): (Vec<(G, f32)>, Vec<_>) = data
                 .into_iter()
-                .map(|(g, raw, div)| ((g, raw), div))
+                .map(|(g, raw, div, species)| ((g, raw), (div, species)))
                 .unzip();
 
             self.inner.observer.observe(&observer_pairs);
 
             // Re-sort by divided fitness and truncate for speciation-aware elimination.
-            let mut with_divided: Vec<_> = observer_pairs.into_iter().zip(divided_vals).collect();
-            with_divided.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+            let mut with_divided: Vec<_> = observer_pairs.into_iter().zip(extra).collect();
+            with_divided.sort_by(|(_, (a, _)), (_, (b, _))| b.partial_cmp(a).unwrap());
             with_divided.truncate(median_index as usize + 1);
-            with_divided.into_iter().map(|((g, _), _)| g).collect()
+
+            let mut survivors = Vec::with_capacity(with_divided.len());
+            let mut fitness = Vec::with_capacity(with_divided.len());
+            let mut species = Vec::with_capacity(with_divided.len());
+            for ((g, raw), (_, s)) in with_divided {
+                survivors.push(g);
+                fitness.push(raw);
+                species.push(s);
+            }
+            EvaluatedPopulation::new(survivors)
+                .with_fitness(fitness)
+                .with_species(species)
+        }
+    }
+
+    /// An eliminator that implements the clearing procedure for niching.
+    /// Genomes within [`clearing_radius`][ClearingEliminator::clearing_radius] of each other
+    /// (measured by [`Speciated::divergence`]) share a niche, and only the best
+    /// [`capacity`][ClearingEliminator::capacity] genomes of each niche keep their fitness.
+    /// Every other genome in the niche is set to [`CLEARED_FITNESS`][ClearingEliminator::CLEARED_FITNESS]
+    /// before selection.
+    ///
+    /// Unlike [`SpeciatedFitnessEliminator`], niches are not fixed species with a representative.
+    /// Each surviving niche winner clears its own neighbourhood, which tends to work better on multimodal problems.
+    pub struct ClearingEliminator<
+        F: FeatureBoundedFitnessFn<G>,
+        G: Speciated + FeatureBoundedGenome,
+        O: FeatureBoundedFitnessObserver<G> = (),
+    > {
+        /// The niche radius. Genomes with a divergence less than this value are in the same niche.
+        pub clearing_radius: f32,
+
+        /// The number of genomes in each niche that keep their fitness. Must be at least 1.
+        pub capacity: usize,
+
+        /// The inner fitness eliminator used to hold settings and such.
+        pub inner: FitnessEliminator<F, G, O>,
+
+        /// The context used to calculate divergence between genomes.
+        pub ctx: <G as Speciated>::Context,
+
+        _marker: std::marker::PhantomData<G>,
+    }
+
+    impl<F, G, O> ClearingEliminator<F, G, O>
+    where
+        F: FeatureBoundedFitnessFn<G>,
+        G: Speciated + FeatureBoundedGenome,
+        O: FeatureBoundedFitnessObserver<G>,
+    {
+        /// The fitness assigned to genomes that have been cleared from their niche.
+        pub const CLEARED_FITNESS: f32 = f32::NEG_INFINITY;
+
+        /// Creates a new [`ClearingEliminator`] with a given fitness function, niche settings, and keep threshold.
+        /// Panics if `capacity` is 0.
+        pub fn new(
+            fitness_fn: F,
+            clearing_radius: f32,
+            capacity: usize,
+            keep_threshold: f32,
+            observer: O,
+            ctx: <G as Speciated>::Context,
+        ) -> Self {
+            Self::from_fitness_eliminator(
+                FitnessEliminator::new(fitness_fn, keep_threshold, observer),
+                clearing_radius,
+                capacity,
+                ctx,
+            )
+        }
+
+        /// Creates a new [`ClearingEliminator`] from a regular [`FitnessEliminator`] and niche settings.
+        /// Panics if `capacity` is 0.
+        pub fn from_fitness_eliminator(
+            fitness_eliminator: FitnessEliminator<F, G, O>,
+            clearing_radius: f32,
+            capacity: usize,
+            ctx: <G as Speciated>::Context,
+        ) -> Self {
+            assert!(capacity > 0, "Niche capacity must be at least 1");
+            Self {
+                clearing_radius,
+                capacity,
+                inner: fitness_eliminator,
+                ctx,
+                _marker: std::marker::PhantomData,
+            }
+        }
+
+        #[cfg(not(feature = "rayon"))]
+        fn raw_fitnesses(&self, genomes: &[G]) -> Vec<f32> {
+            genomes
+                .iter()
+                .map(|g| self.inner.fitness_fn.fitness(g))
+                .collect()
         }
 
         #[cfg(feature = "rayon")]
-        fn eliminate(&mut self, genomes: Vec<G>) -> Vec<G> {
-            let (raw, divided) = self.calculate_fitnesses(&genomes);
+        fn raw_fitnesses(&self, genomes: &[G]) -> Vec<f32> {
+            let fitness_fn = &self.inner.fitness_fn;
+            genomes.par_iter().map(|g| fitness_fn.fitness(g)).collect()
+        }
 
-            let mut data: Vec<(G, f32, f32)> = genomes
-                .into_iter()
-                .enumerate()
-                .map(|(i, g)| (g, raw[i], divided[i]))
-                .collect();
+        /// Computes raw and cleared fitness for every genome.
+        ///
+        /// Returns `(raw_fitnesses, cleared_fitnesses)` where both vecs are indexed
+        /// the same way as `genomes`. The cleared value is used for elimination;
+        /// the raw value is what observers see.
+        fn calculate_fitnesses(&self, genomes: &[G]) -> (Vec<f32>, Vec<f32>) {
+            let raw = self.raw_fitnesses(genomes);
 
-            let median_index = (data.len() as f32) * self.inner.threshold;
+            let mut order: Vec<usize> = (0..genomes.len()).collect();
+            order.sort_by(|&a, &b| raw[b].partial_cmp(&raw[a]).unwrap());
 
-            // Sort by raw fitness so observer inputs are ordered by fitness descending.
-            data.sort_by(|(_, a, _), (_, b, _)| b.partial_cmp(a).unwrap());
+            let mut cleared = raw.clone();
+            for (pos, &i) in order.iter().enumerate() {
+                if cleared[i] == Self::CLEARED_FITNESS {
+                    continue;
+                }
 
-            // Split raw-sorted pairs for the observer while retaining divided values.
-            let (observer_pairs, divided_vals): (Vec<(G, f32)>, Vec<f32>) = data
-                .into_iter()
-                .map(|(g, raw, div)| ((g, raw), div))
-                .unzip();
+                // `i` is the best remaining genome in its niche, so it is always a winner.
+                let mut winners = 1;
+                for &j in &order[pos + 1..] {
+                    if cleared[j] == Self::CLEARED_FITNESS
+                        || genomes[i].divergence(&genomes[j], &self.ctx) >= self.clearing_radius
+                    {
+                        continue;
+                    }
+
+                    if winners < self.capacity {
+                        winners += 1;
+                    } else {
+                        cleared[j] = Self::CLEARED_FITNESS;
+                    }
+                }
+            }
+
+            (raw, cleared)
+        }
+
+        /// Calculates the fitness of each genome, clears the losers of each niche, and sorts them by fitness.
+        /// Returns a vector of tuples containing the genome and its cleared fitness score.
+        pub fn calculate_and_sort(&self, genomes: Vec<G>) -> Vec<(G, f32)> {
+            let (_, cleared) = self.calculate_fitnesses(&genomes);
+            let mut result: Vec<(G, f32)> = genomes.into_iter().zip(cleared).collect();
+            result.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+            result
+        }
+    }
+
+    impl<F, G, O> Eliminator<G> for ClearingEliminator<F, G, O>
+    where
+        F: FeatureBoundedFitnessFn<G>,
+        G: Speciated + FeatureBoundedGenome,
+        O: FeatureBoundedFitnessObserver<G>,
+    {
+        fn eliminate(&mut self, genomes: Vec<G>) -> Vec<G> {
+            self.eliminate_evaluated(genomes).genomes
+        }
+
+        /// The survivors are reported with their raw (uncleared) fitness.
+        fn eliminate_evaluated(&mut self, genomes: Vec<G>) -> EvaluatedPopulation<G> {
+            self.inner.fitness_fn.prepare(&genomes);
+            let (raw, cleared) = self.calculate_fitnesses(&genomes);
+
+            let mut data: Vec<((G, f32), f32)> =
+                genomes.into_iter().zip(raw).zip(cleared).collect();
+
+            let median_index = (data.len() as f32) * self.inner.threshold;
 
+            // Observers see raw fitness, sorted descending.
+            data.sort_by(|((_, a), _), ((_, b), _)| b.partial_cmp(a).unwrap());
+            let (observer_pairs, cleared_vals): (Vec<(G, f32)>, Vec<f32>) =
+                data.into_iter().unzip();
             self.inner.observer.observe(&observer_pairs);
 
-            // Re-sort by divided fitness and truncate for speciation-aware elimination.
-            let mut with_divided: Vec<_> = observer_pairs.into_iter().zip(divided_vals).collect();
-            with_divided.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
-            with_divided.truncate(median_index as usize + 1);
-            with_divided.into_par_iter().map(|((g, _), _)| g).collect()
+            // Re-sort by cleared fitness and truncate so that only niche winners are favored.
+            let mut with_cleared: Vec<_> = observer_pairs.into_iter().zip(cleared_vals).collect();
+            with_cleared.sort_by(|(_, a), (_, b)| b.partial_cmp(a).unwrap());
+            with_cleared.truncate(median_index as usize + 1);
+            let (genomes, fitness) = with_cleared.into_iter().map(|(pair, _)| pair).unzip();
+            EvaluatedPopulation::new(genomes).with_fitness(fitness)
         }
     }
 }
 
 #[cfg(feature = "speciation")]
 pub use speciation::*;
+
+#[cfg(feature = "lexicase")]
+mod lexicase {
+    use rand::{seq::SliceRandom, RngExt};
+
+    use super::*;
+
+    /// A trait for per-case fitness functions used by [`LexicaseEliminator`].
+    /// Instead of a single score, a genome is given one error per test case, where lower errors are better.
+    /// Any `Fn(&G) -> Vec<f32>` can be used as a case fitness function.
+    pub trait CaseFitnessFn<G> {
+        /// Evaluates a genome's error on every test case.
+        /// Every genome must be given the same number of cases, in the same order.
+        fn case_errors(&self, genome: &G) -> Vec<f32>;
+    }
+
+    impl<G, F> CaseFitnessFn<G> for F
+    where
+        F: Fn(&G) -> Vec<f32>,
+    {
+        fn case_errors(&self, genome: &G) -> Vec<f32> {
+            (self)(genome)
+        }
+    }
+
+    #[doc(hidden)]
+    #[cfg(not(feature = "rayon"))]
+    pub trait FeatureBoundedCaseFitnessFn<G>: CaseFitnessFn<G> {}
+    #[cfg(not(feature = "rayon"))]
+    impl<G, T: CaseFitnessFn<G>> FeatureBoundedCaseFitnessFn<G> for T {}
+
+    #[doc(hidden)]
+    #[cfg(feature = "rayon")]
+    pub trait FeatureBoundedCaseFitnessFn<G>: CaseFitnessFn<G> + Send + Sync {}
+    #[cfg(feature = "rayon")]
+    impl<G, T: CaseFitnessFn<G> + Send + Sync> FeatureBoundedCaseFitnessFn<G> for T {}
+
+    /// How much worse than the best error a genome can be on a case and still pass the lexicase filter.
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum LexicaseEpsilon {
+        /// Standard lexicase selection. Only genomes with exactly the best error on a case pass.
+        Exact,
+
+        /// Epsilon-lexicase selection with the same epsilon for every case.
+        Fixed(f32),
+
+        /// Epsilon-lexicase selection where each case's epsilon is the median absolute deviation
+        /// of the population's errors on that case. Works well on continuous errors without any tuning.
+        MedianAbsoluteDeviation,
+    }
+
+    /// Eliminator that performs (epsilon-)lexicase selection over per-case errors.
+    ///
+    /// Each selection event shuffles the test cases, then repeatedly filters the candidates down to the genomes
+    /// that perform best (within epsilon) on the next case until one genome is left or the cases run out.
+    /// Since each event is independent, a genome can be selected more than once, in which case it is cloned.
+    pub struct LexicaseEliminator<F: FeatureBoundedCaseFitnessFn<G>, G: FeatureBoundedGenome> {
+        /// The function that evaluates each genome's error on every test case.
+        pub case_fn: F,
+
+        /// The percentage of the population to select. Must be between 0.0 and 1.0.
+        pub threshold: f32,
+
+        /// The epsilon used when filtering candidates on each case.
+        pub epsilon: LexicaseEpsilon,
+
+        _marker: std::marker::PhantomData<G>,
+    }
+
+    impl<F, G> LexicaseEliminator<F, G>
+    where
+        F: FeatureBoundedCaseFitnessFn<G>,
+        G: FeatureBoundedGenome,
+    {
+        /// The default threshold for the [`LexicaseEliminator`]. Selects as many genomes as half the population.
+        pub const DEFAULT_THRESHOLD: f32 = 0.5;
+
+        /// Creates a new [`LexicaseEliminator`].
+        /// Panics if the threshold is not between 0.0 and 1.0.
+        pub fn new(case_fn: F, threshold: f32, epsilon: LexicaseEpsilon) -> Self {
+            if !(0.0..=1.0).contains(&threshold) {
+                panic!("Threshold must be between 0.0 and 1.0");
+            }
+            Self {
+                case_fn,
+                threshold,
+                epsilon,
+                _marker: std::marker::PhantomData,
+            }
+        }
+
+        /// Creates a new standard (non-epsilon) [`LexicaseEliminator`] with the default threshold.
+        pub fn new_with_default(case_fn: F) -> Self {
+            Self::new(case_fn, Self::DEFAULT_THRESHOLD, LexicaseEpsilon::Exact)
+        }
+
+        /// Calculates the errors of each genome on every case.
+        #[cfg(not(feature = "rayon"))]
+        pub fn calculate_errors(&self, genomes: &[G]) -> Vec<Vec<f32>> {
+            genomes
+                .iter()
+                .map(|g| self.case_fn.case_errors(g))
+                .collect()
+        }
+
+        /// Calculates the errors of each genome on every case.
+        #[cfg(feature = "rayon")]
+        pub fn calculate_errors(&self, genomes: &[G]) -> Vec<Vec<f32>> {
+            genomes
+                .par_iter()
+                .map(|g| self.case_fn.case_errors(g))
+                .collect()
+        }
+
+        /// Calculates the epsilon of every case from the population's errors.
+        pub fn case_epsilons(&self, errors: &[Vec<f32>]) -> Vec<f32> {
+            let cases = errors.first().map_or(0, |e| e.len());
+            match self.epsilon {
+                LexicaseEpsilon::Exact => vec![0.0; cases],
+                LexicaseEpsilon::Fixed(epsilon) => vec![epsilon; cases],
+                LexicaseEpsilon::MedianAbsoluteDeviation => (0..cases)
+                    .map(|case| {
+                        let mut column: Vec<f32> = errors.iter().map(|e| e[case]).collect();
+                        let center = median(&mut column);
+                        let mut deviations: Vec<f32> =
+                            column.iter().map(|e| (e - center).abs()).collect();
+                        median(&mut deviations)
+                    })
+                    .collect(),
+            }
+        }
+
+        /// Performs a single selection event, returning the index of the selected genome.
+        /// NaN errors count as infinitely bad. A case that would reject every remaining candidate is skipped.
+        /// Panics if `errors` is empty.
+        pub fn select(
+            &self,
+            errors: &[Vec<f32>],
+            epsilons: &[f32],
+            rng: &mut impl rand::Rng,
+        ) -> usize {
+            let mut candidates: Vec<usize> = (0..errors.len()).collect();
+            let mut cases: Vec<usize> = (0..epsilons.len()).collect();
+            cases.shuffle(rng);
+
+            for case in cases {
+                if candidates.len() <= 1 {
+                    break;
+                }
+
+                let error = |i: usize| {
+                    let e = errors[i][case];
+                    if e.is_nan() {
+                        f32::INFINITY
+                    } else {
+                        e
+                    }
+                };
+                let best = candidates
+                    .iter()
+                    .map(|&i| error(i))
+                    .fold(f32::INFINITY, f32::min);
+                let remaining: Vec<usize> = candidates
+                    .iter()
+                    .copied()
+                    .filter(|&i| error(i) <= best + epsilons[case])
+                    .collect();
+
+                // a NaN epsilon can still reject everyone
+                if !remaining.is_empty() {
+                    candidates = remaining;
+                }
+            }
+
+            candidates[rng.random_range(0..candidates.len())]
+        }
+    }
+
+    fn median(values: &mut [f32]) -> f32 {
+        values.sort_by(|a, b| a.total_cmp(b));
+        let mid = values.len() / 2;
+        if values.len().is_multiple_of(2) {
+            (values[mid - 1] + values[mid]) / 2.0
+        } else {
+            values[mid]
+        }
+    }
+
+    impl<F, G> Eliminator<G> for LexicaseEliminator<F, G>
+    where
+        F: FeatureBoundedCaseFitnessFn<G>,
+        G: FeatureBoundedGenome + Clone,
+    {
+        fn eliminate(&mut self, genomes: Vec<G>) -> Vec<G> {
+            self.eliminate_evaluated(genomes).genomes
+        }
+
+        /// The survivors are reported with the negated mean of their case errors as their fitness,
+        /// so lower errors mean higher fitness. NaN errors count as infinitely bad.
+        fn eliminate_evaluated(&mut self, genomes: Vec<G>) -> EvaluatedPopulation<G> {
+            if genomes.is_empty() {
+                return EvaluatedPopulation::new(genomes);
+            }
+
+            let errors = self.calculate_errors(&genomes);
+            assert!(
+                errors.iter().all(|e| e.len() == errors[0].len()),
+                "every genome must have an error for the same number of cases"
+            );
+            let epsilons = self.case_epsilons(&errors);
+            let count = ((genomes.len() as f32 * self.threshold) as usize + 1).min(genomes.len());
+
+            #[cfg(not(feature = "rayon"))]
+            let selected: Vec<usize> = {
+                let mut rng = rand::rng();
+                (0..count)
+                    .map(|_| self.select(&errors, &epsilons, &mut rng))
+                    .collect()
+            };
+
+            #[cfg(feature = "rayon")]
+            let selected: Vec<usize> = (0..count)
+                .into_par_iter()
+                .map(|_| self.select(&errors, &epsilons, &mut rand::rng()))
+                .collect();
+
+            let fitness = selected
+                .iter()
+                .map(|&i| {
+                    let total: f32 = errors[i]
+                        .iter()
+                        .map(|&e| if e.is_nan() { f32::INFINITY } else { e })
+                        .sum();
+                    -total / errors[i].len().max(1) as f32
+                })
+                .collect();
+            let survivors = selected.into_iter().map(|i| genomes[i].clone()).collect();
+            EvaluatedPopulation::new(survivors).with_fitness(fitness)
+        }
+    }
+}
+
+#[cfg(feature = "lexicase")]
+pub use lexicase::*;
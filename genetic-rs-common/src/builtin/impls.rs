@@ -0,0 +1,507 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    hash::{BuildHasher, Hash},
+};
+
+use rand::RngExt;
+
+use super::{
+    repopulator::{Mitosis, RandomlyMutable},
+    sampling::standard_normal,
+};
+
+#[cfg(feature = "crossover")]
+use super::repopulator::Crossover;
+
+#[cfg(feature = "genrand")]
+use crate::GenerateRandom;
+
+/// The context of `f32` and `f64`. Mutation adds Gaussian noise and clamps the result into `min..=max`.
+/// Mutation panics if `min` is greater than `max`, or if either is NaN.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FloatMutation<T> {
+    /// The standard deviation of the noise added by mutation.
+    pub sigma: T,
+
+    /// The smallest value mutation can produce.
+    pub min: T,
+
+    /// The largest value mutation can produce.
+    pub max: T,
+}
+
+/// The context of the integer types. Mutation moves the value up or down by at most `step`, staying within `min..=max`.
+/// Mutation panics if `min` is greater than `max`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntegerMutation<T> {
+    /// The largest distance a value can move.
+    pub step: T,
+
+    /// The smallest value mutation can produce.
+    pub min: T,
+
+    /// The largest value mutation can produce.
+    pub max: T,
+}
+
+#[cfg(feature = "crossover")]
+fn either<'a, T>(a: &'a T, b: &'a T, rng: &mut impl rand::Rng) -> &'a T {
+    if rng.random_bool(0.5) {
+        a
+    } else {
+        b
+    }
+}
+
+/// Implements [`Mitosis`] and [`Crossover`] for a scalar in terms of its [`RandomlyMutable`] implementation.
+macro_rules! impl_scalar_reproduction {
+    ($t:ty) => {
+        impl Mitosis for $t {
+            type Context = <$t as RandomlyMutable>::Context;
+
+            fn divide(&self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+                let mut child = *self;
+                child.mutate(ctx, rate, rng);
+                child
+            }
+        }
+
+        #[cfg(feature = "crossover")]
+        impl Crossover for $t {
+            type Context = <$t as RandomlyMutable>::Context;
+
+            fn crossover(
+                &self,
+                other: &Self,
+                ctx: &Self::Context,
+                rate: f32,
+                rng: &mut impl rand::Rng,
+            ) -> Self {
+                let mut child = *either(self, other, rng);
+                child.mutate(ctx, rate, rng);
+                child
+            }
+        }
+    };
+}
+
+macro_rules! impl_float {
+    ($($t:ty),*) => {$(
+        impl Default for FloatMutation<$t> {
+            fn default() -> Self {
+                Self {
+                    sigma: 0.1,
+                    min: <$t>::NEG_INFINITY,
+                    max: <$t>::INFINITY,
+                }
+            }
+        }
+
+        impl RandomlyMutable for $t {
+            type Context = FloatMutation<$t>;
+
+            fn mutate(&mut self, ctx: &FloatMutation<$t>, rate: f32, rng: &mut impl rand::Rng) {
+                assert!(
+                    ctx.min <= ctx.max,
+                    "FloatMutation min ({}) must not be greater than max ({}) or NaN",
+                    ctx.min,
+                    ctx.max
+                );
+                if rng.random_bool(rate.clamp(0.0, 1.0) as f64) {
+                    let noise = standard_normal(rng) as $t * ctx.sigma;
+                    *self = (*self + noise).clamp(ctx.min, ctx.max);
+                }
+            }
+        }
+
+        impl_scalar_reproduction!($t);
+
+        #[cfg(feature = "genrand")]
+        impl GenerateRandom for $t {
+            fn gen_random(rng: &mut impl rand::Rng) -> Self {
+                rng.random()
+            }
+        }
+    )*};
+}
+
+impl_float!(f32, f64);
+
+/// Each integer type is paired with a fixed-size type to sample with, since `rand` cannot sample `isize` and `usize` directly.
+macro_rules! impl_integer {
+    ($($t:ty as $sample:ty),*) => {$(
+        impl Default for IntegerMutation<$t> {
+            fn default() -> Self {
+                Self {
+                    step: 1,
+                    min: <$t>::MIN,
+                    max: <$t>::MAX,
+                }
+            }
+        }
+
+        impl RandomlyMutable for $t {
+            type Context = IntegerMutation<$t>;
+
+            fn mutate(&mut self, ctx: &IntegerMutation<$t>, rate: f32, rng: &mut impl rand::Rng) {
+                assert!(
+                    ctx.min <= ctx.max,
+                    "IntegerMutation min ({}) must not be greater than max ({})",
+                    ctx.min,
+                    ctx.max
+                );
+                if rng.random_bool(rate.clamp(0.0, 1.0) as f64) {
+                    let delta = rng.random_range(1..=ctx.step.max(1) as $sample) as $t;
+                    let moved = if rng.random_bool(0.5) {
+                        self.saturating_add(delta)
+                    } else {
+                        self.saturating_sub(delta)
+                    };
+                    *self = moved.clamp(ctx.min, ctx.max);
+                }
+            }
+        }
+
+        impl_scalar_reproduction!($t);
+
+        #[cfg(feature = "genrand")]
+        impl GenerateRandom for $t {
+            fn gen_random(rng: &mut impl rand::Rng) -> Self {
+                rng.random::<$sample>() as $t
+            }
+        }
+    )*};
+}
+
+impl_integer!(
+    i8 as i8,
+    i16 as i16,
+    i32 as i32,
+    i64 as i64,
+    i128 as i128,
+    isize as i64,
+    u8 as u8,
+    u16 as u16,
+    u32 as u32,
+    u64 as u64,
+    u128 as u128,
+    usize as u64
+);
+
+impl RandomlyMutable for bool {
+    type Context = ();
+
+    fn mutate(&mut self, _ctx: &(), rate: f32, rng: &mut impl rand::Rng) {
+        if rng.random_bool(rate.clamp(0.0, 1.0) as f64) {
+            *self = !*self;
+        }
+    }
+}
+
+impl_scalar_reproduction!(bool);
+
+#[cfg(feature = "genrand")]
+impl GenerateRandom for bool {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        rng.random()
+    }
+}
+
+impl<T: RandomlyMutable> RandomlyMutable for [T] {
+    type Context = T::Context;
+
+    fn mutate(&mut self, ctx: &T::Context, rate: f32, rng: &mut impl rand::Rng) {
+        for x in self {
+            x.mutate(ctx, rate, rng);
+        }
+    }
+}
+
+impl<T: RandomlyMutable, const N: usize> RandomlyMutable for [T; N] {
+    type Context = T::Context;
+
+    fn mutate(&mut self, ctx: &T::Context, rate: f32, rng: &mut impl rand::Rng) {
+        self.as_mut_slice().mutate(ctx, rate, rng);
+    }
+}
+
+impl<T: Mitosis, const N: usize> Mitosis for [T; N] {
+    type Context = T::Context;
+
+    fn divide(&self, ctx: &T::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        std::array::from_fn(|i| self[i].divide(ctx, rate, rng))
+    }
+}
+
+#[cfg(feature = "crossover")]
+impl<T: Crossover, const N: usize> Crossover for [T; N] {
+    type Context = T::Context;
+
+    /// Crosses each element with the element at the same position in `other`.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &T::Context,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        std::array::from_fn(|i| self[i].crossover(&other[i], ctx, rate, rng))
+    }
+}
+
+#[cfg(feature = "genrand")]
+impl<T: GenerateRandom, const N: usize> GenerateRandom for [T; N] {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        std::array::from_fn(|_| T::gen_random(rng))
+    }
+}
+
+impl<T: RandomlyMutable> RandomlyMutable for Vec<T> {
+    type Context = T::Context;
+
+    fn mutate(&mut self, ctx: &T::Context, rate: f32, rng: &mut impl rand::Rng) {
+        self.as_mut_slice().mutate(ctx, rate, rng);
+    }
+}
+
+#[cfg(feature = "crossover")]
+impl<T: Crossover> Crossover for Vec<T> {
+    type Context = T::Context;
+
+    /// Crosses each element with the element at the same position in `other`.
+    /// The child has the length of `self`, and elements past the end of `other` are cloned from `self`.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &T::Context,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let mut child: Vec<T> = self
+            .iter()
+            .zip(other)
+            .map(|(a, b)| a.crossover(b, ctx, rate, rng))
+            .collect();
+        child.extend_from_slice(&self[child.len()..]);
+        child
+    }
+}
+
+impl<T: RandomlyMutable> RandomlyMutable for Option<T> {
+    type Context = T::Context;
+
+    /// Mutates the value if there is one. Mutation never changes whether there is a value.
+    fn mutate(&mut self, ctx: &T::Context, rate: f32, rng: &mut impl rand::Rng) {
+        if let Some(x) = self {
+            x.mutate(ctx, rate, rng);
+        }
+    }
+}
+
+impl<T: Mitosis> Mitosis for Option<T> {
+    type Context = T::Context;
+
+    fn divide(&self, ctx: &T::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        self.as_ref().map(|x| x.divide(ctx, rate, rng))
+    }
+}
+
+#[cfg(feature = "crossover")]
+impl<T: Crossover> Crossover for Option<T> {
+    type Context = T::Context;
+
+    /// Crosses the values if both parents have one. Otherwise, clones either parent with equal probability.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &T::Context,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.crossover(b, ctx, rate, rng)),
+            _ => either(self, other, rng).clone(),
+        }
+    }
+}
+
+#[cfg(feature = "genrand")]
+impl<T: GenerateRandom> GenerateRandom for Option<T> {
+    /// `Some` or `None` with equal probability.
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        rng.random_bool(0.5).then(|| T::gen_random(rng))
+    }
+}
+
+macro_rules! impl_tuple {
+    ($($name:ident $idx:tt),+) => {
+        impl<$($name: RandomlyMutable),+> RandomlyMutable for ($($name,)+) {
+            type Context = ($(<$name as RandomlyMutable>::Context,)+);
+
+            fn mutate(&mut self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) {
+                $(self.$idx.mutate(&ctx.$idx, rate, rng);)+
+            }
+        }
+
+        impl<$($name: Mitosis),+> Mitosis for ($($name,)+) {
+            type Context = ($(<$name as Mitosis>::Context,)+);
+
+            fn divide(&self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+                ($(self.$idx.divide(&ctx.$idx, rate, rng),)+)
+            }
+        }
+
+        #[cfg(feature = "crossover")]
+        impl<$($name: Crossover),+> Crossover for ($($name,)+) {
+            type Context = ($(<$name as Crossover>::Context,)+);
+
+            fn crossover(
+                &self,
+                other: &Self,
+                ctx: &Self::Context,
+                rate: f32,
+                rng: &mut impl rand::Rng,
+            ) -> Self {
+                ($(self.$idx.crossover(&other.$idx, &ctx.$idx, rate, rng),)+)
+            }
+        }
+
+        #[cfg(feature = "genrand")]
+        impl<$($name: GenerateRandom),+> GenerateRandom for ($($name,)+) {
+            fn gen_random(rng: &mut impl rand::Rng) -> Self {
+                ($($name::gen_random(rng),)+)
+            }
+        }
+    };
+}
+
+impl_tuple!(A 0);
+impl_tuple!(A 0, B 1);
+impl_tuple!(A 0, B 1, C 2);
+impl_tuple!(A 0, B 1, C 2, D 3);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_tuple!(A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+impl<K, V: RandomlyMutable, S> RandomlyMutable for HashMap<K, V, S> {
+    type Context = V::Context;
+
+    /// Mutates every value. The keys never change.
+    fn mutate(&mut self, ctx: &V::Context, rate: f32, rng: &mut impl rand::Rng) {
+        for v in self.values_mut() {
+            v.mutate(ctx, rate, rng);
+        }
+    }
+}
+
+impl<K, V, S> Mitosis for HashMap<K, V, S>
+where
+    K: Clone + Eq + Hash,
+    V: Mitosis,
+    S: BuildHasher + Clone,
+{
+    type Context = V::Context;
+
+    fn divide(&self, ctx: &V::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = HashMap::with_capacity_and_hasher(self.len(), self.hasher().clone());
+        for (k, v) in self {
+            child.insert(k.clone(), v.divide(ctx, rate, rng));
+        }
+        child
+    }
+}
+
+#[cfg(feature = "crossover")]
+impl<K, V, S> Crossover for HashMap<K, V, S>
+where
+    K: Clone + Eq + Hash,
+    V: Crossover,
+    S: BuildHasher + Clone,
+{
+    type Context = V::Context;
+
+    /// Crosses the values of keys both parents have.
+    /// Keys only one parent has are cloned into the child with probability 0.5.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &V::Context,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let mut child = HashMap::with_hasher(self.hasher().clone());
+        for (k, v) in self {
+            match other.get(k) {
+                Some(w) => {
+                    child.insert(k.clone(), v.crossover(w, ctx, rate, rng));
+                }
+                None if rng.random_bool(0.5) => {
+                    child.insert(k.clone(), v.clone());
+                }
+                None => {}
+            }
+        }
+        for (k, w) in other {
+            if !self.contains_key(k) && rng.random_bool(0.5) {
+                child.insert(k.clone(), w.clone());
+            }
+        }
+        child
+    }
+}
+
+impl<K, V: RandomlyMutable> RandomlyMutable for BTreeMap<K, V> {
+    type Context = V::Context;
+
+    /// Mutates every value. The keys never change.
+    fn mutate(&mut self, ctx: &V::Context, rate: f32, rng: &mut impl rand::Rng) {
+        for v in self.values_mut() {
+            v.mutate(ctx, rate, rng);
+        }
+    }
+}
+
+impl<K: Clone + Ord, V: Mitosis> Mitosis for BTreeMap<K, V> {
+    type Context = V::Context;
+
+    fn divide(&self, ctx: &V::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        self.iter()
+            .map(|(k, v)| (k.clone(), v.divide(ctx, rate, rng)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "crossover")]
+impl<K: Clone + Ord, V: Crossover> Crossover for BTreeMap<K, V> {
+    type Context = V::Context;
+
+    /// Crosses the values of keys both parents have.
+    /// Keys only one parent has are cloned into the child with probability 0.5.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &V::Context,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let mut child = BTreeMap::new();
+        for (k, v) in self {
+            match other.get(k) {
+                Some(w) => {
+                    child.insert(k.clone(), v.crossover(w, ctx, rate, rng));
+                }
+                None if rng.random_bool(0.5) => {
+                    child.insert(k.clone(), v.clone());
+                }
+                None => {}
+            }
+        }
+        for (k, w) in other {
+            if !self.contains_key(k) && rng.random_bool(0.5) {
+                child.insert(k.clone(), w.clone());
+            }
+        }
+        child
+    }
+}
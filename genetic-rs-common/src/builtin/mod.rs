@@ -3,3 +3,33 @@ pub mod eliminator;
 
 /// Contains types implementing [`Repopulator`][crate::Repopulator]
 pub mod repopulator;
+
+/// Random sampling helpers shared by several algorithms.
+#[cfg(any(feature = "cmaes", feature = "genomes", feature = "impls"))]
+pub(crate) mod sampling;
+
+/// [`RandomlyMutable`][repopulator::RandomlyMutable], [`Mitosis`][repopulator::Mitosis], [`Crossover`][repopulator::Crossover] and [`GenerateRandom`][crate::GenerateRandom] for primitives, arrays, tuples and std collections,
+/// so the derive macros work on structs made of plain fields.
+///
+/// - Numbers mutate with probability equal to the mutation rate, by an amount configured with [`FloatMutation`][impls::FloatMutation] or [`IntegerMutation`][impls::IntegerMutation].
+///   Booleans flip with probability equal to the mutation rate.
+/// - Crossover of a number or boolean takes the value of either parent with equal probability, then mutates it.
+/// - Containers apply the operation to each element with the element's context. Tuples take a tuple of contexts.
+/// - Random floats are uniform in `[0, 1)`, and random integers are uniform over every value of their type.
+///
+/// `Vec` and the maps do not implement [`GenerateRandom`][crate::GenerateRandom], since their length is unknown and it would clash with
+/// [`GenerateRandomCollection`][crate::GenerateRandomCollection].
+///
+/// ```
+/// # use genetic_rs_common::prelude::*;
+/// let mut genome = (0.5f32, [3u8; 4], true);
+/// let ctx = (
+///     FloatMutation { sigma: 0.1, min: 0.0, max: 1.0 },
+///     IntegerMutation { step: 2, min: 0, max: 10 },
+///     (),
+/// );
+/// genome.mutate(&ctx, 0.5, &mut rand::rng());
+/// assert!((0.0..=1.0).contains(&genome.0));
+/// ```
+#[cfg(feature = "impls")]
+pub mod impls;
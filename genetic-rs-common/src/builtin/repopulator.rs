@@ -10,14 +10,27 @@ pub trait RandomlyMutable {
 }
 
 // TODO rayon version
-impl<'a, T: RandomlyMutable + 'a, I: Iterator<Item = &'a mut T>> RandomlyMutable for I {
-    type Context = T::Context;
+// implemented on concrete iterators rather than every `Iterator`, so containers can implement `RandomlyMutable` too.
+macro_rules! impl_iter_mut {
+    ($(<$($g:ident),*> $iter:ty),* $(,)?) => {$(
+        impl<'a, $($g,)* T: RandomlyMutable> RandomlyMutable for $iter {
+            type Context = T::Context;
 
-    fn mutate(&mut self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) {
-        self.for_each(|x| x.mutate(ctx, rate, rng));
-    }
+            fn mutate(&mut self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) {
+                self.for_each(|x| x.mutate(ctx, rate, rng));
+            }
+        }
+    )*};
 }
 
+impl_iter_mut!(
+    <> std::slice::IterMut<'a, T>,
+    <> std::collections::vec_deque::IterMut<'a, T>,
+    <> std::collections::linked_list::IterMut<'a, T>,
+    <K> std::collections::hash_map::ValuesMut<'a, K, T>,
+    <K> std::collections::btree_map::ValuesMut<'a, K, T>,
+);
+
 /// Used in dividually-reproducing [`Repopulator`]s
 pub trait Mitosis: Clone {
     /// Simulation-wide context required for this mitosis implementation.
@@ -109,6 +122,8 @@ impl<G: Mitosis> FromParent<G> for Vec<G> {
 mod crossover {
     use rand::RngExt;
 
+    use crate::EvaluatedPopulation;
+
     use super::*;
 
     /// Used in crossover-reproducing [`Repopulator`]s
@@ -127,43 +142,65 @@ mod crossover {
     }
 
     /// Repopulator that uses crossover reproduction to create new genomes.
-    pub struct CrossoverRepopulator<G: Crossover> {
+    /// Every survivor is used as the first parent in turn, and the second parent is chosen by a [`ParentSelector`].
+    pub struct CrossoverRepopulator<G: Crossover, S: ParentSelector<G> = UniformSelector> {
         /// The mutation rate to use when mutating genomes. 0.0 - 1.0
         pub mutation_rate: f32,
 
         /// Additional context for crossover/mutation.
         pub ctx: G::Context,
+
+        /// Chooses the second parent for each crossover.
+        pub selector: S,
         _marker: std::marker::PhantomData<G>,
     }
 
     impl<G: Crossover> CrossoverRepopulator<G> {
-        /// Creates a new [`CrossoverRepopulator`].
+        /// Creates a new [`CrossoverRepopulator`] that picks the second parent uniformly at random.
         pub fn new(mutation_rate: f32, ctx: G::Context) -> Self {
+            Self::new_with_selector(mutation_rate, ctx, UniformSelector)
+        }
+    }
+
+    impl<G: Crossover, S: ParentSelector<G>> CrossoverRepopulator<G, S> {
+        /// Creates a new [`CrossoverRepopulator`] with a custom [`ParentSelector`].
+        pub fn new_with_selector(mutation_rate: f32, ctx: G::Context, selector: S) -> Self {
             Self {
                 mutation_rate,
                 ctx,
+                selector,
                 _marker: std::marker::PhantomData,
             }
         }
-    }
 
-    impl<G> Repopulator<G> for CrossoverRepopulator<G>
-    where
-        G: Crossover,
-    {
-        fn repopulate(&mut self, genomes: &mut Vec<G>, target_size: usize) {
+        /// Replaces the [`ParentSelector`] of this repopulator.
+        pub fn with_selector<S2: ParentSelector<G>>(
+            self,
+            selector: S2,
+        ) -> CrossoverRepopulator<G, S2> {
+            CrossoverRepopulator::new_with_selector(self.mutation_rate, self.ctx, selector)
+        }
+
+        /// Same as [`Repopulator::repopulate`], but with the fitness of each survivor (in the same order as `genomes`)
+        /// available to the [`ParentSelector`]. When `fitness` is [`None`], selectors fall back to the order of the survivors.
+        pub fn repopulate_with_fitness(
+            &mut self,
+            genomes: &mut Vec<G>,
+            fitness: Option<&[f32]>,
+            target_size: usize,
+        ) {
             let mut rng = rand::rng();
             let champions = genomes.clone();
+            let candidates: Vec<usize> = (0..champions.len()).collect();
             let mut champs_cycle = champions.iter().enumerate().cycle();
 
             // TODO maybe rayonify
             while genomes.len() < target_size {
                 let (i, parent1) = champs_cycle.next().unwrap();
-                let mut j = rng.random_range(1..champions.len());
-                if i == j {
-                    j = 0;
-                }
-                let parent2 = &genomes[j];
+                let j = self
+                    .selector
+                    .select_mate(&champions, fitness, i, &candidates, &mut rng);
+                let parent2 = &champions[j];
 
                 let child = parent1.crossover(parent2, &self.ctx, self.mutation_rate, &mut rng);
 
@@ -172,6 +209,26 @@ mod crossover {
         }
     }
 
+    impl<G, S> Repopulator<G> for CrossoverRepopulator<G, S>
+    where
+        G: Crossover,
+        S: ParentSelector<G>,
+    {
+        fn repopulate(&mut self, genomes: &mut Vec<G>, target_size: usize) {
+            self.repopulate_with_fitness(genomes, None, target_size);
+        }
+
+        fn repopulate_evaluated(
+            &mut self,
+            population: EvaluatedPopulation<G>,
+            target_size: usize,
+        ) -> Vec<G> {
+            let mut genomes = population.genomes;
+            self.repopulate_with_fitness(&mut genomes, population.fitness.as_deref(), target_size);
+            genomes
+        }
+    }
+
     impl<G> Default for CrossoverRepopulator<G>
     where
         G: Crossover,
@@ -181,16 +238,570 @@ mod crossover {
             Self::new(0.05, G::Context::default())
         }
     }
+
+    /// Used in repopulators that combine more than two parents into each child,
+    /// such as gene-pool recombination or diagonal crossover.
+    pub trait NParentCrossover: Clone {
+        /// Simulation-wide context required for this crossover implementation.
+        type Context;
+
+        /// Use crossover reproduction to create a new genome from any number of parents.
+        /// `parents` is never empty.
+        fn crossover_n(
+            parents: &[&Self],
+            ctx: &Self::Context,
+            rate: f32,
+            rng: &mut impl rand::Rng,
+        ) -> Self;
+    }
+
+    /// Repopulator that creates each child from a fixed number of parents using [`NParentCrossover`].
+    /// Every survivor is used as the first parent in turn, and the rest are chosen by a [`ParentSelector`].
+    pub struct NParentCrossoverRepopulator<
+        G: NParentCrossover,
+        S: ParentSelector<G> = UniformSelector,
+    > {
+        /// The number of parents combined into each child. Must be at least 1.
+        pub parents_per_child: usize,
+
+        /// The mutation rate to use when mutating genomes. 0.0 - 1.0
+        pub mutation_rate: f32,
+
+        /// Additional context for crossover/mutation.
+        pub ctx: G::Context,
+
+        /// Chooses the parents after the first one.
+        pub selector: S,
+        _marker: std::marker::PhantomData<G>,
+    }
+
+    impl<G: NParentCrossover> NParentCrossoverRepopulator<G> {
+        /// Creates a new [`NParentCrossoverRepopulator`] that picks the extra parents uniformly at random.
+        /// Panics if `parents_per_child` is 0.
+        pub fn new(parents_per_child: usize, mutation_rate: f32, ctx: G::Context) -> Self {
+            Self::new_with_selector(parents_per_child, mutation_rate, ctx, UniformSelector)
+        }
+    }
+
+    impl<G: NParentCrossover, S: ParentSelector<G>> NParentCrossoverRepopulator<G, S> {
+        /// Creates a new [`NParentCrossoverRepopulator`] with a custom [`ParentSelector`].
+        /// Panics if `parents_per_child` is 0.
+        pub fn new_with_selector(
+            parents_per_child: usize,
+            mutation_rate: f32,
+            ctx: G::Context,
+            selector: S,
+        ) -> Self {
+            assert!(
+                parents_per_child > 0,
+                "Parents per child must be greater than 0"
+            );
+            Self {
+                parents_per_child,
+                mutation_rate,
+                ctx,
+                selector,
+                _marker: std::marker::PhantomData,
+            }
+        }
+
+        /// Same as [`Repopulator::repopulate`], but with the fitness of each survivor (in the same order as `genomes`)
+        /// available to the [`ParentSelector`]. When `fitness` is [`None`], selectors fall back to the order of the survivors.
+        ///
+        /// The parents of a child are distinct whenever there are enough survivors.
+        pub fn repopulate_with_fitness(
+            &mut self,
+            genomes: &mut Vec<G>,
+            fitness: Option<&[f32]>,
+            target_size: usize,
+        ) {
+            let mut rng = rand::rng();
+            let champions = genomes.clone();
+            let all: Vec<usize> = (0..champions.len()).collect();
+            let mut champs_cycle = (0..champions.len()).cycle();
+
+            // TODO maybe rayonify
+            while genomes.len() < target_size {
+                let first = champs_cycle.next().unwrap();
+                let mut chosen = vec![first];
+                while chosen.len() < self.parents_per_child {
+                    let mut candidates: Vec<usize> = all
+                        .iter()
+                        .copied()
+                        .filter(|c| !chosen.contains(c))
+                        .collect();
+                    if candidates.is_empty() {
+                        candidates = all.clone();
+                    }
+                    let mate = self.selector.select_mate(
+                        &champions,
+                        fitness,
+                        first,
+                        &candidates,
+                        &mut rng,
+                    );
+                    chosen.push(mate);
+                }
+
+                let parents: Vec<&G> = chosen.iter().map(|&i| &champions[i]).collect();
+                let child = G::crossover_n(&parents, &self.ctx, self.mutation_rate, &mut rng);
+                genomes.push(child);
+            }
+        }
+    }
+
+    impl<G, S> Repopulator<G> for NParentCrossoverRepopulator<G, S>
+    where
+        G: NParentCrossover,
+        S: ParentSelector<G>,
+    {
+        fn repopulate(&mut self, genomes: &mut Vec<G>, target_size: usize) {
+            self.repopulate_with_fitness(genomes, None, target_size);
+        }
+
+        fn repopulate_evaluated(
+            &mut self,
+            population: EvaluatedPopulation<G>,
+            target_size: usize,
+        ) -> Vec<G> {
+            let mut genomes = population.genomes;
+            self.repopulate_with_fitness(&mut genomes, population.fitness.as_deref(), target_size);
+            genomes
+        }
+    }
+
+    /// Counts how many children each operator of a [`HybridRepopulator`] produced.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct OperatorStats {
+        /// The number of children produced by crossover.
+        pub crossover: usize,
+
+        /// The number of children produced by mitosis.
+        pub mitosis: usize,
+
+        /// The number of crossover children that were additionally mutated.
+        pub mutated: usize,
+    }
+
+    impl OperatorStats {
+        /// The total number of children produced.
+        pub fn total(&self) -> usize {
+            self.crossover + self.mitosis
+        }
+
+        /// The fraction of children produced by crossover, or [`None`] if no children were produced.
+        pub fn crossover_fraction(&self) -> Option<f32> {
+            let total = self.total();
+            (total != 0).then(|| self.crossover as f32 / total as f32)
+        }
+    }
+
+    impl std::ops::AddAssign for OperatorStats {
+        fn add_assign(&mut self, other: Self) {
+            self.crossover += other.crossover;
+            self.mitosis += other.mitosis;
+            self.mutated += other.mutated;
+        }
+    }
+
+    /// Repopulator that produces each child by crossover with a given probability and by mitosis otherwise.
+    /// Both operators (and the optional extra mutation of crossover children) share the same context.
+    /// Every survivor is used as the first parent in turn, and the mate for crossover is chosen by a [`ParentSelector`].
+    pub struct HybridRepopulator<G, S = UniformSelector>
+    where
+        G: Crossover + Mitosis<Context = <G as Crossover>::Context>,
+        S: ParentSelector<G>,
+    {
+        /// The probability of producing a child by crossover instead of mitosis. 0.0 - 1.0
+        pub crossover_probability: f32,
+
+        /// The mutation rate to use when mutating genomes. 0.0 - 1.0
+        pub mutation_rate: f32,
+
+        /// Whether crossover children are also mutated with [`RandomlyMutable::mutate`] after being created.
+        pub mutate_crossover_children: bool,
+
+        /// Additional context for crossover/mitosis/mutation.
+        pub ctx: <G as Crossover>::Context,
+
+        /// Chooses the second parent for each crossover.
+        pub selector: S,
+
+        /// The operator counts from the most recent generation.
+        pub last_stats: OperatorStats,
+
+        /// The operator counts accumulated over every generation.
+        pub total_stats: OperatorStats,
+
+        _marker: std::marker::PhantomData<G>,
+    }
+
+    impl<G> HybridRepopulator<G>
+    where
+        G: Crossover + Mitosis<Context = <G as Crossover>::Context>,
+    {
+        /// Creates a new [`HybridRepopulator`] that picks the mate uniformly at random.
+        /// Panics if the crossover probability is not between 0.0 and 1.0.
+        pub fn new(
+            crossover_probability: f32,
+            mutation_rate: f32,
+            mutate_crossover_children: bool,
+            ctx: <G as Crossover>::Context,
+        ) -> Self {
+            Self::new_with_selector(
+                crossover_probability,
+                mutation_rate,
+                mutate_crossover_children,
+                ctx,
+                UniformSelector,
+            )
+        }
+    }
+
+    impl<G, S> HybridRepopulator<G, S>
+    where
+        G: Crossover + Mitosis<Context = <G as Crossover>::Context>,
+        S: ParentSelector<G>,
+    {
+        /// Creates a new [`HybridRepopulator`] with a custom [`ParentSelector`].
+        /// Panics if the crossover probability is not between 0.0 and 1.0.
+        pub fn new_with_selector(
+            crossover_probability: f32,
+            mutation_rate: f32,
+            mutate_crossover_children: bool,
+            ctx: <G as Crossover>::Context,
+            selector: S,
+        ) -> Self {
+            if !(0.0..=1.0).contains(&crossover_probability) {
+                panic!("Crossover probability must be between 0.0 and 1.0");
+            }
+            Self {
+                crossover_probability,
+                mutation_rate,
+                mutate_crossover_children,
+                ctx,
+                selector,
+                last_stats: OperatorStats::default(),
+                total_stats: OperatorStats::default(),
+                _marker: std::marker::PhantomData,
+            }
+        }
+
+        /// Replaces the [`ParentSelector`] of this repopulator. The operator counts are kept.
+        pub fn with_selector<S2: ParentSelector<G>>(
+            self,
+            selector: S2,
+        ) -> HybridRepopulator<G, S2> {
+            HybridRepopulator {
+                crossover_probability: self.crossover_probability,
+                mutation_rate: self.mutation_rate,
+                mutate_crossover_children: self.mutate_crossover_children,
+                ctx: self.ctx,
+                selector,
+                last_stats: self.last_stats,
+                total_stats: self.total_stats,
+                _marker: std::marker::PhantomData,
+            }
+        }
+
+        /// Resets both the most recent and the accumulated operator counts.
+        pub fn reset_stats(&mut self) {
+            self.last_stats = OperatorStats::default();
+            self.total_stats = OperatorStats::default();
+        }
+    }
+
+    impl<G, S> HybridRepopulator<G, S>
+    where
+        G: Crossover
+            + Mitosis<Context = <G as Crossover>::Context>
+            + RandomlyMutable<Context = <G as Crossover>::Context>,
+        S: ParentSelector<G>,
+    {
+        /// Same as [`Repopulator::repopulate`], but with the fitness of each survivor (in the same order as `genomes`)
+        /// available to the [`ParentSelector`]. When `fitness` is [`None`], selectors fall back to the order of the survivors.
+        pub fn repopulate_with_fitness(
+            &mut self,
+            genomes: &mut Vec<G>,
+            fitness: Option<&[f32]>,
+            target_size: usize,
+        ) {
+            let mut rng = rand::rng();
+            let champions = genomes.clone();
+            let candidates: Vec<usize> = (0..champions.len()).collect();
+            let mut champs_cycle = champions.iter().enumerate().cycle();
+            let mut stats = OperatorStats::default();
+
+            // TODO maybe rayonify
+            while genomes.len() < target_size {
+                let (i, parent1) = champs_cycle.next().unwrap();
+
+                // crossover needs two distinct parents, so a single champion always uses mitosis.
+                if champions.len() < 2 || !rng.random_bool(self.crossover_probability as f64) {
+                    genomes.push(parent1.divide(&self.ctx, self.mutation_rate, &mut rng));
+                    stats.mitosis += 1;
+                    continue;
+                }
+
+                let j = self
+                    .selector
+                    .select_mate(&champions, fitness, i, &candidates, &mut rng);
+                let parent2 = &champions[j];
+
+                let mut child = parent1.crossover(parent2, &self.ctx, self.mutation_rate, &mut rng);
+                if self.mutate_crossover_children {
+                    child.mutate(&self.ctx, self.mutation_rate, &mut rng);
+                    stats.mutated += 1;
+                }
+                genomes.push(child);
+                stats.crossover += 1;
+            }
+
+            self.last_stats = stats;
+            self.total_stats += stats;
+        }
+    }
+
+    impl<G, S> Repopulator<G> for HybridRepopulator<G, S>
+    where
+        G: Crossover
+            + Mitosis<Context = <G as Crossover>::Context>
+            + RandomlyMutable<Context = <G as Crossover>::Context>,
+        S: ParentSelector<G>,
+    {
+        fn repopulate(&mut self, genomes: &mut Vec<G>, target_size: usize) {
+            self.repopulate_with_fitness(genomes, None, target_size);
+        }
+
+        fn repopulate_evaluated(
+            &mut self,
+            population: EvaluatedPopulation<G>,
+            target_size: usize,
+        ) -> Vec<G> {
+            let mut genomes = population.genomes;
+            self.repopulate_with_fitness(&mut genomes, population.fitness.as_deref(), target_size);
+            genomes
+        }
+    }
 }
 
 #[cfg(feature = "crossover")]
 pub use crossover::*;
 
-#[cfg(feature = "speciation")]
-mod speciation {
+#[cfg(feature = "crossover")]
+mod selection {
     use rand::RngExt;
 
-    use crate::speciation::{Speciated, SpeciatedPopulation};
+    /// Chooses the second parent (the mate) for a crossover.
+    pub trait ParentSelector<G> {
+        /// Picks a mate for `genomes[first]` from `candidates`, a list of indices into `genomes`.
+        /// `fitness`, when available, holds the fitness of every genome in `genomes`.
+        /// Implementations should avoid returning `first` unless it is the only candidate.
+        fn select_mate(
+            &mut self,
+            genomes: &[G],
+            fitness: Option<&[f32]>,
+            first: usize,
+            candidates: &[usize],
+            rng: &mut impl rand::Rng,
+        ) -> usize;
+    }
+
+    /// The candidates other than `first`, or just `first` if there are no others.
+    fn mates(first: usize, candidates: &[usize]) -> Vec<usize> {
+        let others: Vec<usize> = candidates.iter().copied().filter(|&c| c != first).collect();
+        if others.is_empty() {
+            vec![first]
+        } else {
+            others
+        }
+    }
+
+    /// The fitness of a genome, or a score based on its position when no fitness is available.
+    /// The builtin eliminators return survivors sorted by descending fitness, so earlier genomes score higher.
+    fn score(fitness: Option<&[f32]>, genomes_len: usize, i: usize) -> f32 {
+        match fitness {
+            Some(fitness) => fitness[i],
+            None => (genomes_len - i) as f32,
+        }
+    }
+
+    /// Picks the mate uniformly at random.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct UniformSelector;
+
+    impl<G> ParentSelector<G> for UniformSelector {
+        fn select_mate(
+            &mut self,
+            _genomes: &[G],
+            _fitness: Option<&[f32]>,
+            first: usize,
+            candidates: &[usize],
+            rng: &mut impl rand::Rng,
+        ) -> usize {
+            let mates = mates(first, candidates);
+            mates[rng.random_range(0..mates.len())]
+        }
+    }
+
+    /// Picks the mate with a probability proportional to its fitness (roulette wheel selection).
+    /// Fitness values are shifted so the least fit candidate still has a small chance of being picked.
+    /// Infinite and NaN fitness values are clamped to the finite range of the other candidates, and the mate is
+    /// picked uniformly if no candidate has a finite fitness.
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct FitnessWeightedSelector;
+
+    impl<G> ParentSelector<G> for FitnessWeightedSelector {
+        fn select_mate(
+            &mut self,
+            genomes: &[G],
+            fitness: Option<&[f32]>,
+            first: usize,
+            candidates: &[usize],
+            rng: &mut impl rand::Rng,
+        ) -> usize {
+            let mates = mates(first, candidates);
+            let scores: Vec<f32> = mates
+                .iter()
+                .map(|&m| score(fitness, genomes.len(), m))
+                .collect();
+
+            let finite = scores.iter().copied().filter(|s| s.is_finite());
+            let min = finite.clone().fold(f32::INFINITY, f32::min);
+            let max = finite.fold(f32::NEG_INFINITY, f32::max);
+
+            // weights are summed as f64, since the spread of f32 scores can overflow f32.
+            let weights: Vec<f64> = scores
+                .iter()
+                .map(|&s| {
+                    let s = if s == f32::INFINITY { max } else { s };
+                    let s = if s.is_nan() { min } else { s.max(min) };
+                    (s as f64 - min as f64) + f32::EPSILON as f64
+                })
+                .collect();
+            let total: f64 = weights.iter().sum();
+            if !(total.is_finite() && total > 0.0) {
+                return mates[rng.random_range(0..mates.len())];
+            }
+
+            let mut remaining = rng.random_range(0.0..total);
+            for (m, w) in mates.iter().zip(&weights) {
+                if remaining < *w {
+                    return *m;
+                }
+                remaining -= w;
+            }
+            *mates.last().unwrap()
+        }
+    }
+
+    /// Picks `size` random candidates and uses the fittest of them as the mate.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct TournamentSelector {
+        /// The number of candidates in each tournament.
+        pub size: usize,
+    }
+
+    impl TournamentSelector {
+        /// Creates a new [`TournamentSelector`]. Panics if `size` is 0.
+        pub fn new(size: usize) -> Self {
+            assert!(size > 0, "Tournament size must be greater than 0");
+            Self { size }
+        }
+    }
+
+    impl Default for TournamentSelector {
+        fn default() -> Self {
+            Self::new(3)
+        }
+    }
+
+    impl<G> ParentSelector<G> for TournamentSelector {
+        fn select_mate(
+            &mut self,
+            genomes: &[G],
+            fitness: Option<&[f32]>,
+            first: usize,
+            candidates: &[usize],
+            rng: &mut impl rand::Rng,
+        ) -> usize {
+            let mates = mates(first, candidates);
+            (0..self.size)
+                .map(|_| mates[rng.random_range(0..mates.len())])
+                .max_by(|&a, &b| {
+                    score(fitness, genomes.len(), a).total_cmp(&score(fitness, genomes.len(), b))
+                })
+                .unwrap()
+        }
+    }
+
+    #[cfg(feature = "speciation")]
+    mod assortative {
+        use crate::speciation::Speciated;
+
+        use super::*;
+
+        /// Whether an [`AssortativeSelector`] prefers similar or dissimilar mates.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum Assortment {
+            /// Pick the mate with the lowest [`Speciated::divergence`] from the first parent.
+            MostSimilar,
+
+            /// Pick the mate with the highest [`Speciated::divergence`] from the first parent.
+            MostDissimilar,
+        }
+
+        /// Picks the mate that is most similar or most dissimilar to the first parent, as measured by [`Speciated::divergence`].
+        pub struct AssortativeSelector<G: Speciated> {
+            /// Whether similar or dissimilar mates are preferred.
+            pub assortment: Assortment,
+
+            /// Additional context for speciation.
+            pub ctx: G::Context,
+        }
+
+        impl<G: Speciated> AssortativeSelector<G> {
+            /// Creates a new [`AssortativeSelector`].
+            pub fn new(assortment: Assortment, ctx: G::Context) -> Self {
+                Self { assortment, ctx }
+            }
+        }
+
+        impl<G: Speciated> ParentSelector<G> for AssortativeSelector<G> {
+            fn select_mate(
+                &mut self,
+                genomes: &[G],
+                _fitness: Option<&[f32]>,
+                first: usize,
+                candidates: &[usize],
+                _rng: &mut impl rand::Rng,
+            ) -> usize {
+                let mates = mates(first, candidates);
+                let divergence = |&m: &usize| genomes[first].divergence(&genomes[m], &self.ctx);
+                let by_divergence = |a: &usize, b: &usize| divergence(a).total_cmp(&divergence(b));
+
+                let mate = match self.assortment {
+                    Assortment::MostSimilar => mates.iter().min_by(|a, b| by_divergence(a, b)),
+                    Assortment::MostDissimilar => mates.iter().max_by(|a, b| by_divergence(a, b)),
+                };
+                *mate.unwrap()
+            }
+        }
+    }
+
+    #[cfg(feature = "speciation")]
+    pub use assortative::*;
+}
+
+#[cfg(feature = "crossover")]
+pub use selection::*;
+
+#[cfg(feature = "speciation")]
+mod speciation {
+    use crate::{
+        speciation::{Speciated, SpeciatedPopulation},
+        EvaluatedPopulation,
+    };
 
     use super::*;
 
@@ -217,9 +828,13 @@ mod speciation {
     }
 
     /// Repopulator that uses crossover reproduction to create new genomes, but only between genomes of the same species.
-    pub struct SpeciatedCrossoverRepopulator<G: Crossover + Speciated> {
+    /// The mate is chosen from the first parent's species by the inner repopulator's [`ParentSelector`].
+    pub struct SpeciatedCrossoverRepopulator<
+        G: Crossover + Speciated,
+        S: ParentSelector<G> = UniformSelector,
+    > {
         /// The inner crossover repopulator. This holds the settings for crossover operations.
-        pub inner: CrossoverRepopulator<G>,
+        pub inner: CrossoverRepopulator<G, S>,
 
         /// The threshold used to determine if a genome belongs in a species.
         /// See [`SpeciatedPopulation::threshold`] for more info.
@@ -251,10 +866,13 @@ mod speciation {
                 _marker: std::marker::PhantomData,
             }
         }
+    }
 
-        /// Creates a new [`SpeciatedCrossoverRepopulator`] from an existing [`CrossoverRepopulator`], using the same mutation settings.
+    impl<G: Crossover + Speciated, S: ParentSelector<G>> SpeciatedCrossoverRepopulator<G, S> {
+        /// Creates a new [`SpeciatedCrossoverRepopulator`] from an existing [`CrossoverRepopulator`], using the same mutation settings
+        /// and [`ParentSelector`].
         pub fn from_crossover(
-            inner: CrossoverRepopulator<G>,
+            inner: CrossoverRepopulator<G, S>,
             threshold: f32,
             action_if_isolated: ActionIfIsolated,
             ctx: <G as Speciated>::Context,
@@ -267,13 +885,15 @@ mod speciation {
                 _marker: std::marker::PhantomData,
             }
         }
-    }
 
-    impl<G> Repopulator<G> for SpeciatedCrossoverRepopulator<G>
-    where
-        G: Crossover + Speciated,
-    {
-        fn repopulate(&mut self, genomes: &mut Vec<G>, target_size: usize) {
+        /// Same as [`Repopulator::repopulate`], but with the fitness of each survivor (in the same order as `genomes`)
+        /// available to the [`ParentSelector`]. When `fitness` is [`None`], selectors fall back to the order of the survivors.
+        pub fn repopulate_with_fitness(
+            &mut self,
+            genomes: &mut Vec<G>,
+            fitness: Option<&[f32]>,
+            target_size: usize,
+        ) {
             let initial_size = genomes.len();
             if initial_size >= target_size {
                 return;
@@ -289,11 +909,13 @@ mod speciation {
                 || (matches!(self.action_if_isolated, ActionIfIsolated::CrossoverRandom)
                     && initial_size == 1)
             {
-                self.inner.repopulate(genomes, target_size);
+                self.inner
+                    .repopulate_with_fitness(genomes, fitness, target_size);
                 return;
             }
 
             let amount_to_make = target_size - initial_size;
+            let everyone: Vec<usize> = (0..initial_size).collect();
             let mut species_cycle = population.round_robin_enumerate();
 
             let mut i = 0;
@@ -331,8 +953,14 @@ mod speciation {
                             }
 
                             let best_species = &population.species()[best_species_i];
-                            let j = rng.random_range(0..best_species.len());
-                            let parent2 = &genomes[best_species[j]];
+                            let j = self.inner.selector.select_mate(
+                                &genomes[..initial_size],
+                                fitness,
+                                genome_i,
+                                best_species,
+                                &mut rng,
+                            );
+                            let parent2 = &genomes[j];
                             let child = parent1.crossover(
                                 parent2,
                                 &self.inner.ctx,
@@ -344,11 +972,13 @@ mod speciation {
                             continue;
                         }
                         ActionIfIsolated::CrossoverRandom => {
-                            let mut j = rng.random_range(1..initial_size);
-                            if j == genome_i {
-                                j = 0;
-                            }
-
+                            let j = self.inner.selector.select_mate(
+                                &genomes[..initial_size],
+                                fitness,
+                                genome_i,
+                                &everyone,
+                                &mut rng,
+                            );
                             let parent2 = &genomes[j];
                             let child = parent1.crossover(
                                 parent2,
@@ -363,11 +993,14 @@ mod speciation {
                     }
                 }
 
-                let mut j = rng.random_range(1..species.len());
-                if genome_i == species[j] {
-                    j = 0;
-                }
-                let parent2 = &genomes[species[j]];
+                let j = self.inner.selector.select_mate(
+                    &genomes[..initial_size],
+                    fitness,
+                    genome_i,
+                    species,
+                    &mut rng,
+                );
+                let parent2 = &genomes[j];
 
                 let child =
                     parent1.crossover(parent2, &self.inner.ctx, self.inner.mutation_rate, &mut rng);
@@ -378,6 +1011,26 @@ mod speciation {
         }
     }
 
+    impl<G, S> Repopulator<G> for SpeciatedCrossoverRepopulator<G, S>
+    where
+        G: Crossover + Speciated,
+        S: ParentSelector<G>,
+    {
+        fn repopulate(&mut self, genomes: &mut Vec<G>, target_size: usize) {
+            self.repopulate_with_fitness(genomes, None, target_size);
+        }
+
+        fn repopulate_evaluated(
+            &mut self,
+            population: EvaluatedPopulation<G>,
+            target_size: usize,
+        ) -> Vec<G> {
+            let mut genomes = population.genomes;
+            self.repopulate_with_fitness(&mut genomes, population.fitness.as_deref(), target_size);
+            genomes
+        }
+    }
+
     impl<G> Default for SpeciatedCrossoverRepopulator<G>
     where
         G: Crossover + Speciated,
@@ -397,3 +1050,155 @@ mod speciation {
 
 #[cfg(feature = "speciation")]
 pub use speciation::*;
+
+#[cfg(feature = "genrand")]
+mod immigrants {
+    use crate::{EvaluatedPopulation, FeatureBoundedRepopulator, GenerateRandom};
+
+    use super::*;
+
+    /// Measures how diverse a population is. Any `Fn(&[G]) -> f32` can be used as a diversity measure.
+    /// Higher values should mean a more diverse population.
+    pub trait DiversityMeasure<G> {
+        /// Computes the diversity of the population.
+        fn diversity(&self, genomes: &[G]) -> f32;
+    }
+
+    impl<G, F> DiversityMeasure<G> for F
+    where
+        F: Fn(&[G]) -> f32,
+    {
+        fn diversity(&self, genomes: &[G]) -> f32 {
+            (self)(genomes)
+        }
+    }
+
+    /// Decides when a [`RandomImmigrantsRepopulator`] adds random immigrants.
+    pub enum ImmigrationTrigger<D> {
+        /// Add immigrants every generation.
+        Always,
+
+        /// Add immigrants only in generations where the diversity of the survivors is below the threshold.
+        DiversityBelow {
+            /// The diversity measure applied to the survivors.
+            measure: D,
+
+            /// The diversity below which immigrants are added.
+            threshold: f32,
+        },
+    }
+
+    /// A repopulator that fills a fraction of each new generation with random genomes and the rest with an inner repopulator.
+    /// This is a simple way to keep fresh genetic material flowing into a population that is stagnating.
+    /// The survivors are always kept, so fewer immigrants are added if the survivors alone exceed the rest of the population.
+    pub struct RandomImmigrantsRepopulator<
+        R: FeatureBoundedRepopulator<G>,
+        G: GenerateRandom,
+        D: DiversityMeasure<G> = fn(&[G]) -> f32,
+    > {
+        /// The repopulator that creates the rest of the population.
+        pub inner: R,
+
+        /// The fraction of the target population that is replaced by random immigrants. Must be between 0.0 and 1.0.
+        pub fraction: f32,
+
+        /// Decides in which generations immigrants are added.
+        pub trigger: ImmigrationTrigger<D>,
+
+        /// The diversity measured in the most recent generation, if the trigger measures diversity.
+        pub last_diversity: Option<f32>,
+
+        _marker: std::marker::PhantomData<G>,
+    }
+
+    impl<R, G> RandomImmigrantsRepopulator<R, G>
+    where
+        R: FeatureBoundedRepopulator<G>,
+        G: GenerateRandom,
+    {
+        /// Creates a new [`RandomImmigrantsRepopulator`] that adds immigrants every generation.
+        /// Panics if the fraction is not between 0.0 and 1.0.
+        pub fn new(inner: R, fraction: f32) -> Self {
+            Self::with_trigger(inner, fraction, ImmigrationTrigger::Always)
+        }
+    }
+
+    impl<R, G, D> RandomImmigrantsRepopulator<R, G, D>
+    where
+        R: FeatureBoundedRepopulator<G>,
+        G: GenerateRandom,
+        D: DiversityMeasure<G>,
+    {
+        /// Creates a new [`RandomImmigrantsRepopulator`] with a given trigger.
+        /// Panics if the fraction is not between 0.0 and 1.0.
+        pub fn with_trigger(inner: R, fraction: f32, trigger: ImmigrationTrigger<D>) -> Self {
+            if !(0.0..=1.0).contains(&fraction) {
+                panic!("Fraction must be between 0.0 and 1.0");
+            }
+            Self {
+                inner,
+                fraction,
+                trigger,
+                last_diversity: None,
+                _marker: std::marker::PhantomData,
+            }
+        }
+
+        /// Creates a new [`RandomImmigrantsRepopulator`] that only adds immigrants when the diversity of the survivors
+        /// drops below `threshold`.
+        /// Panics if the fraction is not between 0.0 and 1.0.
+        pub fn on_low_diversity(inner: R, fraction: f32, measure: D, threshold: f32) -> Self {
+            Self::with_trigger(
+                inner,
+                fraction,
+                ImmigrationTrigger::DiversityBelow { measure, threshold },
+            )
+        }
+
+        fn should_immigrate(&mut self, genomes: &[G]) -> bool {
+            match &self.trigger {
+                ImmigrationTrigger::Always => true,
+                ImmigrationTrigger::DiversityBelow { measure, threshold } => {
+                    let diversity = measure.diversity(genomes);
+                    self.last_diversity = Some(diversity);
+                    diversity < *threshold
+                }
+            }
+        }
+    }
+
+    impl<R, G, D> Repopulator<G> for RandomImmigrantsRepopulator<R, G, D>
+    where
+        R: FeatureBoundedRepopulator<G>,
+        G: GenerateRandom,
+        D: DiversityMeasure<G>,
+    {
+        fn repopulate(&mut self, genomes: &mut Vec<G>, target_size: usize) {
+            let population = EvaluatedPopulation::new(std::mem::take(genomes));
+            *genomes = self.repopulate_evaluated(population, target_size);
+        }
+
+        fn repopulate_evaluated(
+            &mut self,
+            population: EvaluatedPopulation<G>,
+            target_size: usize,
+        ) -> Vec<G> {
+            if !self.should_immigrate(&population.genomes) {
+                return self.inner.repopulate_evaluated(population, target_size);
+            }
+
+            let immigrants = (target_size as f32 * self.fraction).round() as usize;
+            let inner_target = target_size.saturating_sub(immigrants).max(population.len());
+            let mut genomes = self.inner.repopulate_evaluated(population, inner_target);
+
+            let mut rng = rand::rng();
+            while genomes.len() < target_size {
+                genomes.push(G::gen_random(&mut rng));
+            }
+            genomes
+        }
+    }
+}
+
+#[cfg(feature = "genrand")]
+pub use immigrants::*;
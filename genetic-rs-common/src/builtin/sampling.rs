@@ -0,0 +1,8 @@
+use rand::RngExt;
+
+/// Samples from the standard normal distribution using the Box-Muller transform.
+pub(crate) fn standard_normal(rng: &mut impl rand::Rng) -> f64 {
+    let u1 = 1.0 - rng.random::<f64>();
+    let u2: f64 = rng.random();
+    (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+}
@@ -0,0 +1,597 @@
+use std::collections::VecDeque;
+
+use crate::{
+    builtin::{
+        eliminator::{FeatureBoundedFitnessFn, FeatureBoundedFitnessObserver},
+        sampling::standard_normal,
+    },
+    FeatureBoundedGenome, RealParameters,
+};
+
+use rand::RngExt;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// What a [`CmaEs`] optimizer does once a run has converged or stalled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RestartStrategy {
+    /// Stop searching after the first run.
+    None,
+
+    /// IPOP-CMA-ES. Every restart multiplies the population size by `increase` (usually 2.0).
+    Ipop {
+        /// The factor the population size grows by on each restart.
+        increase: f64,
+    },
+
+    /// BIPOP-CMA-ES. Alternates between the increasing population sizes of IPOP and runs with
+    /// small, randomly sized populations and step sizes, giving both regimes a similar evaluation budget.
+    Bipop {
+        /// The factor the large population size grows by on each large restart.
+        increase: f64,
+    },
+}
+
+/// The criteria that end a run of a [`CmaEs`] optimizer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CmaTermination {
+    /// The run ends when the step size in every coordinate falls below this value.
+    pub tol_x: f64,
+
+    /// The run ends when the best fitness of recent generations varies by no more than this value.
+    pub tol_fun: f64,
+
+    /// The run ends when the condition number of the covariance matrix exceeds this value.
+    pub max_condition: f64,
+}
+
+impl Default for CmaTermination {
+    fn default() -> Self {
+        Self {
+            tol_x: 1e-12,
+            tol_fun: 1e-12,
+            max_condition: 1e14,
+        }
+    }
+}
+
+/// The strategy parameters that depend on the dimension and population size.
+#[derive(Clone, Debug)]
+struct Coefficients {
+    weights: Vec<f64>,
+    mueff: f64,
+    cc: f64,
+    cs: f64,
+    c1: f64,
+    cmu: f64,
+    damps: f64,
+    chi_n: f64,
+}
+
+impl Coefficients {
+    fn new(n: usize, lambda: usize) -> Self {
+        let nf = n as f64;
+        let mu = lambda / 2;
+
+        let raw: Vec<f64> = (1..=mu)
+            .map(|i| (mu as f64 + 0.5).ln() - (i as f64).ln())
+            .collect();
+        let sum: f64 = raw.iter().sum();
+        let weights: Vec<f64> = raw.iter().map(|w| w / sum).collect();
+        let mueff = 1.0 / weights.iter().map(|w| w * w).sum::<f64>();
+
+        let cc = (4.0 + mueff / nf) / (nf + 4.0 + 2.0 * mueff / nf);
+        let cs = (mueff + 2.0) / (nf + mueff + 5.0);
+        let c1 = 2.0 / ((nf + 1.3).powi(2) + mueff);
+        let cmu = (1.0 - c1).min(2.0 * (mueff - 2.0 + 1.0 / mueff) / ((nf + 2.0).powi(2) + mueff));
+        let damps = 1.0 + 2.0 * (((mueff - 1.0) / (nf + 1.0)).sqrt() - 1.0).max(0.0) + cs;
+        let chi_n = nf.sqrt() * (1.0 - 1.0 / (4.0 * nf) + 1.0 / (21.0 * nf * nf));
+
+        Self {
+            weights,
+            mueff,
+            cc,
+            cs,
+            c1,
+            cmu,
+            damps,
+            chi_n,
+        }
+    }
+}
+
+/// A CMA-ES (covariance matrix adaptation evolution strategy) optimizer.
+///
+/// Like [`DifferentialEvolution`][crate::differential_evolution::DifferentialEvolution], this runs outside of
+/// [`GeneticSim`][crate::GeneticSim]. Each generation samples genomes from a multivariate normal distribution,
+/// evaluates them with a [`FitnessFn`][crate::builtin::eliminator::FitnessFn], reports them to a
+/// [`FitnessObserver`][crate::builtin::eliminator::FitnessObserver] (sorted by fitness, highest first), and moves
+/// the distribution towards the fittest samples. Fitness is maximized, like everywhere else in this crate.
+/// A NaN fitness is ranked below every other sample, and is reported as [`f32::NEG_INFINITY`].
+///
+/// When a run converges or stalls (see [`CmaTermination`]), the optimizer either stops or restarts from the initial
+/// genome, depending on its [`RestartStrategy`].
+pub struct CmaEs<G, F, O = ()>
+where
+    G: RealParameters + Clone + FeatureBoundedGenome,
+    F: FeatureBoundedFitnessFn<G>,
+    O: FeatureBoundedFitnessObserver<G>,
+{
+    /// The fitness function to maximize.
+    pub fitness_fn: F,
+
+    /// The observer that is shown every generation.
+    pub observer: O,
+
+    /// What to do when a run ends.
+    pub restart_strategy: RestartStrategy,
+
+    /// The maximum number of restarts before the optimizer stops for good.
+    pub max_restarts: usize,
+
+    /// The criteria that end a run.
+    pub termination: CmaTermination,
+
+    initial_mean: Vec<f64>,
+    initial_sigma: f64,
+    default_lambda: usize,
+    large_lambda: usize,
+
+    lambda: usize,
+    coefficients: Coefficients,
+    mean: Vec<f64>,
+    sigma: f64,
+    cov: Vec<Vec<f64>>,
+    eigenvectors: Vec<Vec<f64>>,
+    axis_lengths: Vec<f64>,
+    pc: Vec<f64>,
+    ps: Vec<f64>,
+    history: VecDeque<f32>,
+    run_generation: usize,
+    run_evaluations: usize,
+
+    small_regime: bool,
+    small_budget: usize,
+    large_budget: usize,
+
+    best: Option<(G, f32)>,
+    generation: usize,
+    evaluations: usize,
+    restarts: usize,
+    finished: bool,
+}
+
+impl<G, F, O> CmaEs<G, F, O>
+where
+    G: RealParameters + Clone + FeatureBoundedGenome,
+    F: FeatureBoundedFitnessFn<G>,
+    O: FeatureBoundedFitnessObserver<G>,
+{
+    /// Creates a new [`CmaEs`] optimizer centered on `initial` with a step size of `sigma`.
+    /// The population size defaults to `4 + 3 * ln(n)`, where `n` is the number of parameters, and restarts are disabled.
+    /// Panics if the genome has no parameters or `sigma` is not positive.
+    pub fn new(initial: G, sigma: f64, fitness_fn: F, observer: O) -> Self {
+        let initial_mean = initial.to_parameters();
+        let n = initial_mean.len();
+        assert!(n > 0, "CMA-ES needs at least one parameter");
+        assert!(sigma > 0.0, "Step size must be greater than 0.0");
+
+        let lambda = 4 + (3.0 * (n as f64).ln()) as usize;
+        let mut cma = Self {
+            fitness_fn,
+            observer,
+            restart_strategy: RestartStrategy::None,
+            max_restarts: 9,
+            termination: CmaTermination::default(),
+            initial_mean,
+            initial_sigma: sigma,
+            default_lambda: lambda,
+            large_lambda: lambda,
+            lambda,
+            coefficients: Coefficients::new(n, lambda),
+            mean: Vec::new(),
+            sigma,
+            cov: Vec::new(),
+            eigenvectors: Vec::new(),
+            axis_lengths: Vec::new(),
+            pc: Vec::new(),
+            ps: Vec::new(),
+            history: VecDeque::new(),
+            run_generation: 0,
+            run_evaluations: 0,
+            small_regime: false,
+            small_budget: 0,
+            large_budget: 0,
+            best: None,
+            generation: 0,
+            evaluations: 0,
+            restarts: 0,
+            finished: false,
+        };
+        cma.reset_run(lambda, sigma);
+        cma
+    }
+
+    /// Sets the population size of the first run. Restarts grow the population from this size.
+    /// Panics if `lambda` is less than 2.
+    pub fn with_population_size(mut self, lambda: usize) -> Self {
+        assert!(lambda >= 2, "Population size must be at least 2");
+        self.default_lambda = lambda;
+        self.large_lambda = lambda;
+        self.reset_run(lambda, self.initial_sigma);
+        self
+    }
+
+    /// Enables restarts. See [`RestartStrategy`].
+    pub fn with_restarts(mut self, strategy: RestartStrategy, max_restarts: usize) -> Self {
+        self.restart_strategy = strategy;
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Replaces the criteria that end a run.
+    pub fn with_termination(mut self, termination: CmaTermination) -> Self {
+        self.termination = termination;
+        self
+    }
+
+    /// The mean of the current search distribution.
+    pub fn mean(&self) -> G {
+        G::from_parameters(self.mean.clone())
+    }
+
+    /// The current step size.
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+
+    /// The number of genomes sampled each generation in the current run.
+    pub fn population_size(&self) -> usize {
+        self.lambda
+    }
+
+    /// The fittest genome found so far across all runs, and its fitness.
+    pub fn best(&self) -> Option<(&G, f32)> {
+        self.best.as_ref().map(|(g, f)| (g, *f))
+    }
+
+    /// The total number of generations performed so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// The total number of fitness evaluations performed so far.
+    pub fn evaluations(&self) -> usize {
+        self.evaluations
+    }
+
+    /// The number of times the optimizer has restarted.
+    pub fn restarts(&self) -> usize {
+        self.restarts
+    }
+
+    /// Whether the optimizer has stopped searching, either because restarts are disabled or because they ran out.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Performs one generation. Does nothing if the optimizer [is finished][CmaEs::is_finished].
+    pub fn step(&mut self) {
+        if self.finished {
+            return;
+        }
+
+        let mut rng = rand::rng();
+        let n = self.mean.len();
+
+        let genomes: Vec<G> = (0..self.lambda)
+            .map(|_| {
+                let z: Vec<f64> = (0..n).map(|_| standard_normal(&mut rng)).collect();
+                let x = (0..n)
+                    .map(|i| {
+                        let y: f64 = (0..n)
+                            .map(|j| self.eigenvectors[i][j] * self.axis_lengths[j] * z[j])
+                            .sum();
+                        self.mean[i] + self.sigma * y
+                    })
+                    .collect();
+                G::from_parameters(x)
+            })
+            .collect();
+
+        self.fitness_fn.prepare(&genomes);
+        // NaN would otherwise be ranked above everything by `total_cmp`.
+        let fitness = Self::evaluate(&self.fitness_fn, &genomes)
+            .into_iter()
+            .map(|f| if f.is_nan() { f32::NEG_INFINITY } else { f });
+        let mut sorted: Vec<(G, f32)> = genomes.into_iter().zip(fitness).collect();
+        sorted.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        self.observer.observe(&sorted);
+
+        self.generation += 1;
+        self.evaluations += self.lambda;
+        self.run_evaluations += self.lambda;
+        if self.best.as_ref().is_none_or(|(_, f)| sorted[0].1 > *f) {
+            self.best = Some(sorted[0].clone());
+        }
+
+        self.update_distribution(&sorted);
+
+        let range = sorted[0].1 - sorted[sorted.len() - 1].1;
+        if self.should_terminate(sorted[0].1, range) {
+            self.restart(&mut rng);
+        }
+    }
+
+    /// Calls [`step`][CmaEs::step] `count` number of times, stopping early if the optimizer finishes.
+    pub fn perform_steps(&mut self, count: usize) {
+        for _ in 0..count {
+            if self.finished {
+                break;
+            }
+            self.step();
+        }
+    }
+
+    fn update_distribution(&mut self, sorted: &[(G, f32)]) {
+        let n = self.mean.len();
+        let c = &self.coefficients;
+        let old_mean = std::mem::take(&mut self.mean);
+
+        // the steps of the selected genomes, in units of sigma.
+        let steps: Vec<Vec<f64>> = sorted[..c.weights.len()]
+            .iter()
+            .map(|(g, _)| {
+                g.to_parameters()
+                    .iter()
+                    .zip(&old_mean)
+                    .map(|(x, m)| (x - m) / self.sigma)
+                    .collect()
+            })
+            .collect();
+        let y_w: Vec<f64> = (0..n)
+            .map(|i| c.weights.iter().zip(&steps).map(|(w, y)| w * y[i]).sum())
+            .collect();
+        self.mean = old_mean
+            .iter()
+            .zip(&y_w)
+            .map(|(m, y)| m + self.sigma * y)
+            .collect();
+
+        // C^(-1/2) * y_w = B * D^(-1) * B^T * y_w
+        let projected: Vec<f64> = (0..n)
+            .map(|j| {
+                (0..n)
+                    .map(|i| self.eigenvectors[i][j] * y_w[i])
+                    .sum::<f64>()
+                    / self.axis_lengths[j]
+            })
+            .collect();
+        let whitened: Vec<f64> = (0..n)
+            .map(|i| (0..n).map(|j| self.eigenvectors[i][j] * projected[j]).sum())
+            .collect();
+
+        let ps_factor = (c.cs * (2.0 - c.cs) * c.mueff).sqrt();
+        for (ps, w) in self.ps.iter_mut().zip(&whitened) {
+            *ps = (1.0 - c.cs) * *ps + ps_factor * w;
+        }
+        let ps_norm = self.ps.iter().map(|p| p * p).sum::<f64>().sqrt();
+
+        self.run_generation += 1;
+        let decay = 1.0 - (1.0 - c.cs).powf(2.0 * self.run_generation as f64);
+        let hsig = ps_norm / decay.sqrt() / c.chi_n < 1.4 + 2.0 / (n as f64 + 1.0);
+
+        let pc_factor = if hsig {
+            (c.cc * (2.0 - c.cc) * c.mueff).sqrt()
+        } else {
+            0.0
+        };
+        for (pc, y) in self.pc.iter_mut().zip(&y_w) {
+            *pc = (1.0 - c.cc) * *pc + pc_factor * y;
+        }
+
+        let delta_h = if hsig { 0.0 } else { c.cc * (2.0 - c.cc) };
+        for i in 0..n {
+            for j in 0..=i {
+                let rank_mu: f64 = c
+                    .weights
+                    .iter()
+                    .zip(&steps)
+                    .map(|(w, y)| w * y[i] * y[j])
+                    .sum();
+                let value = (1.0 - c.c1 - c.cmu + c.c1 * delta_h) * self.cov[i][j]
+                    + c.c1 * self.pc[i] * self.pc[j]
+                    + c.cmu * rank_mu;
+                self.cov[i][j] = value;
+                self.cov[j][i] = value;
+            }
+        }
+
+        self.sigma *= ((c.cs / c.damps) * (ps_norm / c.chi_n - 1.0)).exp();
+
+        let (eigenvalues, eigenvectors) = symmetric_eigen(&self.cov);
+        self.axis_lengths = eigenvalues
+            .iter()
+            .map(|v| v.max(f64::MIN_POSITIVE).sqrt())
+            .collect();
+        self.eigenvectors = eigenvectors;
+    }
+
+    fn should_terminate(&mut self, best_fitness: f32, range: f32) -> bool {
+        let n = self.mean.len();
+        let history_len = 10 + (30 * n).div_ceil(self.lambda);
+        self.history.push_back(best_fitness);
+        if self.history.len() > history_len {
+            self.history.pop_front();
+        }
+
+        if !self.sigma.is_finite() || self.mean.iter().any(|m| !m.is_finite()) {
+            return true;
+        }
+
+        let max_std = (0..n)
+            .map(|i| self.sigma * self.cov[i][i].sqrt())
+            .fold(0.0, f64::max);
+        if max_std < self.termination.tol_x {
+            return true;
+        }
+
+        let (min_axis, max_axis) = self
+            .axis_lengths
+            .iter()
+            .fold((f64::MAX, 0.0f64), |(lo, hi), &d| (lo.min(d), hi.max(d)));
+        if (max_axis / min_axis).powi(2) > self.termination.max_condition {
+            return true;
+        }
+
+        if self.history.len() == history_len {
+            let (lo, hi) = self
+                .history
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(lo, hi), &f| (lo.min(f), hi.max(f)));
+            if ((hi - lo).max(range) as f64) <= self.termination.tol_fun {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    fn restart(&mut self, rng: &mut impl rand::Rng) {
+        if self.restarts >= self.max_restarts {
+            self.finished = true;
+            return;
+        }
+
+        let (lambda, sigma) = match self.restart_strategy {
+            RestartStrategy::None => {
+                self.finished = true;
+                return;
+            }
+            RestartStrategy::Ipop { increase } => {
+                self.large_lambda = (self.large_lambda as f64 * increase).ceil() as usize;
+                (self.large_lambda, self.initial_sigma)
+            }
+            RestartStrategy::Bipop { increase } => {
+                if self.small_regime {
+                    self.small_budget += self.run_evaluations;
+                } else if self.restarts > 0 {
+                    self.large_budget += self.run_evaluations;
+                }
+
+                // the first restart is always large, so the small regime has something to be smaller than.
+                self.small_regime = self.restarts > 0 && self.small_budget < self.large_budget;
+                if self.small_regime {
+                    let u: f64 = rng.random();
+                    let ratio = 0.5 * self.large_lambda as f64 / self.default_lambda as f64;
+                    let lambda = (self.default_lambda as f64 * ratio.powf(u * u)) as usize;
+                    (lambda.max(2), self.initial_sigma * 10f64.powf(-2.0 * u))
+                } else {
+                    self.large_lambda = (self.large_lambda as f64 * increase).ceil() as usize;
+                    (self.large_lambda, self.initial_sigma)
+                }
+            }
+        };
+
+        self.restarts += 1;
+        self.reset_run(lambda, sigma);
+    }
+
+    fn reset_run(&mut self, lambda: usize, sigma: f64) {
+        let n = self.initial_mean.len();
+        self.lambda = lambda;
+        self.coefficients = Coefficients::new(n, lambda);
+        self.mean = self.initial_mean.clone();
+        self.sigma = sigma;
+        self.cov = identity(n);
+        self.eigenvectors = identity(n);
+        self.axis_lengths = vec![1.0; n];
+        self.pc = vec![0.0; n];
+        self.ps = vec![0.0; n];
+        self.history.clear();
+        self.run_generation = 0;
+        self.run_evaluations = 0;
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn evaluate(fitness_fn: &F, genomes: &[G]) -> Vec<f32> {
+        genomes.iter().map(|g| fitness_fn.fitness(g)).collect()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn evaluate(fitness_fn: &F, genomes: &[G]) -> Vec<f32> {
+        genomes.par_iter().map(|g| fitness_fn.fitness(g)).collect()
+    }
+}
+
+impl<G, F> CmaEs<G, F, ()>
+where
+    G: RealParameters + Clone + FeatureBoundedGenome,
+    F: FeatureBoundedFitnessFn<G>,
+{
+    /// Creates a new [`CmaEs`] optimizer with unit observer `()`.
+    /// This is a convenience function that doesn't require explicit type annotations.
+    pub fn new_without_observer(initial: G, sigma: f64, fitness_fn: F) -> Self {
+        Self::new(initial, sigma, fitness_fn, ())
+    }
+}
+
+fn identity(n: usize) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect()
+}
+
+/// Decomposes a symmetric matrix with the cyclic Jacobi method.
+/// Returns the eigenvalues and a matrix whose columns are the matching eigenvectors.
+fn symmetric_eigen(matrix: &[Vec<f64>]) -> (Vec<f64>, Vec<Vec<f64>>) {
+    let n = matrix.len();
+    let mut a = matrix.to_vec();
+    let mut v = identity(n);
+
+    let total: f64 = a.iter().flatten().map(|x| x * x).sum();
+    for _ in 0..100 {
+        let off: f64 = (0..n)
+            .flat_map(|i| (0..n).filter(move |&j| j != i).map(move |j| (i, j)))
+            .map(|(i, j)| a[i][j] * a[i][j])
+            .sum();
+        if off <= 1e-30 * total {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                if a[p][q] == 0.0 {
+                    continue;
+                }
+
+                let theta = (a[q][q] - a[p][p]) / (2.0 * a[p][q]);
+                let t = if theta == 0.0 {
+                    1.0
+                } else {
+                    theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt())
+                };
+                let c = 1.0 / (t * t + 1.0).sqrt();
+                let s = t * c;
+
+                for row in a.iter_mut() {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+                let (upper, lower) = a.split_at_mut(q);
+                for (pk, qk) in upper[p].iter_mut().zip(lower[0].iter_mut()) {
+                    (*pk, *qk) = (c * *pk - s * *qk, s * *pk + c * *qk);
+                }
+                for row in v.iter_mut() {
+                    let (kp, kq) = (row[p], row[q]);
+                    row[p] = c * kp - s * kq;
+                    row[q] = s * kp + c * kq;
+                }
+            }
+        }
+    }
+
+    ((0..n).map(|i| a[i][i]).collect(), v)
+}
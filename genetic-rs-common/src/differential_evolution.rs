@@ -0,0 +1,364 @@
+use crate::{builtin::eliminator::FeatureBoundedFitnessFn, FeatureBoundedGenome, RealParameters};
+
+use rand::RngExt;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// How the mutant vector is built for each member of the population.
+/// All strategies use binomial crossover between the mutant and the current member.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeStrategy {
+    /// `v = x_r1 + F * (x_r2 - x_r3)`. Explores well, but converges slowly.
+    Rand1Bin,
+
+    /// `v = x_best + F * (x_r1 - x_r2)`. Converges quickly, but can get stuck in local optima.
+    Best1Bin,
+
+    /// `v = x_i + F * (x_best - x_i) + F * (x_r1 - x_r2)`. A compromise between the other two.
+    CurrentToBest1Bin,
+}
+
+/// The control parameters of differential evolution.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DeParams {
+    /// The differential weight, which scales difference vectors. Usually between 0.4 and 1.0.
+    pub f: f64,
+
+    /// The crossover rate, the probability of taking each value from the mutant vector. 0.0 - 1.0
+    pub cr: f64,
+}
+
+impl Default for DeParams {
+    fn default() -> Self {
+        Self { f: 0.5, cr: 0.9 }
+    }
+}
+
+/// Settings for jDE-style self-adaptation, where every member of the population carries its own [`DeParams`].
+/// Before each trial, the member's `f` is regenerated with probability `tau_f` and its `cr` with probability `tau_cr`.
+/// The new parameters are only kept if the trial replaces the member.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct JdeSettings {
+    /// The probability of regenerating `f` for a trial.
+    pub tau_f: f64,
+
+    /// The probability of regenerating `cr` for a trial.
+    pub tau_cr: f64,
+
+    /// The lowest value a regenerated `f` can have.
+    pub f_lower: f64,
+
+    /// The width of the range a regenerated `f` is drawn from, so `f` is drawn from `f_lower..f_lower + f_range`.
+    pub f_range: f64,
+}
+
+impl Default for JdeSettings {
+    fn default() -> Self {
+        Self {
+            tau_f: 0.1,
+            tau_cr: 0.1,
+            f_lower: 0.1,
+            f_range: 0.9,
+        }
+    }
+}
+
+/// A differential evolution optimizer.
+///
+/// Unlike [`GeneticSim`][crate::GeneticSim], differential evolution does not eliminate and repopulate.
+/// Instead, each member of the population produces one trial genome per generation and is replaced by it
+/// only if the trial's fitness is at least as high. Fitness is maximized, like everywhere else in this crate.
+/// A NaN fitness is treated as the worst possible, and is stored as [`f32::NEG_INFINITY`].
+pub struct DifferentialEvolution<G, F>
+where
+    G: RealParameters + Clone + FeatureBoundedGenome,
+    F: FeatureBoundedFitnessFn<G>,
+{
+    /// The fitness function to maximize.
+    pub fitness_fn: F,
+
+    /// How mutant vectors are built.
+    pub strategy: DeStrategy,
+
+    /// The self-adaptation settings. [`None`] means every member uses the same parameters.
+    pub adaptation: Option<JdeSettings>,
+
+    /// The range every parameter is clamped to after crossover, if any.
+    pub bounds: Option<(f64, f64)>,
+
+    population: Vec<G>,
+    fitness: Vec<f32>,
+    params: Vec<DeParams>,
+    generation: usize,
+}
+
+impl<G, F> DifferentialEvolution<G, F>
+where
+    G: RealParameters + Clone + FeatureBoundedGenome,
+    F: FeatureBoundedFitnessFn<G>,
+{
+    /// Creates a new [`DifferentialEvolution`] optimizer and evaluates the starting population.
+    /// Panics if the population has fewer than 4 members (every strategy needs 3 other distinct members),
+    /// or if `params.cr` is not between 0.0 and 1.0.
+    pub fn new(
+        population: Vec<G>,
+        mut fitness_fn: F,
+        strategy: DeStrategy,
+        params: DeParams,
+    ) -> Self {
+        assert!(
+            population.len() >= 4,
+            "Differential evolution needs a population of at least 4"
+        );
+        if !(0.0..=1.0).contains(&params.cr) {
+            panic!("Crossover rate must be between 0.0 and 1.0");
+        }
+
+        fitness_fn.prepare(&population);
+        let fitness = Self::evaluate(&fitness_fn, &population);
+        let params = vec![params; population.len()];
+
+        Self {
+            fitness_fn,
+            strategy,
+            adaptation: None,
+            bounds: None,
+            population,
+            fitness,
+            params,
+            generation: 0,
+        }
+    }
+
+    /// Enables jDE-style self-adaptation. See [`JdeSettings`].
+    /// Panics if `tau_f` or `tau_cr` is not between 0.0 and 1.0.
+    pub fn with_self_adaptation(mut self, settings: JdeSettings) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&settings.tau_f) && (0.0..=1.0).contains(&settings.tau_cr),
+            "Self-adaptation probabilities must be between 0.0 and 1.0"
+        );
+        self.adaptation = Some(settings);
+        self
+    }
+
+    /// Clamps every parameter to `min..=max` after crossover.
+    /// Panics if `min` is greater than `max`.
+    pub fn with_bounds(mut self, min: f64, max: f64) -> Self {
+        assert!(
+            min <= max,
+            "Lower bound must not be greater than upper bound"
+        );
+        self.bounds = Some((min, max));
+        self
+    }
+
+    /// The current population.
+    pub fn population(&self) -> &[G] {
+        &self.population
+    }
+
+    /// The fitness of each member of the population, in the same order as [`DifferentialEvolution::population`].
+    pub fn fitness(&self) -> &[f32] {
+        &self.fitness
+    }
+
+    /// The control parameters of each member of the population.
+    /// These only differ between members when self-adaptation is enabled.
+    pub fn params(&self) -> &[DeParams] {
+        &self.params
+    }
+
+    /// The number of generations performed so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// The index of the fittest member of the population.
+    pub fn best_index(&self) -> usize {
+        self.fitness
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.total_cmp(b))
+            .map(|(i, _)| i)
+            .unwrap()
+    }
+
+    /// The fittest member of the population and its fitness.
+    pub fn best(&self) -> (&G, f32) {
+        let i = self.best_index();
+        (&self.population[i], self.fitness[i])
+    }
+
+    /// Performs one generation. Returns the number of members that were replaced by their trial.
+    pub fn step(&mut self) -> usize {
+        let mut rng = rand::rng();
+        let best = self.best_index();
+
+        let vectors: Vec<Vec<f64>> = self.population.iter().map(|g| g.to_parameters()).collect();
+
+        let (trials, trial_params): (Vec<G>, Vec<DeParams>) = (0..self.population.len())
+            .map(|i| {
+                let params = self.trial_params(i, &mut rng);
+                (self.trial(&vectors, i, best, params, &mut rng), params)
+            })
+            .unzip();
+
+        self.fitness_fn.prepare(&trials);
+        let trial_fitness = Self::evaluate(&self.fitness_fn, &trials);
+
+        let mut replaced = 0;
+        for (i, ((trial, fitness), params)) in trials
+            .into_iter()
+            .zip(trial_fitness)
+            .zip(trial_params)
+            .enumerate()
+        {
+            if fitness >= self.fitness[i] {
+                self.population[i] = trial;
+                self.fitness[i] = fitness;
+                self.params[i] = params;
+                replaced += 1;
+            }
+        }
+
+        self.generation += 1;
+        replaced
+    }
+
+    /// Calls [`step`][DifferentialEvolution::step] `count` number of times.
+    pub fn perform_steps(&mut self, count: usize) {
+        for _ in 0..count {
+            self.step();
+        }
+    }
+
+    /// Consumes the optimizer, returning the population and the fitness of each member.
+    pub fn into_population(self) -> Vec<(G, f32)> {
+        self.population.into_iter().zip(self.fitness).collect()
+    }
+
+    fn trial_params(&self, i: usize, rng: &mut impl rand::Rng) -> DeParams {
+        let mut params = self.params[i];
+        if let Some(jde) = &self.adaptation {
+            if rng.random_bool(jde.tau_f) {
+                params.f = jde.f_lower + rng.random::<f64>() * jde.f_range;
+            }
+            if rng.random_bool(jde.tau_cr) {
+                params.cr = rng.random();
+            }
+        }
+        params
+    }
+
+    /// Picks `N` distinct indices that are different from every index in `exclude`.
+    fn distinct<const N: usize>(&self, exclude: &[usize], rng: &mut impl rand::Rng) -> [usize; N] {
+        let mut picked = [0; N];
+        for k in 0..N {
+            loop {
+                let r = rng.random_range(0..self.population.len());
+                if !exclude.contains(&r) && !picked[..k].contains(&r) {
+                    picked[k] = r;
+                    break;
+                }
+            }
+        }
+        picked
+    }
+
+    fn trial(
+        &self,
+        vectors: &[Vec<f64>],
+        i: usize,
+        best: usize,
+        params: DeParams,
+        rng: &mut impl rand::Rng,
+    ) -> G {
+        let x = |j: usize| vectors[j].as_slice();
+        let target = x(i);
+        let dims = target.len();
+
+        let mutant: Vec<f64> = match self.strategy {
+            DeStrategy::Rand1Bin => {
+                let [r1, r2, r3] = self.distinct(&[i], rng);
+                (0..dims)
+                    .map(|d| x(r1)[d] + params.f * (x(r2)[d] - x(r3)[d]))
+                    .collect()
+            }
+            DeStrategy::Best1Bin => {
+                let [r1, r2] = self.distinct(&[i, best], rng);
+                (0..dims)
+                    .map(|d| x(best)[d] + params.f * (x(r1)[d] - x(r2)[d]))
+                    .collect()
+            }
+            DeStrategy::CurrentToBest1Bin => {
+                let [r1, r2] = self.distinct(&[i, best], rng);
+                (0..dims)
+                    .map(|d| {
+                        target[d]
+                            + params.f * (x(best)[d] - target[d])
+                            + params.f * (x(r1)[d] - x(r2)[d])
+                    })
+                    .collect()
+            }
+        };
+
+        // binomial crossover, always taking at least one value from the mutant.
+        let forced = rng.random_range(0..dims.max(1));
+        let mut trial = target.to_vec();
+        for (d, value) in trial.iter_mut().enumerate() {
+            if d == forced || rng.random_bool(params.cr) {
+                *value = mutant[d];
+            }
+            if let Some((min, max)) = self.bounds {
+                *value = value.clamp(min, max);
+            }
+        }
+        G::from_parameters(trial)
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn evaluate(fitness_fn: &F, genomes: &[G]) -> Vec<f32> {
+        genomes
+            .iter()
+            .map(|g| worst_if_nan(fitness_fn.fitness(g)))
+            .collect()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn evaluate(fitness_fn: &F, genomes: &[G]) -> Vec<f32> {
+        genomes
+            .par_iter()
+            .map(|g| worst_if_nan(fitness_fn.fitness(g)))
+            .collect()
+    }
+}
+
+#[cfg(feature = "genrand")]
+impl<G, F> DifferentialEvolution<G, F>
+where
+    G: RealParameters + Clone + FeatureBoundedGenome + crate::GenerateRandom,
+    F: FeatureBoundedFitnessFn<G>,
+{
+    /// Creates a new [`DifferentialEvolution`] optimizer with `count` random genomes.
+    /// Panics under the same conditions as [`DifferentialEvolution::new`].
+    pub fn new_random(
+        rng: &mut impl rand::Rng,
+        count: usize,
+        fitness_fn: F,
+        strategy: DeStrategy,
+        params: DeParams,
+    ) -> Self {
+        let population = (0..count).map(|_| G::gen_random(rng)).collect();
+        Self::new(population, fitness_fn, strategy, params)
+    }
+}
+
+/// Maps NaN to negative infinity, so NaN members are replaced by any trial and never chosen as the best.
+fn worst_if_nan(fitness: f32) -> f32 {
+    if fitness.is_nan() {
+        f32::NEG_INFINITY
+    } else {
+        fitness
+    }
+}
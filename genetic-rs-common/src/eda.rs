@@ -0,0 +1,188 @@
+use crate::{EvaluatedPopulation, Repopulator};
+
+use rand::RngExt;
+
+/// How an [`EdaRepopulator`] learns its probability vector from the survivors.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EdaModel {
+    /// Univariate marginal distribution algorithm.
+    /// The probability of each bit is replaced with its frequency among the survivors.
+    Umda,
+
+    /// Population-based incremental learning.
+    /// The probability vector is moved towards the survivors' frequencies, then randomly perturbed.
+    Pbil {
+        /// How far the probability vector moves towards the survivors' frequencies each generation. 0.0 - 1.0
+        learning_rate: f32,
+
+        /// The probability of perturbing each entry of the probability vector. 0.0 - 1.0
+        mutation_probability: f32,
+
+        /// How far a perturbed entry moves towards a random 0 or 1. 0.0 - 1.0
+        mutation_shift: f32,
+    },
+
+    /// The compact genetic algorithm.
+    /// Survivors are paired up, fittest against least fit, and wherever the pair disagrees,
+    /// the probability moves towards the winner's bit by `1 / virtual_population`.
+    CompactGa {
+        /// The size of the population the model simulates. Larger values learn more slowly but more reliably.
+        virtual_population: f32,
+    },
+}
+
+/// A repopulator for bit-string genomes that fits a probability vector to the survivors,
+/// then samples new genomes from it. The survivors are kept.
+///
+/// [`EdaModel::CompactGa`] depends on the survivors being ranked. When the eliminator reports fitness
+/// (like [`FitnessEliminator`][crate::builtin::eliminator::FitnessEliminator] does), the survivors are ranked by it;
+/// otherwise they are assumed to already be ordered from fittest to least fit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct EdaRepopulator {
+    /// The estimation-of-distribution algorithm used to fit the probability vector.
+    pub model: EdaModel,
+
+    /// The probability of each bit being `true` in a sampled genome.
+    pub probabilities: Vec<f32>,
+
+    /// Probabilities are kept within `margin..=1.0 - margin` after fitting, so no bit is ever fixed for good.
+    pub margin: f32,
+}
+
+impl EdaRepopulator {
+    /// Creates a new [`EdaRepopulator`] for genomes of `genome_len` bits, with every probability starting at 0.5.
+    /// Panics if any rate in the model is not between 0.0 and 1.0, or if the virtual population is not positive.
+    pub fn new(model: EdaModel, genome_len: usize) -> Self {
+        match model {
+            EdaModel::Umda => {}
+            EdaModel::Pbil {
+                learning_rate,
+                mutation_probability,
+                mutation_shift,
+            } => {
+                for rate in [learning_rate, mutation_probability, mutation_shift] {
+                    if !(0.0..=1.0).contains(&rate) {
+                        panic!("PBIL rates must be between 0.0 and 1.0");
+                    }
+                }
+            }
+            EdaModel::CompactGa { virtual_population } => {
+                assert!(
+                    virtual_population > 0.0,
+                    "Virtual population must be greater than 0.0"
+                );
+            }
+        }
+
+        Self {
+            model,
+            probabilities: vec![0.5; genome_len],
+            margin: 0.0,
+        }
+    }
+
+    /// Sets the margin that keeps probabilities away from 0.0 and 1.0. `1.0 / genome_len` is a common choice.
+    /// Panics if the margin is not between 0.0 and 0.5.
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        if !(0.0..=0.5).contains(&margin) {
+            panic!("Margin must be between 0.0 and 0.5");
+        }
+        self.margin = margin;
+        self
+    }
+
+    /// Updates the probability vector from survivors ordered from fittest to least fit.
+    /// Does nothing if there are no survivors.
+    /// Panics if a survivor's length does not match the model.
+    pub fn fit(&mut self, survivors: &[Vec<bool>], rng: &mut impl rand::Rng) {
+        if survivors.is_empty() {
+            return;
+        }
+        assert!(
+            survivors
+                .iter()
+                .all(|g| g.len() == self.probabilities.len()),
+            "Genome length does not match the model"
+        );
+
+        match self.model {
+            EdaModel::Umda => {
+                self.probabilities = Self::frequencies(survivors);
+            }
+            EdaModel::Pbil {
+                learning_rate,
+                mutation_probability,
+                mutation_shift,
+            } => {
+                let frequencies = Self::frequencies(survivors);
+                for (p, f) in self.probabilities.iter_mut().zip(frequencies) {
+                    *p = (1.0 - learning_rate) * *p + learning_rate * f;
+                    if rng.random_bool(mutation_probability as f64) {
+                        let target = if rng.random_bool(0.5) { 1.0 } else { 0.0 };
+                        *p = (1.0 - mutation_shift) * *p + mutation_shift * target;
+                    }
+                }
+            }
+            EdaModel::CompactGa { virtual_population } => {
+                let step = 1.0 / virtual_population;
+                let half = survivors.len() / 2;
+                for (winner, loser) in survivors[..half].iter().zip(survivors.iter().rev()) {
+                    for ((p, &w), &l) in self.probabilities.iter_mut().zip(winner).zip(loser) {
+                        if w != l {
+                            *p += if w { step } else { -step };
+                        }
+                    }
+                }
+            }
+        }
+
+        let (min, max) = (self.margin, 1.0 - self.margin);
+        for p in &mut self.probabilities {
+            *p = p.clamp(min, max);
+        }
+    }
+
+    /// Samples a new genome from the probability vector.
+    pub fn sample(&self, rng: &mut impl rand::Rng) -> Vec<bool> {
+        self.probabilities
+            .iter()
+            .map(|&p| rng.random_bool(p as f64))
+            .collect()
+    }
+
+    fn frequencies(survivors: &[Vec<bool>]) -> Vec<f32> {
+        let n = survivors.len() as f32;
+        (0..survivors[0].len())
+            .map(|i| survivors.iter().filter(|g| g[i]).count() as f32 / n)
+            .collect()
+    }
+}
+
+impl Repopulator<Vec<bool>> for EdaRepopulator {
+    fn repopulate(&mut self, genomes: &mut Vec<Vec<bool>>, target_size: usize) {
+        let mut rng = rand::rng();
+        self.fit(genomes, &mut rng);
+
+        while genomes.len() < target_size {
+            genomes.push(self.sample(&mut rng));
+        }
+    }
+
+    /// Ranks the survivors by fitness, if any was reported, before fitting the model.
+    fn repopulate_evaluated(
+        &mut self,
+        population: EvaluatedPopulation<Vec<bool>>,
+        target_size: usize,
+    ) -> Vec<Vec<bool>> {
+        let mut genomes = match population.fitness {
+            Some(fitness) => {
+                let mut ranked: Vec<_> = population.genomes.into_iter().zip(fitness).collect();
+                ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+                ranked.into_iter().map(|(g, _)| g).collect()
+            }
+            None => population.genomes,
+        };
+        self.repopulate(&mut genomes, target_size);
+        genomes
+    }
+}
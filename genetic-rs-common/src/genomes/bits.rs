@@ -0,0 +1,239 @@
+use rand::RngExt;
+
+use crate::{
+    builtin::repopulator::{Crossover, Mitosis, RandomlyMutable},
+    GenerateRandom,
+};
+
+/// The crossover operators of a [`BitString`]. Each produces a single child.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BitCrossover {
+    /// Bits before a random cut point are taken from the first parent, and the rest from the second.
+    OnePoint,
+
+    /// Bits between two random cut points are taken from the second parent, and the rest from the first.
+    TwoPoint,
+
+    /// Each bit is taken from either parent with equal probability.
+    #[default]
+    Uniform,
+}
+
+/// The context of a [`BitString`], which chooses its operators.
+/// Mutation always flips each bit with probability equal to the mutation rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct BitOperators {
+    /// The crossover operator.
+    pub crossover: BitCrossover,
+}
+
+/// A genome of `N` bits, packed into `u64` words.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BitString<const N: usize> {
+    words: Vec<u64>,
+}
+
+impl<const N: usize> BitString<N> {
+    const WORDS: usize = N.div_ceil(64);
+
+    /// Creates a [`BitString`] with every bit unset.
+    pub fn zeros() -> Self {
+        Self {
+            words: vec![0; Self::WORDS],
+        }
+    }
+
+    /// Creates a [`BitString`] with every bit set.
+    pub fn ones() -> Self {
+        let mut bits = Self {
+            words: vec![u64::MAX; Self::WORDS],
+        };
+        bits.clear_padding();
+        bits
+    }
+
+    /// Creates a [`BitString`] from a slice of bools.
+    /// Panics if the slice does not have exactly `N` elements.
+    pub fn from_bools(bools: &[bool]) -> Self {
+        assert_eq!(bools.len(), N, "Bool count does not match bit count");
+        let mut bits = Self::zeros();
+        for (i, &b) in bools.iter().enumerate() {
+            bits.set(i, b);
+        }
+        bits
+    }
+
+    /// The number of bits, which is always `N`.
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    /// Whether the bit string has no bits.
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// The packed words. Bits past `N` in the last word are always unset.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// The value of bit `i`. Panics if `i` is out of range.
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < N, "Bit index out of range");
+        self.words[i / 64] >> (i % 64) & 1 == 1
+    }
+
+    /// Sets bit `i` to `value`. Panics if `i` is out of range.
+    pub fn set(&mut self, i: usize, value: bool) {
+        assert!(i < N, "Bit index out of range");
+        let mask = 1 << (i % 64);
+        if value {
+            self.words[i / 64] |= mask;
+        } else {
+            self.words[i / 64] &= !mask;
+        }
+    }
+
+    /// Flips bit `i`. Panics if `i` is out of range.
+    pub fn flip(&mut self, i: usize) {
+        assert!(i < N, "Bit index out of range");
+        self.words[i / 64] ^= 1 << (i % 64);
+    }
+
+    /// The number of set bits.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// The number of bits that differ between two bit strings.
+    pub fn hamming_distance(&self, other: &Self) -> usize {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .map(|(a, b)| (a ^ b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Iterates over the bits.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..N).map(|i| self.get(i))
+    }
+
+    /// Takes the bits selected by `mask` from `other`, and the rest from `self`.
+    fn blend(&self, other: &Self, mask: impl Fn(usize) -> u64) -> Self {
+        Self {
+            words: self
+                .words
+                .iter()
+                .zip(&other.words)
+                .enumerate()
+                .map(|(w, (a, b))| {
+                    let m = mask(w);
+                    (a & !m) | (b & m)
+                })
+                .collect(),
+        }
+    }
+
+    fn clear_padding(&mut self) {
+        if !N.is_multiple_of(64) {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1 << (N % 64)) - 1;
+            }
+        }
+    }
+}
+
+/// A mask of every bit at or after `cut`, within word `w`.
+fn suffix_mask(w: usize, cut: usize) -> u64 {
+    let start = w * 64;
+    if cut <= start {
+        u64::MAX
+    } else if cut >= start + 64 {
+        0
+    } else {
+        u64::MAX << (cut - start)
+    }
+}
+
+impl<const N: usize> GenerateRandom for BitString<N> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        let mut bits = Self {
+            words: (0..Self::WORDS).map(|_| rng.random()).collect(),
+        };
+        bits.clear_padding();
+        bits
+    }
+}
+
+impl<const N: usize> RandomlyMutable for BitString<N> {
+    type Context = BitOperators;
+
+    fn mutate(&mut self, _ctx: &BitOperators, rate: f32, rng: &mut impl rand::Rng) {
+        let rate = rate.clamp(0.0, 1.0) as f64;
+        if rate == 0.0 {
+            return;
+        }
+        for i in 0..N {
+            if rng.random_bool(rate) {
+                self.flip(i);
+            }
+        }
+    }
+}
+
+impl<const N: usize> Mitosis for BitString<N> {
+    type Context = BitOperators;
+
+    fn divide(&self, ctx: &BitOperators, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl<const N: usize> Crossover for BitString<N> {
+    type Context = BitOperators;
+
+    /// Crosses the parents with `ctx.crossover`, then flips bits of the child.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &BitOperators,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let mut child = match ctx.crossover {
+            BitCrossover::OnePoint => {
+                let cut = rng.random_range(0..=N);
+                self.blend(other, |w| suffix_mask(w, cut))
+            }
+            BitCrossover::TwoPoint => {
+                let a = rng.random_range(0..=N);
+                let b = rng.random_range(0..=N);
+                let (start, end) = (a.min(b), a.max(b));
+                self.blend(other, |w| suffix_mask(w, start) & !suffix_mask(w, end))
+            }
+            BitCrossover::Uniform => {
+                let masks: Vec<u64> = (0..Self::WORDS).map(|_| rng.random()).collect();
+                self.blend(other, |w| masks[w])
+            }
+        };
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+#[cfg(feature = "speciation")]
+impl<const N: usize> crate::speciation::Speciated for BitString<N> {
+    type Context = ();
+
+    /// The fraction of bits that differ.
+    fn divergence(&self, other: &Self, _ctx: &()) -> f32 {
+        if N == 0 {
+            return 0.0;
+        }
+        self.hamming_distance(other) as f32 / N as f32
+    }
+}
@@ -0,0 +1,376 @@
+use std::marker::PhantomData;
+
+use rand::RngExt;
+
+use crate::{
+    builtin::repopulator::{Crossover, Mitosis, RandomlyMutable},
+    GenerateRandom,
+};
+
+/// The inclusive `(min, max)` range of each gene of an [`IntegerVector`].
+/// Like [`RealBounds`][super::real::RealBounds], these live on a type so [`GenerateRandom`] can sample within them.
+///
+/// Every range must have `min <= max`. This is checked when the genome is compiled, so an empty range is a compile error:
+///
+/// ```compile_fail
+/// # use genetic_rs_common::prelude::*;
+/// struct Empty;
+///
+/// impl IntegerRanges for Empty {
+///     const RANGES: &'static [(i64, i64)] = &[(0, 10), (5, 4)];
+/// }
+///
+/// IntegerVector::<Empty>::gen_random(&mut rand::rng());
+/// ```
+pub trait IntegerRanges {
+    /// The `(min, max)` of each gene, both inclusive. The length is the number of genes.
+    const RANGES: &'static [(i64, i64)];
+}
+
+/// The number of categories each gene of a [`CategoricalVector`] can take.
+///
+/// Every alphabet must have at least one category. This is checked when the genome is compiled,
+/// so an empty alphabet is a compile error:
+///
+/// ```compile_fail
+/// # use genetic_rs_common::prelude::*;
+/// struct Empty;
+///
+/// impl Alphabets for Empty {
+///     const SIZES: &'static [usize] = &[3, 0];
+/// }
+///
+/// CategoricalVector::<Empty>::gen_random(&mut rand::rng());
+/// ```
+pub trait Alphabets {
+    /// The alphabet size of each gene. The length is the number of genes.
+    const SIZES: &'static [usize];
+}
+
+/// The mutation operators of [`IntegerVector`] and [`CategoricalVector`], which is also their context.
+/// Each gene is mutated with probability equal to the mutation rate.
+/// Crossover is always uniform, taking each gene from either parent with equal probability.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DiscreteMutation {
+    /// Moves the gene up or down by at most `step`, staying within its range.
+    /// For categorical genes, this moves to a nearby category, which suits ordered categories.
+    /// Mutation panics if `step` is less than 1.
+    Creep {
+        /// The largest distance a gene can move.
+        step: i64,
+    },
+
+    /// Replaces the gene with a uniformly random value from its range.
+    RandomReset,
+}
+
+impl Default for DiscreteMutation {
+    fn default() -> Self {
+        Self::Creep { step: 1 }
+    }
+}
+
+impl DiscreteMutation {
+    fn apply(self, gene: i64, min: i64, max: i64, rng: &mut impl rand::Rng) -> i64 {
+        match self {
+            Self::Creep { step } => {
+                assert!(step >= 1, "Creep step must be at least 1, got {step}");
+                let mut delta = rng.random_range(1..=step);
+                if rng.random_bool(0.5) {
+                    delta = -delta;
+                }
+                gene.saturating_add(delta).clamp(min, max)
+            }
+            Self::RandomReset => rng.random_range(min..=max),
+        }
+    }
+}
+
+/// Takes each gene from either parent with equal probability.
+fn uniform_crossover<T: Copy>(a: &[T], b: &[T], rng: &mut impl rand::Rng) -> Vec<T> {
+    a.iter()
+        .zip(b)
+        .map(|(&x, &y)| if rng.random_bool(0.5) { x } else { y })
+        .collect()
+}
+
+/// A genome made of integers, each within the range given by `R`.
+pub struct IntegerVector<R: IntegerRanges> {
+    genes: Vec<i64>,
+    _marker: PhantomData<fn() -> R>,
+}
+
+impl<R: IntegerRanges> IntegerVector<R> {
+    /// Fails to compile if any range in `R` is empty. Referenced wherever genes are sampled or clamped.
+    const VALID_RANGES: () = {
+        let mut i = 0;
+        while i < R::RANGES.len() {
+            assert!(
+                R::RANGES[i].0 <= R::RANGES[i].1,
+                "Every IntegerRanges range must have min <= max"
+            );
+            i += 1;
+        }
+    };
+
+    /// Creates a new [`IntegerVector`], clamping every gene into its range.
+    /// Panics if the number of genes does not match the number of ranges.
+    pub fn new(mut genes: Vec<i64>) -> Self {
+        let () = Self::VALID_RANGES;
+        assert_eq!(
+            genes.len(),
+            R::RANGES.len(),
+            "Gene count does not match range count"
+        );
+        for (g, &(min, max)) in genes.iter_mut().zip(R::RANGES) {
+            *g = (*g).clamp(min, max);
+        }
+        Self {
+            genes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The genes of the genome.
+    pub fn genes(&self) -> &[i64] {
+        &self.genes
+    }
+
+    /// Unwraps the genes of the genome.
+    pub fn into_genes(self) -> Vec<i64> {
+        self.genes
+    }
+
+    /// The range of each gene.
+    pub fn ranges() -> &'static [(i64, i64)] {
+        R::RANGES
+    }
+}
+
+impl<R: IntegerRanges> Clone for IntegerVector<R> {
+    fn clone(&self) -> Self {
+        Self {
+            genes: self.genes.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R: IntegerRanges> std::fmt::Debug for IntegerVector<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("IntegerVector").field(&self.genes).finish()
+    }
+}
+
+impl<R: IntegerRanges> PartialEq for IntegerVector<R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.genes == other.genes
+    }
+}
+
+impl<R: IntegerRanges> Eq for IntegerVector<R> {}
+
+impl<R: IntegerRanges> GenerateRandom for IntegerVector<R> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        let () = Self::VALID_RANGES;
+        Self {
+            genes: R::RANGES
+                .iter()
+                .map(|&(min, max)| rng.random_range(min..=max))
+                .collect(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<R: IntegerRanges> RandomlyMutable for IntegerVector<R> {
+    type Context = DiscreteMutation;
+
+    fn mutate(&mut self, ctx: &DiscreteMutation, rate: f32, rng: &mut impl rand::Rng) {
+        let () = Self::VALID_RANGES;
+        let rate = rate.clamp(0.0, 1.0) as f64;
+        for (g, &(min, max)) in self.genes.iter_mut().zip(R::RANGES) {
+            if rng.random_bool(rate) {
+                *g = ctx.apply(*g, min, max, rng);
+            }
+        }
+    }
+}
+
+impl<R: IntegerRanges> Mitosis for IntegerVector<R> {
+    type Context = DiscreteMutation;
+
+    fn divide(&self, ctx: &DiscreteMutation, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl<R: IntegerRanges> Crossover for IntegerVector<R> {
+    type Context = DiscreteMutation;
+
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &DiscreteMutation,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let mut child = Self {
+            genes: uniform_crossover(&self.genes, &other.genes, rng),
+            _marker: PhantomData,
+        };
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+/// A genome made of categories, each an index into the alphabet given by `A`.
+pub struct CategoricalVector<A: Alphabets> {
+    genes: Vec<usize>,
+    _marker: PhantomData<fn() -> A>,
+}
+
+impl<A: Alphabets> CategoricalVector<A> {
+    /// Fails to compile if any alphabet in `A` is empty. Referenced wherever genes are sampled.
+    const VALID_SIZES: () = {
+        let mut i = 0;
+        while i < A::SIZES.len() {
+            assert!(
+                A::SIZES[i] > 0,
+                "Every Alphabets alphabet must have at least one category"
+            );
+            i += 1;
+        }
+    };
+
+    /// Creates a new [`CategoricalVector`].
+    /// Panics if the number of genes does not match the number of alphabets, or if a gene is outside its alphabet.
+    pub fn new(genes: Vec<usize>) -> Self {
+        assert_eq!(
+            genes.len(),
+            A::SIZES.len(),
+            "Gene count does not match alphabet count"
+        );
+        assert!(
+            genes.iter().zip(A::SIZES).all(|(g, size)| g < size),
+            "Gene is outside of its alphabet"
+        );
+        Self {
+            genes,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The category index of each gene.
+    pub fn genes(&self) -> &[usize] {
+        &self.genes
+    }
+
+    /// Unwraps the genes of the genome.
+    pub fn into_genes(self) -> Vec<usize> {
+        self.genes
+    }
+
+    /// Looks up each gene in its alphabet.
+    /// Panics if the number of alphabets does not match the number of genes.
+    pub fn decode<'a, T>(&self, alphabets: &[&'a [T]]) -> Vec<&'a T> {
+        assert_eq!(
+            alphabets.len(),
+            self.genes.len(),
+            "Alphabet count does not match gene count"
+        );
+        self.genes
+            .iter()
+            .zip(alphabets)
+            .map(|(&g, alphabet)| &alphabet[g])
+            .collect()
+    }
+
+    /// The alphabet size of each gene.
+    pub fn sizes() -> &'static [usize] {
+        A::SIZES
+    }
+}
+
+impl<A: Alphabets> Clone for CategoricalVector<A> {
+    fn clone(&self) -> Self {
+        Self {
+            genes: self.genes.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<A: Alphabets> std::fmt::Debug for CategoricalVector<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("CategoricalVector")
+            .field(&self.genes)
+            .finish()
+    }
+}
+
+impl<A: Alphabets> PartialEq for CategoricalVector<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.genes == other.genes
+    }
+}
+
+impl<A: Alphabets> Eq for CategoricalVector<A> {}
+
+impl<A: Alphabets> GenerateRandom for CategoricalVector<A> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        let () = Self::VALID_SIZES;
+        Self {
+            genes: A::SIZES
+                .iter()
+                .map(|&size| rng.random_range(0..size))
+                .collect(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<A: Alphabets> RandomlyMutable for CategoricalVector<A> {
+    type Context = DiscreteMutation;
+
+    fn mutate(&mut self, ctx: &DiscreteMutation, rate: f32, rng: &mut impl rand::Rng) {
+        let () = Self::VALID_SIZES;
+        let rate = rate.clamp(0.0, 1.0) as f64;
+        for (g, &size) in self.genes.iter_mut().zip(A::SIZES) {
+            if rng.random_bool(rate) {
+                *g = ctx.apply(*g as i64, 0, size as i64 - 1, rng) as usize;
+            }
+        }
+    }
+}
+
+impl<A: Alphabets> Mitosis for CategoricalVector<A> {
+    type Context = DiscreteMutation;
+
+    fn divide(&self, ctx: &DiscreteMutation, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl<A: Alphabets> Crossover for CategoricalVector<A> {
+    type Context = DiscreteMutation;
+
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &DiscreteMutation,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let mut child = Self {
+            genes: uniform_crossover(&self.genes, &other.genes, rng),
+            _marker: PhantomData,
+        };
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
@@ -0,0 +1,11 @@
+/// A real-valued vector genome with per-dimension bounds.
+pub mod real;
+
+/// A fixed-length bit-string genome packed into `u64` words.
+pub mod bits;
+
+/// A permutation genome with order-preserving operators.
+pub mod permutation;
+
+/// Integer and categorical vector genomes.
+pub mod discrete;
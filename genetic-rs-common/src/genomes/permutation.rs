@@ -0,0 +1,384 @@
+use rand::{seq::SliceRandom, RngExt};
+
+use crate::{
+    builtin::repopulator::{Crossover, Mitosis, RandomlyMutable},
+    GenerateRandom,
+};
+
+/// The mutation operators of a [`Permutation`].
+/// Each position starts a move with probability equal to the mutation rate.
+/// The move spans from that position to another random position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PermutationMutation {
+    /// Swaps the two elements.
+    #[default]
+    Swap,
+
+    /// Removes the element and inserts it at the other position.
+    Insert,
+
+    /// Shuffles the elements between the two positions.
+    Scramble,
+
+    /// Reverses the elements between the two positions.
+    Inversion,
+
+    /// Reverses the elements between the two positions, treating the permutation as a cycle (like a closed tour),
+    /// so the reversed segment may wrap around the end.
+    TwoOpt,
+}
+
+/// The crossover operators of a [`Permutation`]. Each produces a single, valid child.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PermutationCrossover {
+    /// Partially mapped crossover. Copies a random segment from the first parent and maps the conflicting
+    /// elements of the second parent through the segment.
+    Pmx,
+
+    /// Order crossover. Copies a random segment from the first parent, then fills the rest with the
+    /// remaining elements in the order they appear in the second parent.
+    #[default]
+    Order,
+
+    /// Cycle crossover. Every element keeps the position it has in one of the parents,
+    /// taking alternating cycles from each parent.
+    Cycle,
+
+    /// Edge recombination. Builds a tour from the adjacencies of both parents, preferring elements with the fewest
+    /// remaining neighbors. Treats the permutation as a cycle.
+    EdgeRecombination,
+
+    /// Position-based crossover. Keeps a random subset of positions from the first parent, then fills the rest with
+    /// the remaining elements in the order they appear in the second parent.
+    PositionBased,
+}
+
+/// The context of a [`Permutation`], which chooses its operators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub struct PermutationOperators {
+    /// The mutation operator.
+    pub mutation: PermutationMutation,
+
+    /// The crossover operator.
+    pub crossover: PermutationCrossover,
+}
+
+/// A genome that is an ordering of the numbers `0..N`.
+/// Every operator produces a valid permutation, so there is no repair step.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Permutation<const N: usize> {
+    order: Vec<usize>,
+}
+
+impl<const N: usize> Permutation<N> {
+    /// The permutation `0, 1, ..., N - 1`.
+    pub fn identity() -> Self {
+        Self {
+            order: (0..N).collect(),
+        }
+    }
+
+    /// Creates a [`Permutation`] from an ordering.
+    /// Panics if `order` does not contain every number in `0..N` exactly once.
+    pub fn new(order: Vec<usize>) -> Self {
+        let mut seen = vec![false; N];
+        assert!(
+            order.len() == N
+                && order
+                    .iter()
+                    .all(|&x| x < N && !std::mem::replace(&mut seen[x], true)),
+            "Order is not a permutation of 0..N"
+        );
+        Self { order }
+    }
+
+    /// The ordering.
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    /// Unwraps the ordering.
+    pub fn into_order(self) -> Vec<usize> {
+        self.order
+    }
+
+    /// The number of elements, which is always `N`.
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    /// Whether the permutation has no elements.
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// The position of each element, such that `positions()[order()[i]] == i`.
+    pub fn positions(&self) -> Vec<usize> {
+        let mut positions = vec![0; N];
+        for (i, &x) in self.order.iter().enumerate() {
+            positions[x] = i;
+        }
+        positions
+    }
+
+    /// The number of pairs of elements that the two permutations order differently.
+    pub fn kendall_tau_distance(&self, other: &Self) -> usize {
+        let positions = other.positions();
+        let mut ranks: Vec<usize> = self.order.iter().map(|&x| positions[x]).collect();
+        count_inversions(&mut ranks)
+    }
+
+    fn pmx(&self, other: &Self, rng: &mut impl rand::Rng) -> Vec<usize> {
+        let (start, end) = segment(N, rng);
+        let positions = self.positions();
+        let mut child = vec![usize::MAX; N];
+        child[start..end].copy_from_slice(&self.order[start..end]);
+
+        for i in (0..start).chain(end..N) {
+            let mut x = other.order[i];
+            while (start..end).contains(&positions[x]) {
+                x = other.order[positions[x]];
+            }
+            child[i] = x;
+        }
+        child
+    }
+
+    fn order_crossover(&self, other: &Self, rng: &mut impl rand::Rng) -> Vec<usize> {
+        let (start, end) = segment(N, rng);
+        let mut used = vec![false; N];
+        let mut child = vec![usize::MAX; N];
+        for i in start..end {
+            child[i] = self.order[i];
+            used[self.order[i]] = true;
+        }
+
+        let mut fill = (end..N).chain(0..start);
+        for x in (end..N).chain(0..end).map(|i| other.order[i]) {
+            if !used[x] {
+                child[fill.next().unwrap()] = x;
+            }
+        }
+        child
+    }
+
+    fn cycle_crossover(&self, other: &Self) -> Vec<usize> {
+        let positions = self.positions();
+        let mut child = vec![usize::MAX; N];
+        let mut from_self = true;
+        for start in 0..N {
+            if child[start] != usize::MAX {
+                continue;
+            }
+            let mut i = start;
+            loop {
+                child[i] = if from_self {
+                    self.order[i]
+                } else {
+                    other.order[i]
+                };
+                i = positions[other.order[i]];
+                if i == start {
+                    break;
+                }
+            }
+            from_self = !from_self;
+        }
+        child
+    }
+
+    fn edge_recombination(&self, other: &Self, rng: &mut impl rand::Rng) -> Vec<usize> {
+        let mut neighbors: Vec<Vec<usize>> = vec![Vec::with_capacity(4); N];
+        for parent in [&self.order, &other.order] {
+            for i in 0..N {
+                let x = parent[i];
+                for y in [parent[(i + N - 1) % N], parent[(i + 1) % N]] {
+                    if y != x && !neighbors[x].contains(&y) {
+                        neighbors[x].push(y);
+                    }
+                }
+            }
+        }
+
+        let mut visited = vec![false; N];
+        let mut child = Vec::with_capacity(N);
+        let mut current = self.order[0];
+        loop {
+            child.push(current);
+            visited[current] = true;
+            if child.len() == N {
+                break;
+            }
+            for list in &mut neighbors {
+                list.retain(|&y| y != current);
+            }
+
+            let candidates = &neighbors[current];
+            current = match candidates.iter().map(|&y| neighbors[y].len()).min() {
+                Some(fewest) => {
+                    let best: Vec<usize> = candidates
+                        .iter()
+                        .copied()
+                        .filter(|&y| neighbors[y].len() == fewest)
+                        .collect();
+                    best[rng.random_range(0..best.len())]
+                }
+                None => {
+                    let remaining: Vec<usize> = (0..N).filter(|&y| !visited[y]).collect();
+                    remaining[rng.random_range(0..remaining.len())]
+                }
+            };
+        }
+        child
+    }
+
+    fn position_based(&self, other: &Self, rng: &mut impl rand::Rng) -> Vec<usize> {
+        let mut used = vec![false; N];
+        let mut child = vec![usize::MAX; N];
+        for i in 0..N {
+            if rng.random_bool(0.5) {
+                child[i] = self.order[i];
+                used[self.order[i]] = true;
+            }
+        }
+
+        let mut remaining = other.order.iter().copied().filter(|&x| !used[x]);
+        for slot in child.iter_mut().filter(|x| **x == usize::MAX) {
+            *slot = remaining.next().unwrap();
+        }
+        child
+    }
+}
+
+/// A random, non-empty range of positions in `0..n`, as `(start, end)`.
+fn segment(n: usize, rng: &mut impl rand::Rng) -> (usize, usize) {
+    let a = rng.random_range(0..n);
+    let b = rng.random_range(0..n);
+    (a.min(b), a.max(b) + 1)
+}
+
+/// Counts the pairs `i < j` with `values[i] > values[j]`, sorting `values` in the process.
+fn count_inversions(values: &mut [usize]) -> usize {
+    if values.len() < 2 {
+        return 0;
+    }
+
+    let mid = values.len() / 2;
+    let mut inversions =
+        count_inversions(&mut values[..mid]) + count_inversions(&mut values[mid..]);
+
+    let mut merged = Vec::with_capacity(values.len());
+    let (mut i, mut j) = (0, mid);
+    while i < mid && j < values.len() {
+        if values[i] <= values[j] {
+            merged.push(values[i]);
+            i += 1;
+        } else {
+            // every remaining element of the left half is greater than this one.
+            inversions += mid - i;
+            merged.push(values[j]);
+            j += 1;
+        }
+    }
+    merged.extend_from_slice(&values[i..mid]);
+    merged.extend_from_slice(&values[j..]);
+    values.copy_from_slice(&merged);
+    inversions
+}
+
+impl<const N: usize> GenerateRandom for Permutation<N> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        let mut order: Vec<usize> = (0..N).collect();
+        order.shuffle(rng);
+        Self { order }
+    }
+}
+
+impl<const N: usize> RandomlyMutable for Permutation<N> {
+    type Context = PermutationOperators;
+
+    fn mutate(&mut self, ctx: &PermutationOperators, rate: f32, rng: &mut impl rand::Rng) {
+        let rate = rate.clamp(0.0, 1.0) as f64;
+        if N < 2 || rate == 0.0 {
+            return;
+        }
+
+        for i in 0..N {
+            if !rng.random_bool(rate) {
+                continue;
+            }
+            let j = rng.random_range(0..N);
+            let (lo, hi) = (i.min(j), i.max(j));
+
+            match ctx.mutation {
+                PermutationMutation::Swap => self.order.swap(i, j),
+                PermutationMutation::Insert => {
+                    let x = self.order.remove(i);
+                    self.order.insert(j, x);
+                }
+                PermutationMutation::Scramble => self.order[lo..=hi].shuffle(rng),
+                PermutationMutation::Inversion => self.order[lo..=hi].reverse(),
+                PermutationMutation::TwoOpt => {
+                    // reverse the cyclic segment from i to j, which wraps around the end when j < i.
+                    let len = (j + N - i) % N + 1;
+                    for k in 0..len / 2 {
+                        self.order.swap((i + k) % N, (i + len - 1 - k) % N);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<const N: usize> Mitosis for Permutation<N> {
+    type Context = PermutationOperators;
+
+    fn divide(&self, ctx: &PermutationOperators, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl<const N: usize> Crossover for Permutation<N> {
+    type Context = PermutationOperators;
+
+    /// Crosses the parents with `ctx.crossover`, then mutates the child with `ctx.mutation`.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &PermutationOperators,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        if N < 2 {
+            return self.clone();
+        }
+
+        let order = match ctx.crossover {
+            PermutationCrossover::Pmx => self.pmx(other, rng),
+            PermutationCrossover::Order => self.order_crossover(other, rng),
+            PermutationCrossover::Cycle => self.cycle_crossover(other),
+            PermutationCrossover::EdgeRecombination => self.edge_recombination(other, rng),
+            PermutationCrossover::PositionBased => self.position_based(other, rng),
+        };
+
+        let mut child = Self { order };
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+#[cfg(feature = "speciation")]
+impl<const N: usize> crate::speciation::Speciated for Permutation<N> {
+    type Context = ();
+
+    /// The normalized Kendall tau distance: the fraction of element pairs that the permutations order differently.
+    fn divergence(&self, other: &Self, _ctx: &()) -> f32 {
+        if N < 2 {
+            return 0.0;
+        }
+        let pairs = N * (N - 1) / 2;
+        self.kendall_tau_distance(other) as f32 / pairs as f32
+    }
+}
@@ -0,0 +1,379 @@
+use std::marker::PhantomData;
+
+use rand::RngExt;
+
+use crate::{
+    builtin::{
+        repopulator::{Crossover, Mitosis, RandomlyMutable},
+        sampling::standard_normal,
+    },
+    GenerateRandom,
+};
+
+/// The per-dimension bounds of a [`RealVector`].
+/// These live on a type rather than in a [`RealVector`], so [`GenerateRandom`] can sample within them.
+///
+/// ```
+/// # use genetic_rs_common::prelude::*;
+/// struct UnitCube;
+///
+/// impl RealBounds for UnitCube {
+///     const BOUNDS: &'static [(f32, f32)] = &[(0.0, 1.0); 3];
+/// }
+///
+/// let genome = RealVector::<UnitCube>::gen_random(&mut rand::rng());
+/// assert!(genome.values().iter().all(|v| (0.0..=1.0).contains(v)));
+/// ```
+///
+/// Every bound must have `min <= max`, and neither can be NaN. This is checked when the genome is compiled,
+/// so an invalid bound is a compile error:
+///
+/// ```compile_fail
+/// # use genetic_rs_common::prelude::*;
+/// struct Inverted;
+///
+/// impl RealBounds for Inverted {
+///     const BOUNDS: &'static [(f32, f32)] = &[(0.0, 1.0), (1.0, -1.0)];
+/// }
+///
+/// RealVector::<Inverted>::gen_random(&mut rand::rng());
+/// ```
+pub trait RealBounds {
+    /// The `(min, max)` of each dimension. The length is the number of dimensions.
+    const BOUNDS: &'static [(f32, f32)];
+}
+
+/// How values that leave their bounds are brought back.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum BoundaryHandling {
+    /// Moves the value to the nearest bound.
+    #[default]
+    Clamp,
+
+    /// Mirrors the value back into the bounds, as if it bounced off of them.
+    Reflect,
+
+    /// Wraps the value around to the other side of the bounds, as if they were periodic.
+    Wrap,
+}
+
+impl BoundaryHandling {
+    /// Brings a value back within `min..=max`.
+    /// Panics if `min` is greater than `max`, or if either is NaN.
+    pub fn repair(self, value: f32, min: f32, max: f32) -> f32 {
+        let width = max - min;
+        if (min..=max).contains(&value) || width <= 0.0 {
+            return value.clamp(min, max);
+        }
+
+        match self {
+            Self::Clamp => value.clamp(min, max),
+            Self::Reflect => {
+                let t = (value - min).rem_euclid(2.0 * width);
+                if t > width {
+                    min + 2.0 * width - t
+                } else {
+                    min + t
+                }
+            }
+            Self::Wrap => min + (value - min).rem_euclid(width),
+        }
+    }
+}
+
+/// The mutation operators of a [`RealVector`].
+/// Each value is mutated with probability equal to the mutation rate.
+/// Step sizes are relative to the width of the dimension's bounds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RealMutation {
+    /// Adds normally distributed noise with a standard deviation of `sigma`.
+    Gaussian {
+        /// The standard deviation, as a fraction of the bounds' width.
+        sigma: f32,
+    },
+
+    /// Replaces the value with one sampled uniformly within the bounds.
+    Uniform,
+
+    /// Polynomial mutation from NSGA-II. Larger `eta` values produce smaller steps.
+    Polynomial {
+        /// The distribution index. Usually around 20.
+        eta: f32,
+    },
+
+    /// Adds Cauchy-distributed noise, which occasionally makes very large jumps.
+    Cauchy {
+        /// The scale parameter, as a fraction of the bounds' width.
+        scale: f32,
+    },
+}
+
+/// The crossover operators of a [`RealVector`]. Each produces a single child.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RealCrossover {
+    /// Simulated binary crossover. Larger `eta` values keep the child closer to its parents.
+    Sbx {
+        /// The distribution index. Usually between 2 and 20.
+        eta: f32,
+    },
+
+    /// BLX-alpha. Each value is sampled uniformly from the parents' range, extended by `alpha` times its width on both sides.
+    BlendAlpha {
+        /// How far past the parents the child may go. Usually 0.5.
+        alpha: f32,
+    },
+
+    /// A random weighted average of the parents, with the same weight for every value.
+    Arithmetic,
+
+    /// Each value is taken from either parent with equal probability.
+    Uniform,
+
+    /// Values before a random cut point are taken from the first parent, and the rest from the second.
+    OnePoint,
+}
+
+/// The context of a [`RealVector`], which chooses its operators.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RealOperators {
+    /// The mutation operator.
+    pub mutation: RealMutation,
+
+    /// The crossover operator.
+    pub crossover: RealCrossover,
+
+    /// How values that leave their bounds are brought back.
+    pub boundary: BoundaryHandling,
+}
+
+impl Default for RealOperators {
+    fn default() -> Self {
+        Self {
+            mutation: RealMutation::Gaussian { sigma: 0.1 },
+            crossover: RealCrossover::Sbx { eta: 15.0 },
+            boundary: BoundaryHandling::Clamp,
+        }
+    }
+}
+
+/// A genome made of real values, each within the bounds given by `B`.
+pub struct RealVector<B: RealBounds> {
+    values: Vec<f32>,
+    _marker: PhantomData<fn() -> B>,
+}
+
+impl<B: RealBounds> RealVector<B> {
+    /// Fails to compile if any bound in `B` is inverted or NaN. Referenced wherever values are sampled or repaired.
+    const VALID_BOUNDS: () = {
+        let mut i = 0;
+        while i < B::BOUNDS.len() {
+            assert!(
+                B::BOUNDS[i].0 <= B::BOUNDS[i].1,
+                "Every RealBounds bound must have min <= max, and neither can be NaN"
+            );
+            i += 1;
+        }
+    };
+
+    /// Creates a new [`RealVector`], clamping every value into its bounds.
+    /// Panics if the number of values does not match the number of bounds.
+    pub fn new(mut values: Vec<f32>) -> Self {
+        let () = Self::VALID_BOUNDS;
+        assert_eq!(
+            values.len(),
+            B::BOUNDS.len(),
+            "Value count does not match bound count"
+        );
+        for (v, &(min, max)) in values.iter_mut().zip(B::BOUNDS) {
+            *v = v.clamp(min, max);
+        }
+        Self {
+            values,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The values of the genome.
+    pub fn values(&self) -> &[f32] {
+        &self.values
+    }
+
+    /// The values of the genome, mutably. Use [`RealVector::repair`] after moving them out of bounds.
+    pub fn values_mut(&mut self) -> &mut [f32] {
+        &mut self.values
+    }
+
+    /// Unwraps the values of the genome.
+    pub fn into_values(self) -> Vec<f32> {
+        self.values
+    }
+
+    /// The bounds of each dimension.
+    pub fn bounds() -> &'static [(f32, f32)] {
+        B::BOUNDS
+    }
+
+    /// Brings every value back within its bounds.
+    pub fn repair(&mut self, boundary: BoundaryHandling) {
+        let () = Self::VALID_BOUNDS;
+        for (v, &(min, max)) in self.values.iter_mut().zip(B::BOUNDS) {
+            *v = boundary.repair(*v, min, max);
+        }
+    }
+}
+
+impl<B: RealBounds> Clone for RealVector<B> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B: RealBounds> std::fmt::Debug for RealVector<B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RealVector").field(&self.values).finish()
+    }
+}
+
+impl<B: RealBounds> PartialEq for RealVector<B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.values == other.values
+    }
+}
+
+impl<B: RealBounds> GenerateRandom for RealVector<B> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        let () = Self::VALID_BOUNDS;
+        Self {
+            values: B::BOUNDS
+                .iter()
+                .map(|&(min, max)| uniform(rng, min, max))
+                .collect(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B: RealBounds> RandomlyMutable for RealVector<B> {
+    type Context = RealOperators;
+
+    fn mutate(&mut self, ctx: &RealOperators, rate: f32, rng: &mut impl rand::Rng) {
+        let () = Self::VALID_BOUNDS;
+        for (v, &(min, max)) in self.values.iter_mut().zip(B::BOUNDS) {
+            if !rng.random_bool(rate.clamp(0.0, 1.0) as f64) {
+                continue;
+            }
+
+            let width = max - min;
+            *v = match ctx.mutation {
+                RealMutation::Gaussian { sigma } => {
+                    *v + sigma * width * standard_normal(rng) as f32
+                }
+                RealMutation::Uniform => uniform(rng, min, max),
+                RealMutation::Polynomial { eta } => {
+                    let u: f32 = rng.random();
+                    let delta = if u < 0.5 {
+                        (2.0 * u).powf(1.0 / (eta + 1.0)) - 1.0
+                    } else {
+                        1.0 - (2.0 * (1.0 - u)).powf(1.0 / (eta + 1.0))
+                    };
+                    *v + delta * width
+                }
+                RealMutation::Cauchy { scale } => {
+                    let u: f32 = rng.random();
+                    *v + scale * width * (std::f32::consts::PI * (u - 0.5)).tan()
+                }
+            };
+            *v = ctx.boundary.repair(*v, min, max);
+        }
+    }
+}
+
+impl<B: RealBounds> Mitosis for RealVector<B> {
+    type Context = RealOperators;
+
+    fn divide(&self, ctx: &RealOperators, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl<B: RealBounds> Crossover for RealVector<B> {
+    type Context = RealOperators;
+
+    /// Crosses the parents with `ctx.crossover`, then mutates the child with `ctx.mutation`.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &RealOperators,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let pairs = self.values.iter().zip(&other.values);
+        let values: Vec<f32> = match ctx.crossover {
+            RealCrossover::Sbx { eta } => pairs
+                .map(|(&a, &b)| {
+                    let u: f32 = rng.random();
+                    let beta = if u <= 0.5 {
+                        (2.0 * u).powf(1.0 / (eta + 1.0))
+                    } else {
+                        (1.0 / (2.0 * (1.0 - u))).powf(1.0 / (eta + 1.0))
+                    };
+                    let sign = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+                    0.5 * ((a + b) + sign * beta * (a - b))
+                })
+                .collect(),
+            RealCrossover::BlendAlpha { alpha } => pairs
+                .map(|(&a, &b)| {
+                    let (lo, hi) = (a.min(b), a.max(b));
+                    let extent = alpha * (hi - lo);
+                    uniform(rng, lo - extent, hi + extent)
+                })
+                .collect(),
+            RealCrossover::Arithmetic => {
+                let weight: f32 = rng.random();
+                pairs
+                    .map(|(&a, &b)| weight * a + (1.0 - weight) * b)
+                    .collect()
+            }
+            RealCrossover::Uniform => pairs
+                .map(|(&a, &b)| if rng.random_bool(0.5) { a } else { b })
+                .collect(),
+            RealCrossover::OnePoint => {
+                let cut = rng.random_range(0..=self.values.len());
+                pairs
+                    .enumerate()
+                    .map(|(i, (&a, &b))| if i < cut { a } else { b })
+                    .collect()
+            }
+        };
+
+        let mut child = Self {
+            values,
+            _marker: PhantomData,
+        };
+        child.repair(ctx.boundary);
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+#[cfg(any(feature = "de", feature = "cmaes"))]
+impl<B: RealBounds> crate::RealParameters for RealVector<B> {
+    fn to_parameters(&self) -> Vec<f64> {
+        self.values.iter().map(|&v| v as f64).collect()
+    }
+
+    /// Clamps the parameters into the bounds.
+    fn from_parameters(parameters: Vec<f64>) -> Self {
+        Self::new(parameters.into_iter().map(|v| v as f32).collect())
+    }
+}
+
+/// Samples uniformly from `min..=max`, without panicking when the range is empty.
+fn uniform(rng: &mut impl rand::Rng, min: f32, max: f32) -> f32 {
+    min + rng.random::<f32>() * (max - min)
+}
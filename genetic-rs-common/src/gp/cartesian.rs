@@ -0,0 +1,484 @@
+use std::{fmt, marker::PhantomData};
+
+use rand::RngExt;
+
+use crate::{
+    builtin::repopulator::{Mitosis, RandomlyMutable},
+    GenerateRandom,
+};
+
+/// The functions and grid layout of a [`Cartesian`] genome.
+///
+/// Nodes are laid out in `COLUMNS` columns of `ROWS` nodes. A node can read from the program inputs
+/// or from any node up to `LEVELS_BACK` columns to its left, so every genome is acyclic.
+///
+/// ```
+/// # use genetic_rs_common::prelude::*;
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Op {
+///     Add,
+///     Mul,
+///     Neg,
+/// }
+///
+/// struct Arithmetic;
+///
+/// impl FunctionSet for Arithmetic {
+///     type Op = Op;
+///
+///     const OPS: &'static [Op] = &[Op::Add, Op::Mul, Op::Neg];
+///     const INPUTS: usize = 2;
+///     const COLUMNS: usize = 20;
+///
+///     fn arity(op: &Op) -> usize {
+///         match op {
+///             Op::Neg => 1,
+///             _ => 2,
+///         }
+///     }
+///
+///     fn execute(op: &Op, args: &[f32]) -> f32 {
+///         match op {
+///             Op::Add => args[0] + args[1],
+///             Op::Mul => args[0] * args[1],
+///             Op::Neg => -args[0],
+///         }
+///     }
+/// }
+///
+/// let genome = Cartesian::<Arithmetic>::gen_random(&mut rand::rng());
+/// let outputs = genome.eval(&[1.0, 2.0]);
+/// assert_eq!(outputs.len(), 1);
+/// ```
+pub trait FunctionSet {
+    /// The functions nodes can compute.
+    type Op: Clone + PartialEq + fmt::Debug + 'static;
+
+    /// Every function available to nodes.
+    const OPS: &'static [Self::Op];
+
+    /// The number of program inputs.
+    const INPUTS: usize;
+
+    /// The number of program outputs.
+    const OUTPUTS: usize = 1;
+
+    /// The number of nodes in each column.
+    const ROWS: usize = 1;
+
+    /// The number of columns.
+    const COLUMNS: usize;
+
+    /// How many columns back a node can read from. Defaults to every previous column.
+    const LEVELS_BACK: usize = Self::COLUMNS;
+
+    /// The number of arguments `op` takes.
+    fn arity(op: &Self::Op) -> usize;
+
+    /// Computes `op` on its arguments.
+    fn execute(op: &Self::Op, args: &[f32]) -> f32;
+}
+
+/// A node of a [`Cartesian`] genome or [`CartesianDag`]: `op(inputs...)`.
+///
+/// Addresses below [`FunctionSet::INPUTS`] refer to program inputs, and the rest refer to nodes, in order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CartesianNode<O> {
+    /// The function this node computes.
+    pub op: O,
+
+    /// The addresses this node reads its arguments from.
+    /// In a [`Cartesian`] genome every node has one connection per argument of the largest arity,
+    /// and only the first `arity(op)` are used.
+    pub inputs: Vec<usize>,
+}
+
+/// The mutation operators of a [`Cartesian`] genome, which is also its context.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CartesianMutation {
+    /// Goldman's single active mutation: mutates random genes until one that affects the output has changed,
+    /// so no evaluation is wasted on a child that behaves exactly like its parent.
+    /// The mutation rate is ignored, since every mutation changes exactly one active gene.
+    #[default]
+    SingleActive,
+
+    /// Mutates each gene with probability equal to the mutation rate, whether it is active or not.
+    Point,
+}
+
+/// A Cartesian genetic programming genome: a grid of function nodes wired together as a directed acyclic graph.
+///
+/// Only the nodes the outputs depend on are active. The inactive ones are kept, and can become active again after mutation.
+pub struct Cartesian<S: FunctionSet> {
+    nodes: Vec<CartesianNode<S::Op>>,
+    outputs: Vec<usize>,
+    _marker: PhantomData<fn() -> S>,
+}
+
+impl<S: FunctionSet> Cartesian<S> {
+    /// Creates a genome from its nodes, in column-major order, and its output addresses.
+    /// Panics if the shape does not match `S`, or if a connection breaks the levels-back limit.
+    pub fn new(nodes: Vec<CartesianNode<S::Op>>, outputs: Vec<usize>) -> Self {
+        assert_eq!(
+            nodes.len(),
+            S::ROWS * S::COLUMNS,
+            "Node count does not match grid size"
+        );
+        assert_eq!(outputs.len(), S::OUTPUTS, "Output count does not match");
+
+        let arity = Self::max_arity();
+        for (i, node) in nodes.iter().enumerate() {
+            let (start, end) = Self::node_range(i);
+            assert!(
+                node.inputs.len() == arity
+                    && node.inputs.iter().all(
+                        |&a| a < S::INPUTS || (S::INPUTS + start..S::INPUTS + end).contains(&a)
+                    ),
+                "Invalid connections for node {i}"
+            );
+        }
+        assert!(
+            outputs.iter().all(|&a| a < S::INPUTS + nodes.len()),
+            "Output address out of range"
+        );
+
+        Self {
+            nodes,
+            outputs,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The nodes, in column-major order.
+    pub fn nodes(&self) -> &[CartesianNode<S::Op>] {
+        &self.nodes
+    }
+
+    /// The address each output reads from.
+    pub fn outputs(&self) -> &[usize] {
+        &self.outputs
+    }
+
+    /// Marks each node that the outputs depend on.
+    pub fn active(&self) -> Vec<bool> {
+        let mut active = vec![false; self.nodes.len()];
+        for &a in &self.outputs {
+            if a >= S::INPUTS {
+                active[a - S::INPUTS] = true;
+            }
+        }
+
+        for i in (0..self.nodes.len()).rev() {
+            if !active[i] {
+                continue;
+            }
+            let node = &self.nodes[i];
+            for &a in &node.inputs[..S::arity(&node.op)] {
+                if a >= S::INPUTS {
+                    active[a - S::INPUTS] = true;
+                }
+            }
+        }
+        active
+    }
+
+    /// The number of active nodes.
+    pub fn active_count(&self) -> usize {
+        self.active().into_iter().filter(|a| *a).count()
+    }
+
+    /// Decodes the active nodes into a graph that can be evaluated without the inactive ones.
+    pub fn decode(&self) -> CartesianDag<S> {
+        let active = self.active();
+
+        // maps every genome address to its address in the graph.
+        let mut remap: Vec<usize> = (0..S::INPUTS).collect();
+        remap.resize(S::INPUTS + self.nodes.len(), usize::MAX);
+
+        let mut nodes = Vec::new();
+        for (i, node) in self.nodes.iter().enumerate() {
+            if !active[i] {
+                continue;
+            }
+            remap[S::INPUTS + i] = S::INPUTS + nodes.len();
+            nodes.push(CartesianNode {
+                op: node.op.clone(),
+                inputs: node.inputs[..S::arity(&node.op)]
+                    .iter()
+                    .map(|&a| remap[a])
+                    .collect(),
+            });
+        }
+
+        CartesianDag {
+            nodes,
+            outputs: self.outputs.iter().map(|&a| remap[a]).collect(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Evaluates the genome on `inputs`. Shorthand for decoding it and evaluating the graph.
+    /// Decode once with [`Cartesian::decode`] to evaluate many inputs.
+    pub fn eval(&self, inputs: &[f32]) -> Vec<f32> {
+        self.decode().eval(inputs)
+    }
+
+    fn max_arity() -> usize {
+        S::OPS.iter().map(S::arity).max().unwrap_or(0)
+    }
+
+    /// The range of node indices that node `i` can read from.
+    fn node_range(i: usize) -> (usize, usize) {
+        let column = i / S::ROWS;
+        let start = column.saturating_sub(S::LEVELS_BACK);
+        (start * S::ROWS, column * S::ROWS)
+    }
+
+    fn random_connection(i: usize, rng: &mut impl rand::Rng) -> usize {
+        let (start, end) = Self::node_range(i);
+        let choice = rng.random_range(0..S::INPUTS + end - start);
+        if choice < S::INPUTS {
+            choice
+        } else {
+            choice + start
+        }
+    }
+
+    fn random_node(i: usize, arity: usize, rng: &mut impl rand::Rng) -> CartesianNode<S::Op> {
+        CartesianNode {
+            op: S::OPS[rng.random_range(0..S::OPS.len())].clone(),
+            inputs: (0..arity)
+                .map(|_| Self::random_connection(i, rng))
+                .collect(),
+        }
+    }
+
+    /// The number of genes: a function and the connections for each node, then the outputs.
+    fn gene_count(&self) -> usize {
+        self.nodes.len() * (1 + Self::max_arity()) + self.outputs.len()
+    }
+
+    /// Whether gene `g` affects the outputs, given the active nodes.
+    fn gene_is_active(&self, g: usize, active: &[bool]) -> bool {
+        let per_node = 1 + Self::max_arity();
+        let (i, gene) = (g / per_node, g % per_node);
+        match self.nodes.get(i) {
+            Some(node) => active[i] && (gene == 0 || gene <= S::arity(&node.op)),
+            None => true,
+        }
+    }
+
+    /// Replaces gene `g` with a different valid value, returning whether it could be changed.
+    fn mutate_gene(&mut self, g: usize, rng: &mut impl rand::Rng) -> bool {
+        let per_node = 1 + Self::max_arity();
+        let (i, gene) = (g / per_node, g % per_node);
+
+        if i >= self.nodes.len() {
+            let o = g - self.nodes.len() * per_node;
+            let addresses = S::INPUTS + self.nodes.len();
+            if addresses < 2 {
+                return false;
+            }
+            let new = rng.random_range(0..addresses - 1);
+            self.outputs[o] = new + usize::from(new >= self.outputs[o]);
+            return true;
+        }
+
+        if gene == 0 {
+            if S::OPS.len() < 2 {
+                return false;
+            }
+            let current = S::OPS.iter().position(|op| *op == self.nodes[i].op);
+            let mut new = rng.random_range(0..S::OPS.len() - 1);
+            if current.is_some_and(|c| new >= c) {
+                new += 1;
+            }
+            self.nodes[i].op = S::OPS[new].clone();
+            return true;
+        }
+
+        let (start, end) = Self::node_range(i);
+        if S::INPUTS + end - start < 2 {
+            return false;
+        }
+        let old = self.nodes[i].inputs[gene - 1];
+        let mut new = old;
+        while new == old {
+            new = Self::random_connection(i, rng);
+        }
+        self.nodes[i].inputs[gene - 1] = new;
+        true
+    }
+}
+
+impl<S: FunctionSet> Clone for Cartesian<S> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            outputs: self.outputs.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: FunctionSet> PartialEq for Cartesian<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes && self.outputs == other.outputs
+    }
+}
+
+impl<S: FunctionSet> fmt::Debug for Cartesian<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cartesian")
+            .field("nodes", &self.nodes)
+            .field("outputs", &self.outputs)
+            .finish()
+    }
+}
+
+impl<S: FunctionSet> GenerateRandom for Cartesian<S> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        let arity = Self::max_arity();
+        let count = S::ROWS * S::COLUMNS;
+        Self {
+            nodes: (0..count)
+                .map(|i| Self::random_node(i, arity, rng))
+                .collect(),
+            outputs: (0..S::OUTPUTS)
+                .map(|_| rng.random_range(0..S::INPUTS + count))
+                .collect(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: FunctionSet> RandomlyMutable for Cartesian<S> {
+    type Context = CartesianMutation;
+
+    fn mutate(&mut self, ctx: &CartesianMutation, rate: f32, rng: &mut impl rand::Rng) {
+        let rate = rate.clamp(0.0, 1.0) as f64;
+        let genes = self.gene_count();
+
+        match ctx {
+            CartesianMutation::SingleActive => {
+                // inactive mutations cannot change which nodes are active, so this only needs computing once.
+                let active = self.active();
+                // give up eventually in case no active gene can change, such as with a single function and input.
+                for _ in 0..genes * 10 {
+                    let g = rng.random_range(0..genes);
+                    if self.mutate_gene(g, rng) && self.gene_is_active(g, &active) {
+                        break;
+                    }
+                }
+            }
+            CartesianMutation::Point => {
+                for g in 0..genes {
+                    if rng.random_bool(rate) {
+                        self.mutate_gene(g, rng);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<S: FunctionSet> Mitosis for Cartesian<S> {
+    type Context = CartesianMutation;
+
+    fn divide(&self, ctx: &CartesianMutation, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+#[cfg(feature = "speciation")]
+impl<S: FunctionSet> crate::speciation::Speciated for Cartesian<S> {
+    type Context = ();
+
+    /// The fraction of genes that differ, counting every function, connection and output gene.
+    fn divergence(&self, other: &Self, _ctx: &()) -> f32 {
+        let mut differences = self
+            .outputs
+            .iter()
+            .zip(&other.outputs)
+            .filter(|(a, b)| a != b)
+            .count();
+        for (a, b) in self.nodes.iter().zip(&other.nodes) {
+            differences += usize::from(a.op != b.op);
+            differences += a
+                .inputs
+                .iter()
+                .zip(&b.inputs)
+                .filter(|(x, y)| x != y)
+                .count();
+        }
+        differences as f32 / self.gene_count().max(1) as f32
+    }
+}
+
+/// The active nodes of a [`Cartesian`] genome, produced by [`Cartesian::decode`].
+/// Nodes only read from inputs and earlier nodes, so they are evaluated in order.
+pub struct CartesianDag<S: FunctionSet> {
+    nodes: Vec<CartesianNode<S::Op>>,
+    outputs: Vec<usize>,
+    _marker: PhantomData<fn() -> S>,
+}
+
+impl<S: FunctionSet> CartesianDag<S> {
+    /// The nodes of the graph, each reading only as many inputs as its function takes.
+    pub fn nodes(&self) -> &[CartesianNode<S::Op>] {
+        &self.nodes
+    }
+
+    /// The address each output reads from.
+    pub fn outputs(&self) -> &[usize] {
+        &self.outputs
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the graph has no nodes, meaning every output reads an input directly.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Evaluates the graph on `inputs`.
+    /// Panics if the number of inputs does not match [`FunctionSet::INPUTS`].
+    pub fn eval(&self, inputs: &[f32]) -> Vec<f32> {
+        assert_eq!(inputs.len(), S::INPUTS, "Input count does not match");
+        let mut values = Vec::with_capacity(S::INPUTS + self.nodes.len());
+        values.extend_from_slice(inputs);
+
+        let mut args = Vec::new();
+        for node in &self.nodes {
+            args.clear();
+            args.extend(node.inputs.iter().map(|&a| values[a]));
+            values.push(S::execute(&node.op, &args));
+        }
+
+        self.outputs.iter().map(|&a| values[a]).collect()
+    }
+}
+
+impl<S: FunctionSet> Clone for CartesianDag<S> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            outputs: self.outputs.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: FunctionSet> fmt::Debug for CartesianDag<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CartesianDag")
+            .field("nodes", &self.nodes)
+            .field("outputs", &self.outputs)
+            .finish()
+    }
+}
@@ -0,0 +1,600 @@
+use std::fmt;
+
+use rand::RngExt;
+
+use crate::{
+    builtin::{
+        eliminator::FitnessFn,
+        repopulator::{Crossover, Mitosis, RandomlyMutable},
+    },
+    GenerateRandom,
+};
+
+/// A context-free grammar in Backus-Naur form, which [`Codons`] are mapped through.
+///
+/// Each rule is written as `<name> ::= production | production | ...` and may span several lines.
+/// Symbols are separated by whitespace: `<name>` is a nonterminal, and anything else is a terminal.
+/// Terminals can be quoted with `"` or `'` to include whitespace or the special tokens `|`, `::=` and `<...>`,
+/// and `""` is the empty string. The first rule is the start symbol.
+///
+/// Mapping concatenates terminals without separators, so `x + 1` produces `x+1`. Quote spaces to keep them.
+///
+/// ```
+/// # use genetic_rs_common::prelude::*;
+/// let grammar = Grammar::parse(
+///     "<expr> ::= <expr> <op> <expr> | <var>
+///      <op>   ::= + | *
+///      <var>  ::= x | y",
+/// )
+/// .unwrap();
+///
+/// // codon 1 picks `<var>` (1 % 2), then codon 0 picks `x` (0 % 2).
+/// let derivation = grammar.map(&[1, 0], 0).unwrap();
+/// assert_eq!(derivation.to_string(), "x");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grammar {
+    names: Vec<String>,
+    rules: Vec<Vec<Vec<Symbol>>>,
+    max_depth: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Symbol {
+    Terminal(String),
+    Nonterminal(usize),
+}
+
+#[derive(Debug)]
+enum Token {
+    Define,
+    Alternative,
+    Nonterminal(String),
+    Terminal(String),
+}
+
+/// An error from [`Grammar::parse`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GrammarError {
+    /// The grammar has no rules.
+    Empty,
+
+    /// A quoted terminal is missing its closing quote.
+    UnterminatedQuote,
+
+    /// Symbols appear before the first rule.
+    ExpectedRule,
+
+    /// A nonterminal has more than one rule.
+    DuplicateRule(String),
+
+    /// A nonterminal is used but has no rule.
+    UndefinedNonterminal(String),
+
+    /// A nonterminal can never be expanded into terminals alone, so mapping through it would never finish.
+    NonTerminating(String),
+}
+
+impl fmt::Display for GrammarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("grammar has no rules"),
+            Self::UnterminatedQuote => f.write_str("unterminated quoted terminal"),
+            Self::ExpectedRule => f.write_str("expected a rule of the form `<name> ::= ...`"),
+            Self::DuplicateRule(name) => write!(f, "<{name}> has more than one rule"),
+            Self::UndefinedNonterminal(name) => write!(f, "<{name}> is used but has no rule"),
+            Self::NonTerminating(name) => write!(f, "<{name}> can never finish expanding"),
+        }
+    }
+}
+
+impl std::error::Error for GrammarError {}
+
+/// An error from [`Grammar::map`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MappingError {
+    /// The codons ran out, even after wrapping the allowed number of times.
+    /// Such genomes are usually treated as invalid and given the worst fitness.
+    OutOfCodons,
+
+    /// The derivation tree grew deeper than [`Grammar::max_depth`].
+    /// This stops recursive rules from expanding until the stack overflows when many wraps are allowed.
+    TooDeep,
+}
+
+impl fmt::Display for MappingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfCodons => f.write_str("ran out of codons before the derivation finished"),
+            Self::TooDeep => f.write_str("the derivation grew deeper than the maximum depth"),
+        }
+    }
+}
+
+impl std::error::Error for MappingError {}
+
+fn tokenize(src: &str) -> Result<Vec<Token>, GrammarError> {
+    let mut tokens = Vec::new();
+    let mut chars = src.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+            continue;
+        }
+
+        if c == '"' || c == '\'' {
+            chars.next();
+            let mut text = String::new();
+            loop {
+                match chars.next() {
+                    Some(q) if q == c => break,
+                    Some(ch) => text.push(ch),
+                    None => return Err(GrammarError::UnterminatedQuote),
+                }
+            }
+            tokens.push(Token::Terminal(text));
+            continue;
+        }
+
+        let mut word = String::new();
+        while let Some(&ch) = chars.peek() {
+            if ch.is_whitespace() {
+                break;
+            }
+            word.push(ch);
+            chars.next();
+        }
+
+        tokens.push(match word.as_str() {
+            "::=" => Token::Define,
+            "|" => Token::Alternative,
+            _ if word.len() > 2 && word.starts_with('<') && word.ends_with('>') => {
+                Token::Nonterminal(word[1..word.len() - 1].to_string())
+            }
+            _ => Token::Terminal(word),
+        });
+    }
+    Ok(tokens)
+}
+
+impl Grammar {
+    /// Parses a grammar in Backus-Naur form. See [`Grammar`] for the syntax.
+    pub fn parse(src: &str) -> Result<Self, GrammarError> {
+        let tokens = tokenize(src)?;
+
+        // find where each rule starts, which is a nonterminal followed by `::=`.
+        let starts: Vec<usize> = (0..tokens.len())
+            .filter(|&i| {
+                matches!(tokens[i], Token::Nonterminal(_))
+                    && matches!(tokens.get(i + 1), Some(Token::Define))
+            })
+            .collect();
+        if starts.is_empty() {
+            return Err(if tokens.is_empty() {
+                GrammarError::Empty
+            } else {
+                GrammarError::ExpectedRule
+            });
+        }
+        if starts[0] != 0 {
+            return Err(GrammarError::ExpectedRule);
+        }
+
+        let mut names = Vec::with_capacity(starts.len());
+        for &start in &starts {
+            let Token::Nonterminal(name) = &tokens[start] else {
+                unreachable!()
+            };
+            if names.contains(name) {
+                return Err(GrammarError::DuplicateRule(name.clone()));
+            }
+            names.push(name.clone());
+        }
+
+        let mut rules = Vec::with_capacity(starts.len());
+        for (r, &start) in starts.iter().enumerate() {
+            let end = starts.get(r + 1).copied().unwrap_or(tokens.len());
+            let mut productions = vec![Vec::new()];
+            for token in &tokens[start + 2..end] {
+                match token {
+                    Token::Alternative => productions.push(Vec::new()),
+                    Token::Terminal(text) => productions
+                        .last_mut()
+                        .unwrap()
+                        .push(Symbol::Terminal(text.clone())),
+                    Token::Nonterminal(name) => {
+                        let index = names
+                            .iter()
+                            .position(|n| n == name)
+                            .ok_or_else(|| GrammarError::UndefinedNonterminal(name.clone()))?;
+                        productions
+                            .last_mut()
+                            .unwrap()
+                            .push(Symbol::Nonterminal(index));
+                    }
+                    // a stray `::=` is only possible right after another `::=`.
+                    Token::Define => return Err(GrammarError::ExpectedRule),
+                }
+            }
+            rules.push(productions);
+        }
+
+        let grammar = Self {
+            names,
+            rules,
+            max_depth: 256,
+        };
+        grammar.check_termination()?;
+        Ok(grammar)
+    }
+
+    /// Ensures every nonterminal has a production that eventually expands into terminals alone.
+    fn check_termination(&self) -> Result<(), GrammarError> {
+        let mut terminates = vec![false; self.rules.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (r, productions) in self.rules.iter().enumerate() {
+                if terminates[r] {
+                    continue;
+                }
+                if productions.iter().any(|p| {
+                    p.iter().all(|s| match s {
+                        Symbol::Terminal(_) => true,
+                        Symbol::Nonterminal(n) => terminates[*n],
+                    })
+                }) {
+                    terminates[r] = true;
+                    changed = true;
+                }
+            }
+        }
+
+        match terminates.iter().position(|t| !t) {
+            Some(r) => Err(GrammarError::NonTerminating(self.names[r].clone())),
+            None => Ok(()),
+        }
+    }
+
+    /// Sets the deepest derivation tree [`Grammar::map`] may build. Defaults to 256.
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The deepest derivation tree [`Grammar::map`] may build, as measured by [`Derivation::depth`].
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    /// The name of the start symbol.
+    pub fn start(&self) -> &str {
+        &self.names[0]
+    }
+
+    /// The names of every nonterminal, in the order their rules appear.
+    pub fn nonterminals(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// The number of productions in the rule for `nonterminal`, or `None` if it has no rule.
+    pub fn production_count(&self, nonterminal: &str) -> Option<usize> {
+        self.names
+            .iter()
+            .position(|n| n == nonterminal)
+            .map(|r| self.rules[r].len())
+    }
+
+    /// Maps codons to a derivation tree, expanding the leftmost nonterminal each step.
+    ///
+    /// Each expansion of a nonterminal with several productions reads the next codon and picks production
+    /// `codon % count`. Nonterminals with a single production read no codon. When the codons run out,
+    /// reading wraps back to the first codon at most `max_wraps` times before failing with [`MappingError::OutOfCodons`].
+    /// Derivations deeper than [`Grammar::max_depth`] fail with [`MappingError::TooDeep`].
+    pub fn map(&self, codons: &[u8], max_wraps: usize) -> Result<Derivation, MappingError> {
+        let mut reader = CodonReader {
+            codons,
+            position: 0,
+            wraps: 0,
+            max_wraps,
+        };
+        self.expand(0, 1, &mut reader)
+    }
+
+    /// Expands `rule` into a node at `depth`, where the root has a depth of 1.
+    fn expand(
+        &self,
+        rule: usize,
+        depth: usize,
+        reader: &mut CodonReader,
+    ) -> Result<Derivation, MappingError> {
+        // leave room for the children, which are one level deeper.
+        if depth >= self.max_depth {
+            return Err(MappingError::TooDeep);
+        }
+
+        let productions = &self.rules[rule];
+        let production = if productions.len() == 1 {
+            0
+        } else {
+            reader.next()? as usize % productions.len()
+        };
+
+        let children = productions[production]
+            .iter()
+            .map(|symbol| match symbol {
+                Symbol::Terminal(text) => Ok(Derivation::Terminal(text.clone())),
+                Symbol::Nonterminal(n) => self.expand(*n, depth + 1, reader),
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Derivation::Nonterminal {
+            symbol: self.names[rule].clone(),
+            production,
+            children,
+        })
+    }
+}
+
+struct CodonReader<'a> {
+    codons: &'a [u8],
+    position: usize,
+    wraps: usize,
+    max_wraps: usize,
+}
+
+impl CodonReader<'_> {
+    fn next(&mut self) -> Result<u8, MappingError> {
+        if self.position == self.codons.len() {
+            if self.codons.is_empty() || self.wraps == self.max_wraps {
+                return Err(MappingError::OutOfCodons);
+            }
+            self.wraps += 1;
+            self.position = 0;
+        }
+        self.position += 1;
+        Ok(self.codons[self.position - 1])
+    }
+}
+
+/// A derivation tree produced by [`Grammar::map`].
+/// Its [`Display`][fmt::Display] implementation concatenates the terminals, giving the output string.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Derivation {
+    /// A terminal symbol.
+    Terminal(String),
+
+    /// An expanded nonterminal.
+    Nonterminal {
+        /// The name of the nonterminal.
+        symbol: String,
+
+        /// The index of the production that was chosen from its rule.
+        production: usize,
+
+        /// The symbols of the chosen production, each already derived.
+        children: Vec<Derivation>,
+    },
+}
+
+impl Derivation {
+    /// The depth of the tree. A lone terminal has a depth of 1.
+    pub fn depth(&self) -> usize {
+        match self {
+            Self::Terminal(_) => 1,
+            Self::Nonterminal { children, .. } => {
+                1 + children.iter().map(Self::depth).max().unwrap_or(0)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Derivation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Terminal(text) => f.write_str(text),
+            Self::Nonterminal { children, .. } => {
+                for child in children {
+                    write!(f, "{child}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The crossover operators of [`Codons`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum CodonCrossover {
+    /// Picks a separate cut point in each parent and joins the head of the first with the tail of the second,
+    /// so the child's length can differ from both parents.
+    #[default]
+    VariableOnePoint,
+
+    /// Picks a single cut point shared by both parents.
+    OnePoint,
+}
+
+/// The context of [`Codons`], which chooses the crossover operator and limits the genome length.
+/// Calling [`RandomlyMutable::mutate`] replaces each codon with a random one with probability equal to the mutation rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CodonOperators {
+    /// The crossover operator.
+    pub crossover: CodonCrossover,
+
+    /// The maximum number of codons a child can have. Longer children are truncated.
+    pub max_length: usize,
+}
+
+impl Default for CodonOperators {
+    fn default() -> Self {
+        Self {
+            crossover: CodonCrossover::VariableOnePoint,
+            max_length: 256,
+        }
+    }
+}
+
+/// A grammatical evolution genome: a string of integer codons that [`Grammar::map`] turns into a program.
+/// Randomly generated genomes have `N` codons, though crossover can change the length.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Codons<const N: usize> {
+    codons: Vec<u8>,
+}
+
+impl<const N: usize> Codons<N> {
+    /// Creates a genome from its codons.
+    pub fn new(codons: Vec<u8>) -> Self {
+        Self { codons }
+    }
+
+    /// The codons of the genome.
+    pub fn codons(&self) -> &[u8] {
+        &self.codons
+    }
+
+    /// Unwraps the codons of the genome.
+    pub fn into_codons(self) -> Vec<u8> {
+        self.codons
+    }
+
+    /// The number of codons.
+    pub fn len(&self) -> usize {
+        self.codons.len()
+    }
+
+    /// Whether the genome has no codons.
+    pub fn is_empty(&self) -> bool {
+        self.codons.is_empty()
+    }
+
+    /// Maps the genome through `grammar`. Shorthand for [`Grammar::map`].
+    pub fn map(&self, grammar: &Grammar, max_wraps: usize) -> Result<Derivation, MappingError> {
+        grammar.map(&self.codons, max_wraps)
+    }
+}
+
+impl<const N: usize> GenerateRandom for Codons<N> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        Self {
+            codons: (0..N).map(|_| rng.random()).collect(),
+        }
+    }
+}
+
+impl<const N: usize> RandomlyMutable for Codons<N> {
+    type Context = CodonOperators;
+
+    fn mutate(&mut self, _ctx: &CodonOperators, rate: f32, rng: &mut impl rand::Rng) {
+        let rate = rate.clamp(0.0, 1.0) as f64;
+        for codon in &mut self.codons {
+            if rng.random_bool(rate) {
+                *codon = rng.random();
+            }
+        }
+    }
+}
+
+impl<const N: usize> Mitosis for Codons<N> {
+    type Context = CodonOperators;
+
+    fn divide(&self, ctx: &CodonOperators, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl<const N: usize> Crossover for Codons<N> {
+    type Context = CodonOperators;
+
+    /// Crosses the parents with `ctx.crossover`, then mutates the child.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &CodonOperators,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let (a, b) = match ctx.crossover {
+            CodonCrossover::VariableOnePoint => (
+                rng.random_range(0..=self.len()),
+                rng.random_range(0..=other.len()),
+            ),
+            CodonCrossover::OnePoint => {
+                let cut = rng.random_range(0..=self.len().min(other.len()));
+                (cut, cut)
+            }
+        };
+
+        let mut codons = self.codons[..a].to_vec();
+        codons.extend_from_slice(&other.codons[b..]);
+        codons.truncate(ctx.max_length);
+
+        // an empty child can never be mapped, so keep the first parent instead.
+        let mut child = if codons.is_empty() {
+            self.clone()
+        } else {
+            Self { codons }
+        };
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+#[cfg(feature = "speciation")]
+impl<const N: usize> crate::speciation::Speciated for Codons<N> {
+    type Context = ();
+
+    /// The fraction of positions where the codons differ, counting every codon past the end of the shorter genome.
+    fn divergence(&self, other: &Self, _ctx: &()) -> f32 {
+        let longest = self.len().max(other.len());
+        if longest == 0 {
+            return 0.0;
+        }
+        let mismatches = self
+            .codons
+            .iter()
+            .zip(&other.codons)
+            .filter(|(a, b)| a != b)
+            .count();
+        (mismatches + longest - self.len().min(other.len())) as f32 / longest as f32
+    }
+}
+
+/// A fitness function for [`Codons`] that maps each genome through a grammar before scoring its derivation.
+/// Genomes that fail to map receive `invalid_fitness` instead.
+pub struct GrammarFitness<F> {
+    /// The grammar genomes are mapped through.
+    pub grammar: Grammar,
+
+    /// The number of times mapping may wrap around the codons.
+    pub max_wraps: usize,
+
+    /// The fitness given to genomes that fail to map.
+    pub invalid_fitness: f32,
+
+    /// Scores a successfully mapped derivation.
+    pub inner: F,
+}
+
+impl<F: Fn(&Derivation) -> f32> GrammarFitness<F> {
+    /// Creates a new [`GrammarFitness`], which allows 2 wraps and gives invalid genomes a fitness of [`f32::MIN`].
+    pub fn new(grammar: Grammar, inner: F) -> Self {
+        Self {
+            grammar,
+            max_wraps: 2,
+            invalid_fitness: f32::MIN,
+            inner,
+        }
+    }
+}
+
+impl<const N: usize, F: Fn(&Derivation) -> f32> FitnessFn<Codons<N>> for GrammarFitness<F> {
+    fn fitness(&self, genome: &Codons<N>) -> f32 {
+        match genome.map(&self.grammar, self.max_wraps) {
+            Ok(derivation) => (self.inner)(&derivation),
+            Err(_) => self.invalid_fitness,
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use std::{fmt, marker::PhantomData};
+
+use rand::RngExt;
+
+use crate::{
+    builtin::repopulator::{Crossover, Mitosis, RandomlyMutable},
+    GenerateRandom,
+};
+
+/// The instructions and registers of a [`Program`].
+///
+/// A program reads from `REGISTERS` writable calculation registers followed by `INPUTS` read-only input registers.
+/// The calculation registers start at 0.0, and the first `OUTPUTS` of them hold the result.
+///
+/// ```
+/// # use genetic_rs_common::prelude::*;
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Op {
+///     Add,
+///     Mul,
+/// }
+///
+/// struct Calculator;
+///
+/// impl InstructionSet for Calculator {
+///     type Op = Op;
+///
+///     const OPS: &'static [Op] = &[Op::Add, Op::Mul];
+///     const REGISTERS: usize = 4;
+///     const INPUTS: usize = 2;
+///
+///     fn arity(_op: &Op) -> usize {
+///         2
+///     }
+///
+///     fn execute(op: &Op, args: &[f32]) -> f32 {
+///         match op {
+///             Op::Add => args[0] + args[1],
+///             Op::Mul => args[0] * args[1],
+///         }
+///     }
+/// }
+///
+/// let program = Program::<Calculator>::gen_random(&mut rand::rng());
+/// let outputs = program.run(&[1.0, 2.0]);
+/// assert_eq!(outputs.len(), 1);
+/// ```
+pub trait InstructionSet {
+    /// The operations instructions can perform.
+    type Op: Clone + PartialEq + fmt::Debug + 'static;
+
+    /// Every operation available to programs.
+    const OPS: &'static [Self::Op];
+
+    /// The number of writable calculation registers.
+    const REGISTERS: usize;
+
+    /// The number of read-only input registers.
+    const INPUTS: usize;
+
+    /// The number of calculation registers, counted from the first, that hold the output.
+    const OUTPUTS: usize = 1;
+
+    /// The range of lengths that [`GenerateRandom`] picks from.
+    const INIT_LENGTH: (usize, usize) = (4, 16);
+
+    /// The range of lengths that crossover and mutation keep programs within.
+    const LENGTH: (usize, usize) = (1, 64);
+
+    /// The number of arguments `op` takes.
+    fn arity(op: &Self::Op) -> usize;
+
+    /// Performs `op` on its arguments.
+    fn execute(op: &Self::Op, args: &[f32]) -> f32;
+}
+
+/// A single register-machine instruction: `dest = op(sources...)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Instruction<O> {
+    /// The operation to perform.
+    pub op: O,
+
+    /// The calculation register the result is written to.
+    pub dest: usize,
+
+    /// The registers the arguments are read from. Indices past the calculation registers refer to input registers.
+    pub sources: Vec<usize>,
+}
+
+impl<O: fmt::Debug> fmt::Display for Instruction<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "r{} = {:?}(", self.dest, self.op)?;
+        for (i, s) in self.sources.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "r{s}")?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// The context of a [`Program`].
+///
+/// Calling [`RandomlyMutable::mutate`] micro-mutates each instruction with probability equal to the mutation rate,
+/// changing its operation, destination or one of its sources. It then inserts or deletes a random instruction
+/// with probability `macro_probability`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LinearOperators {
+    /// The probability of inserting or deleting an instruction on each mutation. 0.0 - 1.0
+    pub macro_probability: f32,
+
+    /// Only micro-mutate effective instructions, so every mutation has a chance to change the program's behavior.
+    pub effective_only: bool,
+}
+
+impl Default for LinearOperators {
+    fn default() -> Self {
+        Self {
+            macro_probability: 0.5,
+            effective_only: false,
+        }
+    }
+}
+
+/// A linear genetic programming genome: a sequence of register-machine instructions from `S`.
+pub struct Program<S: InstructionSet> {
+    instructions: Vec<Instruction<S::Op>>,
+    _marker: PhantomData<fn() -> S>,
+}
+
+impl<S: InstructionSet> Program<S> {
+    /// Creates a program from its instructions.
+    /// Panics if an instruction's destination or sources are out of range, or if its source count does not match its arity.
+    pub fn new(instructions: Vec<Instruction<S::Op>>) -> Self {
+        for ins in &instructions {
+            assert!(
+                ins.dest < S::REGISTERS
+                    && ins.sources.len() == S::arity(&ins.op)
+                    && ins.sources.iter().all(|&s| s < S::REGISTERS + S::INPUTS),
+                "Invalid instruction {ins}"
+            );
+        }
+        Self {
+            instructions,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The instructions of the program.
+    pub fn instructions(&self) -> &[Instruction<S::Op>] {
+        &self.instructions
+    }
+
+    /// The number of instructions.
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    /// Whether the program has no instructions.
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// Runs the program on `inputs`, returning the output registers.
+    /// Panics if the number of inputs does not match [`InstructionSet::INPUTS`].
+    pub fn run(&self, inputs: &[f32]) -> Vec<f32> {
+        assert_eq!(inputs.len(), S::INPUTS, "Input count does not match");
+        let mut registers = vec![0.0; S::REGISTERS];
+        registers.extend_from_slice(inputs);
+
+        let mut args = Vec::new();
+        for ins in &self.instructions {
+            args.clear();
+            args.extend(ins.sources.iter().map(|&s| registers[s]));
+            registers[ins.dest] = S::execute(&ins.op, &args);
+        }
+
+        registers.truncate(S::OUTPUTS);
+        registers
+    }
+
+    /// Marks each instruction that can affect the output registers.
+    /// The others are introns, which can be removed without changing what the program computes.
+    pub fn effective(&self) -> Vec<bool> {
+        let mut needed = vec![false; S::REGISTERS];
+        needed[..S::OUTPUTS].fill(true);
+
+        let mut effective = vec![false; self.instructions.len()];
+        for (i, ins) in self.instructions.iter().enumerate().rev() {
+            if !needed[ins.dest] {
+                continue;
+            }
+            effective[i] = true;
+            needed[ins.dest] = false;
+            for &s in &ins.sources {
+                if s < S::REGISTERS {
+                    needed[s] = true;
+                }
+            }
+        }
+        effective
+    }
+
+    /// The number of effective instructions.
+    pub fn effective_len(&self) -> usize {
+        self.effective().into_iter().filter(|e| *e).count()
+    }
+
+    /// A copy of the program with every intron removed, which computes the same outputs faster.
+    pub fn without_introns(&self) -> Self {
+        Self {
+            instructions: self
+                .instructions
+                .iter()
+                .zip(self.effective())
+                .filter(|(_, e)| *e)
+                .map(|(ins, _)| ins.clone())
+                .collect(),
+            _marker: PhantomData,
+        }
+    }
+
+    fn random_instruction(rng: &mut impl rand::Rng) -> Instruction<S::Op> {
+        let op = S::OPS[rng.random_range(0..S::OPS.len())].clone();
+        Instruction {
+            dest: rng.random_range(0..S::REGISTERS),
+            sources: (0..S::arity(&op))
+                .map(|_| rng.random_range(0..S::REGISTERS + S::INPUTS))
+                .collect(),
+            op,
+        }
+    }
+
+    fn micro_mutate(ins: &mut Instruction<S::Op>, rng: &mut impl rand::Rng) {
+        match rng.random_range(0..3) {
+            0 => {
+                ins.op = S::OPS[rng.random_range(0..S::OPS.len())].clone();
+                ins.sources.resize_with(S::arity(&ins.op), || {
+                    rng.random_range(0..S::REGISTERS + S::INPUTS)
+                });
+            }
+            1 => ins.dest = rng.random_range(0..S::REGISTERS),
+            _ if !ins.sources.is_empty() => {
+                let i = rng.random_range(0..ins.sources.len());
+                ins.sources[i] = rng.random_range(0..S::REGISTERS + S::INPUTS);
+            }
+            _ => ins.dest = rng.random_range(0..S::REGISTERS),
+        }
+    }
+}
+
+impl<S: InstructionSet> Clone for Program<S> {
+    fn clone(&self) -> Self {
+        Self {
+            instructions: self.instructions.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: InstructionSet> PartialEq for Program<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.instructions == other.instructions
+    }
+}
+
+impl<S: InstructionSet> fmt::Debug for Program<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Program").field(&self.instructions).finish()
+    }
+}
+
+/// Formats the program with one instruction per line.
+impl<S: InstructionSet> fmt::Display for Program<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for ins in &self.instructions {
+            writeln!(f, "{ins}")?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: InstructionSet> GenerateRandom for Program<S> {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        let (min, max) = S::INIT_LENGTH;
+        let len = rng.random_range(min..=max);
+        Self {
+            instructions: (0..len).map(|_| Self::random_instruction(rng)).collect(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: InstructionSet> RandomlyMutable for Program<S> {
+    type Context = LinearOperators;
+
+    fn mutate(&mut self, ctx: &LinearOperators, rate: f32, rng: &mut impl rand::Rng) {
+        let rate = rate.clamp(0.0, 1.0) as f64;
+        let effective = if ctx.effective_only {
+            self.effective()
+        } else {
+            vec![true; self.instructions.len()]
+        };
+        for (ins, effective) in self.instructions.iter_mut().zip(effective) {
+            if effective && rng.random_bool(rate) {
+                Self::micro_mutate(ins, rng);
+            }
+        }
+
+        if !rng.random_bool(ctx.macro_probability.clamp(0.0, 1.0) as f64) {
+            return;
+        }
+        let (min, max) = S::LENGTH;
+        let len = self.instructions.len();
+        let insert = if len <= min {
+            true
+        } else if len >= max {
+            false
+        } else {
+            rng.random_bool(0.5)
+        };
+        if insert && len < max {
+            let at = rng.random_range(0..=len);
+            self.instructions.insert(at, Self::random_instruction(rng));
+        } else if !insert && len > min {
+            self.instructions.remove(rng.random_range(0..len));
+        }
+    }
+}
+
+impl<S: InstructionSet> Mitosis for Program<S> {
+    type Context = LinearOperators;
+
+    fn divide(&self, ctx: &LinearOperators, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl<S: InstructionSet> Crossover for Program<S> {
+    type Context = LinearOperators;
+
+    /// Linear two-point crossover: a random segment of `self` is replaced with a random segment of `other`,
+    /// keeping the child within [`InstructionSet::LENGTH`]. The child is then mutated.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &LinearOperators,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let (min, max) = S::LENGTH;
+        let (len, other_len) = (self.instructions.len(), other.instructions.len());
+
+        let start = rng.random_range(0..=len);
+        let end = rng.random_range(start..=len);
+        let donor_start = rng.random_range(0..=other_len);
+        let mut donor_end = rng.random_range(donor_start..=other_len);
+
+        // shrink or grow the donated segment until the child fits.
+        let kept = len - (end - start);
+        donor_end = donor_end.min(donor_start + max.saturating_sub(kept));
+        donor_end = donor_end.max((donor_start + min.saturating_sub(kept)).min(other_len));
+
+        let mut instructions = self.instructions[..start].to_vec();
+        instructions.extend_from_slice(&other.instructions[donor_start..donor_end]);
+        instructions.extend_from_slice(&self.instructions[end..]);
+
+        // the donor may be too short to reach the minimum length, in which case nothing is exchanged.
+        let mut child = if instructions.len() >= min {
+            Self {
+                instructions,
+                _marker: PhantomData,
+            }
+        } else {
+            self.clone()
+        };
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+#[cfg(feature = "speciation")]
+impl<S: InstructionSet> crate::speciation::Speciated for Program<S> {
+    type Context = ();
+
+    /// The edit distance between the two programs' instruction sequences, divided by the longer length.
+    fn divergence(&self, other: &Self, _ctx: &()) -> f32 {
+        let (a, b) = (&self.instructions, &other.instructions);
+        let longest = a.len().max(b.len());
+        if longest == 0 {
+            return 0.0;
+        }
+
+        let mut previous: Vec<usize> = (0..=b.len()).collect();
+        let mut current = vec![0; b.len() + 1];
+        for (i, x) in a.iter().enumerate() {
+            current[0] = i + 1;
+            for (j, y) in b.iter().enumerate() {
+                let substitution = previous[j] + usize::from(x != y);
+                current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+            }
+            std::mem::swap(&mut previous, &mut current);
+        }
+
+        previous[b.len()] as f32 / longest as f32
+    }
+}
@@ -0,0 +1,11 @@
+/// Strongly typed expression trees.
+pub mod tree;
+
+/// Linear genetic programming, where genomes are register-machine programs.
+pub mod linear;
+
+/// Grammatical evolution, where integer codons are mapped to programs through a grammar.
+pub mod grammar;
+
+/// Cartesian genetic programming, where genomes are grids of nodes wired into a graph.
+pub mod cartesian;
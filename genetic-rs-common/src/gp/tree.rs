@@ -0,0 +1,540 @@
+use std::{fmt, marker::PhantomData};
+
+use rand::RngExt;
+
+use crate::{
+    builtin::{
+        eliminator::FitnessFn,
+        repopulator::{Crossover, Mitosis, RandomlyMutable},
+    },
+    GenerateRandom,
+};
+
+/// A function or terminal that can appear in a [`Tree`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Primitive<O: 'static, T: 'static> {
+    /// The operation this primitive performs.
+    pub op: O,
+
+    /// The type of value this primitive produces.
+    pub returns: T,
+
+    /// The types of this primitive's arguments. Terminals have none.
+    pub args: &'static [T],
+}
+
+impl<O, T> Primitive<O, T> {
+    /// Whether this primitive has no arguments.
+    pub fn is_terminal(&self) -> bool {
+        self.args.is_empty()
+    }
+}
+
+/// The functions and terminals that [`Tree`]s are built from.
+///
+/// Trees are strongly typed: a node can only be placed where its return type is expected,
+/// so every tree is well-typed by construction. Untyped GP can use `()` as [`PrimitiveSet::Type`].
+/// Every type that can be requested needs at least one terminal.
+///
+/// ```
+/// # use genetic_rs_common::prelude::*;
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Op {
+///     Add,
+///     Mul,
+///     X,
+///     Const(f32),
+/// }
+///
+/// struct Arithmetic;
+///
+/// impl PrimitiveSet for Arithmetic {
+///     type Type = ();
+///     type Op = Op;
+///
+///     const ROOT: () = ();
+///     const PRIMITIVES: &'static [Primitive<Op, ()>] = &[
+///         Primitive { op: Op::Add, returns: (), args: &[(), ()] },
+///         Primitive { op: Op::Mul, returns: (), args: &[(), ()] },
+///         Primitive { op: Op::X, returns: (), args: &[] },
+///         Primitive { op: Op::Const(0.0), returns: (), args: &[] },
+///     ];
+///
+///     // gives every constant a random value when it is placed in a tree.
+///     fn instantiate(op: &Op, rng: &mut impl rand::Rng) -> Op {
+///         match op {
+///             Op::Const(_) => Op::Const(rng.random_range(-1.0..1.0)),
+///             op => *op,
+///         }
+///     }
+/// }
+///
+/// let tree = Tree::<Arithmetic>::gen_random(&mut rand::rng());
+/// let y = tree.eval(|op, args: Vec<f32>| match op {
+///     Op::Add => args[0] + args[1],
+///     Op::Mul => args[0] * args[1],
+///     Op::X => 2.0,
+///     Op::Const(c) => *c,
+/// });
+/// ```
+pub trait PrimitiveSet {
+    /// The type tags of the values nodes produce.
+    type Type: Copy + PartialEq + fmt::Debug + 'static;
+
+    /// The operations of the nodes.
+    type Op: Clone + PartialEq + fmt::Debug + 'static;
+
+    /// The type the root of every tree must produce.
+    const ROOT: Self::Type;
+
+    /// Every function and terminal available to trees.
+    const PRIMITIVES: &'static [Primitive<Self::Op, Self::Type>];
+
+    /// The range of depths that [`GenerateRandom`] picks from.
+    const INIT_DEPTH: (usize, usize) = (2, 6);
+
+    /// Called whenever a primitive is placed in a tree. Override this to create ephemeral random constants.
+    /// By default, the operation is cloned as-is.
+    fn instantiate(op: &Self::Op, _rng: &mut impl rand::Rng) -> Self::Op {
+        op.clone()
+    }
+}
+
+/// The mutation operators of a [`Tree`].
+/// Calling [`RandomlyMutable::mutate`] applies the operator once with probability equal to the mutation rate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum TreeMutation {
+    /// Replaces a random subtree with a newly grown one.
+    #[default]
+    Subtree,
+
+    /// Replaces a random node with another primitive of the same signature, keeping its children.
+    Point,
+
+    /// Replaces the whole tree with one of its own subtrees, which shrinks it.
+    Hoist,
+}
+
+/// The context of a [`Tree`], which chooses its operators and controls bloat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TreeOperators {
+    /// The mutation operator.
+    pub mutation: TreeMutation,
+
+    /// The maximum depth of a subtree grown by [`TreeMutation::Subtree`].
+    pub subtree_depth: usize,
+
+    /// The maximum depth of any tree produced by crossover or mutation.
+    /// Offspring that would exceed it are rejected in favor of another attempt.
+    pub max_depth: usize,
+}
+
+impl Default for TreeOperators {
+    fn default() -> Self {
+        Self {
+            mutation: TreeMutation::Subtree,
+            subtree_depth: 4,
+            max_depth: 17,
+        }
+    }
+}
+
+/// A node of a [`Tree`].
+struct Node<S: PrimitiveSet> {
+    op: S::Op,
+    primitive: usize,
+}
+
+impl<S: PrimitiveSet> Node<S> {
+    fn primitive(&self) -> &'static Primitive<S::Op, S::Type> {
+        &S::PRIMITIVES[self.primitive]
+    }
+}
+
+impl<S: PrimitiveSet> Clone for Node<S> {
+    fn clone(&self) -> Self {
+        Self {
+            op: self.op.clone(),
+            primitive: self.primitive,
+        }
+    }
+}
+
+/// The number of times crossover and mutation retry before giving up on the depth limit.
+const ATTEMPTS: usize = 10;
+
+/// An expression tree built from the primitives of `S`, stored in prefix order.
+pub struct Tree<S: PrimitiveSet> {
+    nodes: Vec<Node<S>>,
+    _marker: PhantomData<fn() -> S>,
+}
+
+impl<S: PrimitiveSet> Tree<S> {
+    /// Creates a tree where every branch reaches exactly `depth`, where possible.
+    pub fn full(depth: usize, rng: &mut impl rand::Rng) -> Self {
+        Self::generate(S::ROOT, depth, true, rng)
+    }
+
+    /// Creates a tree whose branches stop at random depths, up to `depth`.
+    pub fn grow(depth: usize, rng: &mut impl rand::Rng) -> Self {
+        Self::generate(S::ROOT, depth, false, rng)
+    }
+
+    /// Creates a population with ramped half-and-half: depths are spread evenly over [`PrimitiveSet::INIT_DEPTH`],
+    /// and half of the trees at each depth are [full][Tree::full] while the other half are [grown][Tree::grow].
+    pub fn ramped_half_and_half(count: usize, rng: &mut impl rand::Rng) -> Vec<Self> {
+        let (min, max) = S::INIT_DEPTH;
+        let depths = max - min + 1;
+        (0..count)
+            .map(|i| {
+                let depth = min + i % depths;
+                Self::generate(S::ROOT, depth, (i / depths) % 2 == 0, rng)
+            })
+            .collect()
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Always false, since a tree has at least a root.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The number of edges on the longest path from the root to a leaf.
+    pub fn depth(&self) -> usize {
+        self.depths().into_iter().max().unwrap_or(0)
+    }
+
+    /// The operations of the nodes, in prefix order.
+    pub fn ops(&self) -> impl Iterator<Item = &S::Op> + '_ {
+        self.nodes.iter().map(|n| &n.op)
+    }
+
+    /// The subtree rooted at the `index`th node in prefix order.
+    /// Panics if `index` is out of range.
+    pub fn subtree(&self, index: usize) -> Self {
+        Self {
+            nodes: self.nodes[index..self.subtree_end(index)].to_vec(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Evaluates the tree bottom-up. `f` is called once per node with its operation and the values of its arguments.
+    pub fn eval<V>(&self, mut f: impl FnMut(&S::Op, Vec<V>) -> V) -> V {
+        let mut stack = Vec::new();
+        for node in self.nodes.iter().rev() {
+            let arity = node.primitive().args.len();
+            let args = stack.drain(stack.len() - arity..).rev().collect();
+            stack.push(f(&node.op, args));
+        }
+        stack.pop().unwrap()
+    }
+
+    fn generate(ty: S::Type, depth: usize, full: bool, rng: &mut impl rand::Rng) -> Self {
+        let mut nodes = Vec::new();
+        Self::generate_into(ty, depth, full, rng, &mut nodes);
+        Self {
+            nodes,
+            _marker: PhantomData,
+        }
+    }
+
+    fn generate_into(
+        ty: S::Type,
+        depth: usize,
+        full: bool,
+        rng: &mut impl rand::Rng,
+        nodes: &mut Vec<Node<S>>,
+    ) {
+        let candidates = |terminal: bool| -> Vec<usize> {
+            S::PRIMITIVES
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| p.returns == ty && p.is_terminal() == terminal)
+                .map(|(i, _)| i)
+                .collect()
+        };
+        let terminals = candidates(true);
+        let functions = candidates(false);
+
+        let use_terminal = depth == 0
+            || functions.is_empty()
+            || (!full
+                && rng.random_bool(
+                    terminals.len() as f64 / (terminals.len() + functions.len()) as f64,
+                ));
+        let pool = if use_terminal { terminals } else { functions };
+        assert!(!pool.is_empty(), "No terminal returns the type {ty:?}");
+
+        let primitive = pool[rng.random_range(0..pool.len())];
+        let p = &S::PRIMITIVES[primitive];
+        nodes.push(Node {
+            op: S::instantiate(&p.op, rng),
+            primitive,
+        });
+        for &arg in p.args {
+            Self::generate_into(arg, depth.saturating_sub(1), full, rng, nodes);
+        }
+    }
+
+    /// The index one past the last node of the subtree rooted at `index`.
+    fn subtree_end(&self, index: usize) -> usize {
+        let mut open = 1;
+        let mut end = index;
+        while open > 0 {
+            open += self.nodes[end].primitive().args.len();
+            open -= 1;
+            end += 1;
+        }
+        end
+    }
+
+    /// The depth of every node, in prefix order.
+    fn depths(&self) -> Vec<usize> {
+        let mut depths = Vec::with_capacity(self.nodes.len());
+        let mut remaining: Vec<usize> = Vec::new();
+        for node in &self.nodes {
+            depths.push(remaining.len());
+            let arity = node.primitive().args.len();
+            if arity > 0 {
+                remaining.push(arity);
+                continue;
+            }
+            while let Some(top) = remaining.last_mut() {
+                *top -= 1;
+                if *top > 0 {
+                    break;
+                }
+                remaining.pop();
+            }
+        }
+        depths
+    }
+
+    /// Picks a node, preferring functions 90% of the time as recommended by Koza.
+    fn pick_node(&self, rng: &mut impl rand::Rng) -> usize {
+        let want_function = rng.random_bool(0.9);
+        let pool: Vec<usize> = (0..self.nodes.len())
+            .filter(|&i| self.nodes[i].primitive().is_terminal() != want_function)
+            .collect();
+        if pool.is_empty() {
+            rng.random_range(0..self.nodes.len())
+        } else {
+            pool[rng.random_range(0..pool.len())]
+        }
+    }
+
+    /// Replaces the subtree rooted at `index` with `nodes`.
+    fn splice(&self, index: usize, nodes: impl IntoIterator<Item = Node<S>>) -> Self {
+        let end = self.subtree_end(index);
+        let mut spliced = self.nodes[..index].to_vec();
+        spliced.extend(nodes);
+        spliced.extend_from_slice(&self.nodes[end..]);
+        Self {
+            nodes: spliced,
+            _marker: PhantomData,
+        }
+    }
+
+    fn mutate_subtree(&mut self, ctx: &TreeOperators, rng: &mut impl rand::Rng) {
+        let depths = self.depths();
+        let index = rng.random_range(0..self.nodes.len());
+        let budget = ctx.max_depth.saturating_sub(depths[index]);
+        let ty = self.nodes[index].primitive().returns;
+        let grown = Self::generate(ty, ctx.subtree_depth.min(budget), false, rng);
+        *self = self.splice(index, grown.nodes);
+    }
+
+    fn mutate_point(&mut self, rng: &mut impl rand::Rng) {
+        let index = rng.random_range(0..self.nodes.len());
+        let current = self.nodes[index].primitive;
+        let signature = &S::PRIMITIVES[current];
+        let mut pool: Vec<usize> = S::PRIMITIVES
+            .iter()
+            .enumerate()
+            .filter(|(i, p)| {
+                *i != current && p.returns == signature.returns && p.args == signature.args
+            })
+            .map(|(i, _)| i)
+            .collect();
+        // with no alternative, re-instantiating the same primitive still changes ephemeral constants.
+        if pool.is_empty() {
+            pool.push(current);
+        }
+
+        let primitive = pool[rng.random_range(0..pool.len())];
+        self.nodes[index] = Node {
+            op: S::instantiate(&S::PRIMITIVES[primitive].op, rng),
+            primitive,
+        };
+    }
+
+    fn mutate_hoist(&mut self, rng: &mut impl rand::Rng) {
+        let pool: Vec<usize> = (1..self.nodes.len())
+            .filter(|&i| self.nodes[i].primitive().returns == S::ROOT)
+            .collect();
+        if !pool.is_empty() {
+            *self = self.subtree(pool[rng.random_range(0..pool.len())]);
+        }
+    }
+}
+
+impl<S: PrimitiveSet> Clone for Tree<S> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: PrimitiveSet> PartialEq for Tree<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes.len() == other.nodes.len()
+            && self
+                .nodes
+                .iter()
+                .zip(&other.nodes)
+                .all(|(a, b)| a.primitive == b.primitive && a.op == b.op)
+    }
+}
+
+impl<S: PrimitiveSet> fmt::Debug for Tree<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Tree")
+            .field(&self.nodes.iter().map(|n| &n.op).collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// Formats the tree as an S-expression, such as `(Add X (Mul X X))`.
+impl<S: PrimitiveSet> fmt::Display for Tree<S>
+where
+    S::Op: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = self.eval(|op, args: Vec<String>| {
+            if args.is_empty() {
+                op.to_string()
+            } else {
+                format!("({op} {})", args.join(" "))
+            }
+        });
+        f.write_str(&text)
+    }
+}
+
+impl<S: PrimitiveSet> GenerateRandom for Tree<S> {
+    /// Picks a random depth from [`PrimitiveSet::INIT_DEPTH`], then uses [full][Tree::full] or [grow][Tree::grow]
+    /// with equal probability, so a randomly generated population is ramped half-and-half.
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        let (min, max) = S::INIT_DEPTH;
+        let depth = rng.random_range(min..=max);
+        Self::generate(S::ROOT, depth, rng.random_bool(0.5), rng)
+    }
+}
+
+impl<S: PrimitiveSet> RandomlyMutable for Tree<S> {
+    type Context = TreeOperators;
+
+    fn mutate(&mut self, ctx: &TreeOperators, rate: f32, rng: &mut impl rand::Rng) {
+        if !rng.random_bool(rate.clamp(0.0, 1.0) as f64) {
+            return;
+        }
+
+        for _ in 0..ATTEMPTS {
+            let mut mutant = self.clone();
+            match ctx.mutation {
+                TreeMutation::Subtree => mutant.mutate_subtree(ctx, rng),
+                TreeMutation::Point => mutant.mutate_point(rng),
+                TreeMutation::Hoist => mutant.mutate_hoist(rng),
+            }
+            if mutant.depth() <= ctx.max_depth {
+                *self = mutant;
+                return;
+            }
+        }
+    }
+}
+
+impl<S: PrimitiveSet> Mitosis for Tree<S> {
+    type Context = TreeOperators;
+
+    fn divide(&self, ctx: &TreeOperators, rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl<S: PrimitiveSet> Crossover for Tree<S> {
+    type Context = TreeOperators;
+
+    /// Subtree crossover: replaces a random subtree of `self` with a random subtree of `other` that has the same type.
+    /// If no such pair fits within [`TreeOperators::max_depth`], the child is a copy of `self`.
+    /// The child is then mutated.
+    fn crossover(
+        &self,
+        other: &Self,
+        ctx: &TreeOperators,
+        rate: f32,
+        rng: &mut impl rand::Rng,
+    ) -> Self {
+        let depths = self.depths();
+        let other_depths = other.depths();
+        let mut child = None;
+
+        for _ in 0..ATTEMPTS {
+            let index = self.pick_node(rng);
+            let ty = self.nodes[index].primitive().returns;
+            let pool: Vec<usize> = (0..other.nodes.len())
+                .filter(|&j| other.nodes[j].primitive().returns == ty)
+                .collect();
+            if pool.is_empty() {
+                continue;
+            }
+
+            let donor = pool[rng.random_range(0..pool.len())];
+            let end = other.subtree_end(donor);
+            let donor_depth = other_depths[donor..end].iter().max().unwrap() - other_depths[donor];
+            if depths[index] + donor_depth <= ctx.max_depth {
+                child = Some(self.splice(index, other.nodes[donor..end].iter().cloned()));
+                break;
+            }
+        }
+
+        let mut child = child.unwrap_or_else(|| self.clone());
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+/// A fitness function that penalizes large trees, which counters bloat.
+/// The fitness is the inner fitness minus `coefficient` times the number of nodes.
+pub struct Parsimony<F> {
+    /// The wrapped fitness function.
+    pub inner: F,
+
+    /// The fitness lost per node.
+    pub coefficient: f32,
+}
+
+impl<F> Parsimony<F> {
+    /// Creates a new [`Parsimony`] fitness function.
+    pub fn new(inner: F, coefficient: f32) -> Self {
+        Self { inner, coefficient }
+    }
+}
+
+impl<S: PrimitiveSet, F: FitnessFn<Tree<S>>> FitnessFn<Tree<S>> for Parsimony<F> {
+    fn fitness(&self, genome: &Tree<S>) -> f32 {
+        self.inner.fitness(genome) - self.coefficient * genome.len() as f32
+    }
+
+    fn prepare(&mut self, genomes: &[Tree<S>]) {
+        self.inner.prepare(genomes);
+    }
+}
@@ -12,6 +12,38 @@ pub mod builtin;
 #[cfg(feature = "speciation")]
 pub mod speciation;
 
+/// Novelty search, which scores genomes by how different their behaviour is from what has been seen before.
+#[cfg(feature = "novelty")]
+pub mod novelty;
+
+/// The MAP-Elites quality-diversity algorithm, which keeps the best genome found for each region of behaviour space.
+#[cfg(feature = "mapelites")]
+pub mod map_elites;
+
+/// The age-layered population structure (ALPS), which keeps genomes of different ages from competing with each other.
+#[cfg(feature = "alps")]
+pub mod alps;
+
+/// Differential evolution, an optimizer for genomes made of real-valued parameters.
+#[cfg(feature = "de")]
+pub mod differential_evolution;
+
+/// CMA-ES, an optimizer that adapts a multivariate normal search distribution to real-valued genomes.
+#[cfg(feature = "cmaes")]
+pub mod cma_es;
+
+/// Estimation-of-distribution algorithms for bit-string genomes.
+#[cfg(feature = "eda")]
+pub mod eda;
+
+/// Ready-made genome types with standard mutation and crossover operators.
+#[cfg(feature = "genomes")]
+pub mod genomes;
+
+/// Genetic programming, where genomes are programs.
+#[cfg(feature = "gp")]
+pub mod gp;
+
 /// Used to quickly import everything this crate has to offer.
 /// Simply add `use genetic_rs::prelude::*` to begin using this crate.
 pub mod prelude;
@@ -23,12 +55,176 @@ use rayon::prelude::*;
 pub trait Eliminator<G> {
     /// Tests and eliminates the unfit from the simulation.
     fn eliminate(&mut self, genomes: Vec<G>) -> Vec<G>;
+
+    /// Same as [`Eliminator::eliminate`], but also returns whatever was learned about the survivors (such as their fitness)
+    /// so that the [`Repopulator`] can make use of it. This is what [`GeneticSim`] calls.
+    /// By default, this wraps the output of [`Eliminator::eliminate`] without any metadata.
+    fn eliminate_evaluated(&mut self, genomes: Vec<G>) -> EvaluatedPopulation<G> {
+        EvaluatedPopulation::new(self.eliminate(genomes))
+    }
 }
 
 /// Refills the population of the simulation based on survivors.
 pub trait Repopulator<G> {
     /// Replaces the genomes in the simulation.
     fn repopulate(&mut self, genomes: &mut Vec<G>, target_size: usize);
+
+    /// Same as [`Repopulator::repopulate`], but with access to whatever the [`Eliminator`] learned about the survivors.
+    /// This is what [`GeneticSim`] calls. By default, the metadata is discarded and [`Repopulator::repopulate`] is used.
+    fn repopulate_evaluated(
+        &mut self,
+        population: EvaluatedPopulation<G>,
+        target_size: usize,
+    ) -> Vec<G> {
+        let mut genomes = population.genomes;
+        self.repopulate(&mut genomes, target_size);
+        genomes
+    }
+}
+
+/// A population of genomes along with what an [`Eliminator`] learned about them while evaluating them.
+/// Every metadata vector that is present has exactly one entry per genome, in the same order as [`genomes`][EvaluatedPopulation::genomes].
+#[derive(Clone, Debug, PartialEq)]
+pub struct EvaluatedPopulation<G> {
+    /// The genomes in the population.
+    pub genomes: Vec<G>,
+
+    /// The fitness of each genome, if known.
+    pub fitness: Option<Vec<f32>>,
+
+    /// The species each genome belongs to, if known.
+    pub species: Option<Vec<usize>>,
+
+    /// The age of each genome, if known.
+    pub ages: Option<Vec<usize>>,
+}
+
+/// A view of a single genome in an [`EvaluatedPopulation`], along with its metadata.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EvaluatedGenome<'a, G> {
+    /// The genome.
+    pub genome: &'a G,
+
+    /// The fitness of the genome, if known.
+    pub fitness: Option<f32>,
+
+    /// The species the genome belongs to, if known.
+    pub species: Option<usize>,
+
+    /// The age of the genome, if known.
+    pub age: Option<usize>,
+}
+
+impl<G> EvaluatedPopulation<G> {
+    /// Creates a new [`EvaluatedPopulation`] without any metadata.
+    pub fn new(genomes: Vec<G>) -> Self {
+        Self {
+            genomes,
+            fitness: None,
+            species: None,
+            ages: None,
+        }
+    }
+
+    /// Sets the fitness of each genome. Panics if the length does not match the number of genomes.
+    pub fn with_fitness(mut self, fitness: Vec<f32>) -> Self {
+        assert_eq!(fitness.len(), self.genomes.len(), "Fitness length mismatch");
+        self.fitness = Some(fitness);
+        self
+    }
+
+    /// Sets the species of each genome. Panics if the length does not match the number of genomes.
+    pub fn with_species(mut self, species: Vec<usize>) -> Self {
+        assert_eq!(species.len(), self.genomes.len(), "Species length mismatch");
+        self.species = Some(species);
+        self
+    }
+
+    /// Sets the age of each genome. Panics if the length does not match the number of genomes.
+    pub fn with_ages(mut self, ages: Vec<usize>) -> Self {
+        assert_eq!(ages.len(), self.genomes.len(), "Ages length mismatch");
+        self.ages = Some(ages);
+        self
+    }
+
+    /// The number of genomes in the population.
+    pub fn len(&self) -> usize {
+        self.genomes.len()
+    }
+
+    /// Whether the population is empty.
+    pub fn is_empty(&self) -> bool {
+        self.genomes.is_empty()
+    }
+
+    /// Keeps the first `len` genomes and their metadata, dropping the rest.
+    pub fn truncate(&mut self, len: usize) {
+        self.genomes.truncate(len);
+        if let Some(fitness) = &mut self.fitness {
+            fitness.truncate(len);
+        }
+        if let Some(species) = &mut self.species {
+            species.truncate(len);
+        }
+        if let Some(ages) = &mut self.ages {
+            ages.truncate(len);
+        }
+    }
+
+    /// Gets a genome and its metadata by index.
+    pub fn get(&self, index: usize) -> Option<EvaluatedGenome<'_, G>> {
+        Some(EvaluatedGenome {
+            genome: self.genomes.get(index)?,
+            fitness: self.fitness.as_ref().map(|f| f[index]),
+            species: self.species.as_ref().map(|s| s[index]),
+            age: self.ages.as_ref().map(|a| a[index]),
+        })
+    }
+
+    /// Iterates over the genomes and their metadata.
+    pub fn iter(&self) -> impl Iterator<Item = EvaluatedGenome<'_, G>> {
+        (0..self.len()).map(|i| self.get(i).unwrap())
+    }
+
+    /// Appends another population to this one.
+    /// Appending an empty population does nothing. Otherwise, metadata is only kept if both populations have it
+    /// (or if this population is empty). Species ids from `other` are offset past the ones already present,
+    /// since species numbered by separate eliminators are unrelated.
+    pub fn append(&mut self, mut other: Self) {
+        if other.is_empty() {
+            return;
+        }
+
+        if self.is_empty() {
+            *self = other;
+            return;
+        }
+
+        if let (Some(a), Some(b)) = (&self.species, &mut other.species) {
+            let offset = a.iter().max().map_or(0, |max| max + 1);
+            for species in b {
+                *species += offset;
+            }
+        }
+
+        fn merge<T>(a: &mut Option<Vec<T>>, b: Option<Vec<T>>) {
+            match (a.as_mut(), b) {
+                (Some(a), Some(mut b)) => a.append(&mut b),
+                _ => *a = None,
+            }
+        }
+
+        merge(&mut self.fitness, other.fitness);
+        merge(&mut self.species, other.species);
+        merge(&mut self.ages, other.ages);
+        self.genomes.append(&mut other.genomes);
+    }
+}
+
+impl<G> From<Vec<G>> for EvaluatedPopulation<G> {
+    fn from(genomes: Vec<G>) -> Self {
+        Self::new(genomes)
+    }
 }
 
 #[doc(hidden)]
@@ -101,8 +297,10 @@ where
         let genomes = std::mem::take(&mut self.genomes);
 
         let target_size = genomes.len();
-        self.genomes = self.eliminator.eliminate(genomes);
-        self.repopulator.repopulate(&mut self.genomes, target_size);
+        let survivors = self.eliminator.eliminate_evaluated(genomes);
+        self.genomes = self
+            .repopulator
+            .repopulate_evaluated(survivors, target_size);
     }
 
     /// Calls [`next_generation`][GeneticSim::next_generation] `count` number of times.
@@ -141,6 +339,44 @@ where
     }
 }
 
+/// A genome that can be converted to and from a vector of real-valued parameters.
+/// This is what [`DifferentialEvolution`][crate::differential_evolution::DifferentialEvolution] and
+/// [`CmaEs`][crate::cma_es::CmaEs] optimize. Every genome they work with must have the same number of parameters.
+#[cfg(any(feature = "de", feature = "cmaes"))]
+pub trait RealParameters: Sized {
+    /// The parameters of the genome.
+    fn to_parameters(&self) -> Vec<f64>;
+
+    /// Builds a genome from its parameters.
+    /// If the genome repairs the parameters (for example by clamping them), [`RealParameters::to_parameters`]
+    /// should return the repaired values, which are then used to update the search distribution.
+    fn from_parameters(parameters: Vec<f64>) -> Self;
+}
+
+#[cfg(any(feature = "de", feature = "cmaes"))]
+impl RealParameters for Vec<f64> {
+    fn to_parameters(&self) -> Vec<f64> {
+        self.clone()
+    }
+
+    fn from_parameters(parameters: Vec<f64>) -> Self {
+        parameters
+    }
+}
+
+#[cfg(any(feature = "de", feature = "cmaes"))]
+impl<const N: usize> RealParameters for [f64; N] {
+    fn to_parameters(&self) -> Vec<f64> {
+        self.to_vec()
+    }
+
+    fn from_parameters(parameters: Vec<f64>) -> Self {
+        parameters
+            .try_into()
+            .expect("Parameter count does not match array length")
+    }
+}
+
 /// Rayon version of the [`GenerateRandomCollection`] trait
 #[cfg(all(feature = "genrand", feature = "rayon"))]
 pub trait GenerateRandomCollectionParallel<T>
@@ -0,0 +1,359 @@
+use std::io;
+
+use crate::{
+    builtin::eliminator::FeatureBoundedFitnessFn, novelty::FeatureBoundedBehaviorFn,
+    FeatureBoundedGenome, FeatureBoundedRepopulator,
+};
+
+use rand::RngExt;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// One axis of a [`MapElites`] grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GridDimension {
+    /// The lowest behaviour value on this axis. Lower values are placed in the first bin.
+    pub min: f32,
+
+    /// The highest behaviour value on this axis. Higher values are placed in the last bin.
+    pub max: f32,
+
+    /// The number of bins this axis is divided into.
+    pub bins: usize,
+}
+
+impl GridDimension {
+    /// Creates a new [`GridDimension`].
+    /// Panics if `bins` is 0 or `min` is not less than `max`.
+    pub fn new(min: f32, max: f32, bins: usize) -> Self {
+        assert!(bins > 0, "A grid dimension must have at least one bin");
+        assert!(
+            min < max,
+            "A grid dimension's min must be less than its max"
+        );
+        Self { min, max, bins }
+    }
+
+    /// The bin that a behaviour value falls into.
+    pub fn bin(&self, value: f32) -> usize {
+        let t = (value - self.min) / (self.max - self.min);
+        ((t * self.bins as f32).max(0.0) as usize).min(self.bins - 1)
+    }
+}
+
+/// The best genome found for a single cell of a [`MapElites`] grid.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Elite<G> {
+    /// The genome occupying the cell.
+    pub genome: G,
+
+    /// The fitness of the genome.
+    pub fitness: f32,
+
+    /// The behaviour descriptor of the genome.
+    pub behavior: Vec<f32>,
+}
+
+/// An exported [`MapElites`] cell. Created by [`MapElites::export`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArchiveEntry<G> {
+    /// The coordinates of the cell in the grid, one bin index per dimension.
+    pub coords: Vec<usize>,
+
+    /// The elite occupying the cell.
+    pub elite: Elite<G>,
+}
+
+/// Summary statistics of a [`MapElites`] archive.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MapElitesStats {
+    /// The number of cells containing an elite.
+    pub filled_cells: usize,
+
+    /// The total number of cells in the grid.
+    pub total_cells: usize,
+
+    /// The fraction of cells containing an elite (0.0 - 1.0).
+    pub coverage: f32,
+
+    /// The sum of the fitness of every elite. See [`MapElites::qd_score`].
+    pub qd_score: f32,
+
+    /// The highest fitness in the archive, if it isn't empty.
+    pub max_fitness: Option<f32>,
+}
+
+/// A MAP-Elites quality-diversity driver.
+///
+/// Genomes are binned into a grid by their behaviour descriptor, and each cell keeps only the fittest genome
+/// that has landed in it. Each [`step`][MapElites::step] picks random elites from the archive, hands them to the
+/// [`Repopulator`][crate::Repopulator] (such as [`MitosisRepopulator`][crate::builtin::repopulator::MitosisRepopulator]
+/// or [`CrossoverRepopulator`][crate::builtin::repopulator::CrossoverRepopulator]) to create new candidates,
+/// and tries to insert every candidate into the grid.
+///
+/// The archive starts empty, so it must be seeded with [`insert_all`][MapElites::insert_all]
+/// (or [`seed_random`][MapElites::seed_random]) before stepping.
+pub struct MapElites<G, F, B, R>
+where
+    G: FeatureBoundedGenome,
+    F: FeatureBoundedFitnessFn<G>,
+    B: FeatureBoundedBehaviorFn<G>,
+    R: FeatureBoundedRepopulator<G>,
+{
+    /// The fitness function used to judge which genome is the elite of a cell.
+    pub fitness_fn: F,
+
+    /// The function mapping genomes to behaviour descriptors.
+    pub behavior_fn: B,
+
+    /// The repopulator used to create new candidates from the chosen elites.
+    pub repopulator: R,
+
+    dimensions: Vec<GridDimension>,
+    cells: Vec<Option<Elite<G>>>,
+}
+
+impl<G, F, B, R> MapElites<G, F, B, R>
+where
+    G: FeatureBoundedGenome + Clone,
+    F: FeatureBoundedFitnessFn<G>,
+    B: FeatureBoundedBehaviorFn<G>,
+    R: FeatureBoundedRepopulator<G>,
+{
+    /// Creates a new [`MapElites`] with an empty grid.
+    /// Every behaviour descriptor should have one value per dimension.
+    /// Panics if `dimensions` is empty.
+    pub fn new(
+        dimensions: Vec<GridDimension>,
+        fitness_fn: F,
+        behavior_fn: B,
+        repopulator: R,
+    ) -> Self {
+        assert!(
+            !dimensions.is_empty(),
+            "A MAP-Elites grid needs at least one dimension"
+        );
+        let total = dimensions.iter().map(|d| d.bins).product();
+        Self {
+            fitness_fn,
+            behavior_fn,
+            repopulator,
+            dimensions,
+            cells: (0..total).map(|_| None).collect(),
+        }
+    }
+
+    /// The dimensions of the grid.
+    pub fn dimensions(&self) -> &[GridDimension] {
+        &self.dimensions
+    }
+
+    /// The cells of the grid in row-major order (the last dimension changes fastest).
+    pub fn cells(&self) -> &[Option<Elite<G>>] {
+        &self.cells
+    }
+
+    /// The flat index of the cell that a behaviour descriptor falls into.
+    pub fn cell_index(&self, behavior: &[f32]) -> usize {
+        assert_eq!(
+            behavior.len(),
+            self.dimensions.len(),
+            "Behaviour descriptor length must match the number of grid dimensions"
+        );
+        self.dimensions
+            .iter()
+            .zip(behavior)
+            .fold(0, |index, (dim, &value)| index * dim.bins + dim.bin(value))
+    }
+
+    /// Converts a flat cell index into per-dimension grid coordinates.
+    pub fn cell_coords(&self, mut index: usize) -> Vec<usize> {
+        let mut coords = vec![0; self.dimensions.len()];
+        for (coord, dim) in coords.iter_mut().zip(&self.dimensions).rev() {
+            *coord = index % dim.bins;
+            index /= dim.bins;
+        }
+        coords
+    }
+
+    /// Iterates over every elite in the archive.
+    pub fn elites(&self) -> impl Iterator<Item = &Elite<G>> {
+        self.cells.iter().flatten()
+    }
+
+    /// The elite with the highest fitness, if the archive isn't empty.
+    pub fn best(&self) -> Option<&Elite<G>> {
+        self.elites().max_by(|a, b| a.fitness.total_cmp(&b.fitness))
+    }
+
+    /// The fraction of cells that contain an elite (0.0 - 1.0).
+    pub fn coverage(&self) -> f32 {
+        self.elites().count() as f32 / self.cells.len() as f32
+    }
+
+    /// The QD-score of the archive: the sum of the fitness of every elite.
+    /// This rewards both filling more cells and improving existing ones, as long as fitness is non-negative.
+    pub fn qd_score(&self) -> f32 {
+        self.elites().map(|e| e.fitness).sum()
+    }
+
+    /// Computes the summary statistics of the archive.
+    pub fn stats(&self) -> MapElitesStats {
+        let filled_cells = self.elites().count();
+        MapElitesStats {
+            filled_cells,
+            total_cells: self.cells.len(),
+            coverage: filled_cells as f32 / self.cells.len() as f32,
+            qd_score: self.qd_score(),
+            max_fitness: self.best().map(|e| e.fitness),
+        }
+    }
+
+    /// Tries to place an already-evaluated genome into the grid.
+    /// Returns whether it became the elite of its cell.
+    pub fn insert_evaluated(&mut self, genome: G, fitness: f32, behavior: Vec<f32>) -> bool {
+        let index = self.cell_index(&behavior);
+        let cell = &mut self.cells[index];
+        if cell.as_ref().is_some_and(|e| e.fitness >= fitness) {
+            return false;
+        }
+        *cell = Some(Elite {
+            genome,
+            fitness,
+            behavior,
+        });
+        true
+    }
+
+    /// Evaluates genomes and tries to place each of them into the grid.
+    /// Returns the number of genomes that became the elite of their cell.
+    pub fn insert_all(&mut self, genomes: Vec<G>) -> usize {
+        self.fitness_fn.prepare(&genomes);
+
+        let mut inserted = 0;
+        for (genome, fitness, behavior) in self.evaluate(genomes) {
+            if self.insert_evaluated(genome, fitness, behavior) {
+                inserted += 1;
+            }
+        }
+        inserted
+    }
+
+    /// Creates `batch_size` new candidates from randomly chosen elites and tries to insert them into the grid.
+    /// Returns the number of candidates that became the elite of their cell.
+    /// Panics if the archive is empty.
+    pub fn step(&mut self, batch_size: usize) -> usize {
+        let elites: Vec<&Elite<G>> = self.elites().collect();
+        assert!(
+            !elites.is_empty(),
+            "The MAP-Elites archive must be seeded before stepping"
+        );
+
+        let mut rng = rand::rng();
+        let mut genomes: Vec<G> = (0..batch_size)
+            .map(|_| elites[rng.random_range(0..elites.len())].genome.clone())
+            .collect();
+
+        self.repopulator.repopulate(&mut genomes, batch_size * 2);
+        let candidates = genomes.split_off(batch_size);
+        self.insert_all(candidates)
+    }
+
+    /// Calls [`step`][MapElites::step] `count` number of times.
+    pub fn perform_steps(&mut self, count: usize, batch_size: usize) {
+        for _ in 0..count {
+            self.step(batch_size);
+        }
+    }
+
+    /// Exports every filled cell along with its grid coordinates.
+    pub fn export(&self) -> Vec<ArchiveEntry<G>> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter_map(|(i, cell)| {
+                cell.as_ref().map(|elite| ArchiveEntry {
+                    coords: self.cell_coords(i),
+                    elite: elite.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// The fitness of every cell in row-major order, or [`None`] for empty cells.
+    /// Useful for drawing heatmaps of the archive.
+    pub fn fitness_grid(&self) -> Vec<Option<f32>> {
+        self.cells
+            .iter()
+            .map(|cell| cell.as_ref().map(|e| e.fitness))
+            .collect()
+    }
+
+    /// Writes every filled cell as a CSV row of grid coordinates, behaviour values, and fitness.
+    /// Genomes are not written, since they have no general textual representation.
+    pub fn write_csv(&self, mut writer: impl io::Write) -> io::Result<()> {
+        let dims = self.dimensions.len();
+        let header: Vec<String> = (0..dims)
+            .map(|d| format!("cell_{d}"))
+            .chain((0..dims).map(|d| format!("behavior_{d}")))
+            .chain(std::iter::once("fitness".to_string()))
+            .collect();
+        writeln!(writer, "{}", header.join(","))?;
+
+        for entry in self.export() {
+            let row: Vec<String> = entry
+                .coords
+                .iter()
+                .map(|c| c.to_string())
+                .chain(entry.elite.behavior.iter().map(|b| b.to_string()))
+                .chain(std::iter::once(entry.elite.fitness.to_string()))
+                .collect();
+            writeln!(writer, "{}", row.join(","))?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn evaluate(&self, genomes: Vec<G>) -> Vec<(G, f32, Vec<f32>)> {
+        genomes
+            .into_iter()
+            .map(|g| {
+                let fitness = self.fitness_fn.fitness(&g);
+                let behavior = self.behavior_fn.behavior(&g);
+                (g, fitness, behavior)
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn evaluate(&self, genomes: Vec<G>) -> Vec<(G, f32, Vec<f32>)> {
+        let fitness_fn = &self.fitness_fn;
+        let behavior_fn = &self.behavior_fn;
+        genomes
+            .into_par_iter()
+            .map(|g| {
+                let fitness = fitness_fn.fitness(&g);
+                let behavior = behavior_fn.behavior(&g);
+                (g, fitness, behavior)
+            })
+            .collect()
+    }
+}
+
+#[cfg(feature = "genrand")]
+impl<G, F, B, R> MapElites<G, F, B, R>
+where
+    G: FeatureBoundedGenome + Clone + crate::GenerateRandom,
+    F: FeatureBoundedFitnessFn<G>,
+    B: FeatureBoundedBehaviorFn<G>,
+    R: FeatureBoundedRepopulator<G>,
+{
+    /// Seeds the archive with `count` random genomes.
+    /// Returns the number of genomes that became the elite of their cell.
+    pub fn seed_random(&mut self, rng: &mut impl rand::Rng, count: usize) -> usize {
+        let genomes = (0..count).map(|_| G::gen_random(rng)).collect();
+        self.insert_all(genomes)
+    }
+}
@@ -0,0 +1,305 @@
+use crate::{builtin::eliminator::FitnessFn, FeatureBoundedGenome};
+
+use rand::RngExt;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Maps a genome to a behaviour descriptor, a point in behaviour space.
+/// Any `Fn(&G) -> Vec<f32>` can be used as a behaviour function.
+/// Behaviour descriptors are compared with the euclidean distance, so every descriptor
+/// produced by the same function should have the same length.
+pub trait BehaviorFn<G> {
+    /// Computes the behaviour descriptor of a genome.
+    fn behavior(&self, genome: &G) -> Vec<f32>;
+}
+
+impl<G, F> BehaviorFn<G> for F
+where
+    F: Fn(&G) -> Vec<f32>,
+{
+    fn behavior(&self, genome: &G) -> Vec<f32> {
+        (self)(genome)
+    }
+}
+
+#[doc(hidden)]
+#[cfg(not(feature = "rayon"))]
+pub trait FeatureBoundedBehaviorFn<G>: BehaviorFn<G> {}
+#[cfg(not(feature = "rayon"))]
+impl<G, T: BehaviorFn<G>> FeatureBoundedBehaviorFn<G> for T {}
+
+#[doc(hidden)]
+#[cfg(feature = "rayon")]
+pub trait FeatureBoundedBehaviorFn<G>: BehaviorFn<G> + Send + Sync {}
+#[cfg(feature = "rayon")]
+impl<G, T: BehaviorFn<G> + Send + Sync> FeatureBoundedBehaviorFn<G> for T {}
+
+/// The euclidean distance between two behaviour descriptors.
+/// Extra dimensions in the longer descriptor are ignored.
+pub fn behavior_distance(a: &[f32], b: &[f32]) -> f32 {
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| (x - y) * (x - y))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// Decides which behaviours are added to a [`NoveltyFitness`] archive each generation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArchivePolicy {
+    /// Never add anything to the archive. Novelty is then measured against the current population only.
+    Never,
+
+    /// Add every behaviour whose novelty score is at least the given value.
+    Threshold(f32),
+
+    /// Add each behaviour with the given probability (0.0 - 1.0), regardless of its novelty.
+    /// [`NoveltyFitness::new`] panics if the probability is outside that range.
+    Random(f32),
+
+    /// Add the given number of most novel behaviours from each generation.
+    MostNovel(usize),
+}
+
+/// A fitness source that scores genomes by novelty instead of an objective.
+///
+/// The novelty of a genome is the mean distance from its behaviour to the `k` nearest behaviours
+/// in the current population and the archive. Since [`NoveltyFitness`] implements [`FitnessFn`],
+/// it can be used with [`FitnessEliminator`][crate::builtin::eliminator::FitnessEliminator] or any other
+/// builtin eliminator that takes a fitness function (including a [`KnockoutEliminator`][crate::builtin::eliminator::KnockoutEliminator]
+/// through a [`FitnessKnockoutFn`][crate::builtin::eliminator::FitnessKnockoutFn]), and it can be mixed with an
+/// objective using [`BlendedFitness`].
+///
+/// The population snapshot and archive are updated in [`FitnessFn::prepare`], which the builtin eliminators
+/// call once per generation. Behaviours chosen by the [`ArchivePolicy`] are added to the archive at the start
+/// of the following generation, so every genome in a generation is scored against the same archive.
+/// Note that the behaviour function is called twice for each genome per generation (once to snapshot the
+/// population and once to score the genome), so expensive behaviours should be cached in the genome itself.
+pub struct NoveltyFitness<B: FeatureBoundedBehaviorFn<G>, G: FeatureBoundedGenome> {
+    /// The function mapping genomes to behaviour descriptors.
+    pub behavior_fn: B,
+
+    /// The number of nearest neighbours used to compute novelty.
+    pub k: usize,
+
+    /// The policy that decides which behaviours are archived.
+    pub policy: ArchivePolicy,
+
+    /// The maximum number of behaviours kept in the archive. When exceeded, the oldest behaviours are discarded.
+    /// [`None`] means the archive grows without bound.
+    pub max_archive_size: Option<usize>,
+
+    /// The archive of previously novel behaviours.
+    pub archive: Vec<Vec<f32>>,
+
+    population: Vec<Vec<f32>>,
+    pending: Vec<Vec<f32>>,
+    _marker: std::marker::PhantomData<G>,
+}
+
+impl<B, G> NoveltyFitness<B, G>
+where
+    B: FeatureBoundedBehaviorFn<G>,
+    G: FeatureBoundedGenome,
+{
+    /// The default number of nearest neighbours used to compute novelty.
+    pub const DEFAULT_K: usize = 15;
+
+    /// Creates a new [`NoveltyFitness`] with an empty, unbounded archive.
+    /// Panics if `k` is 0 or if an [`ArchivePolicy::Random`] probability is outside [0, 1].
+    pub fn new(behavior_fn: B, k: usize, policy: ArchivePolicy) -> Self {
+        assert!(k > 0, "k must be greater than 0");
+        if let ArchivePolicy::Random(chance) = policy {
+            assert!(
+                (0.0..=1.0).contains(&chance),
+                "archive probability must be between 0 and 1, got {chance}"
+            );
+        }
+        Self {
+            behavior_fn,
+            k,
+            policy,
+            max_archive_size: None,
+            archive: Vec::new(),
+            population: Vec::new(),
+            pending: Vec::new(),
+            _marker: std::marker::PhantomData,
+        }
+    }
+
+    /// Sets the maximum archive size. See [`NoveltyFitness::max_archive_size`].
+    pub fn with_max_archive_size(mut self, max_archive_size: usize) -> Self {
+        self.max_archive_size = Some(max_archive_size);
+        self
+    }
+
+    /// Blends this novelty score with an objective fitness function.
+    /// See [`BlendedFitness`] for how the scores are combined.
+    pub fn blend<F: FitnessFn<G>>(
+        self,
+        objective: F,
+        novelty_weight: f32,
+    ) -> BlendedFitness<Self, F, G> {
+        BlendedFitness::new(self, objective, novelty_weight)
+    }
+
+    /// The behaviours of the population passed to the most recent [`FitnessFn::prepare`] call.
+    pub fn population_behaviors(&self) -> &[Vec<f32>] {
+        &self.population
+    }
+
+    /// Computes the novelty of a behaviour against the current population and the archive.
+    /// If `in_population` is true, one exact match in the population is assumed to be
+    /// the behaviour itself and is not counted as a neighbour.
+    pub fn novelty(&self, behavior: &[f32], in_population: bool) -> f32 {
+        let mut distances: Vec<f32> = self
+            .population
+            .iter()
+            .chain(&self.archive)
+            .map(|other| behavior_distance(behavior, other))
+            .collect();
+
+        if in_population {
+            if let Some(i) = self.population.iter().position(|b| b == behavior) {
+                distances.swap_remove(i);
+            }
+        }
+
+        if distances.is_empty() {
+            return 0.0;
+        }
+
+        let k = self.k.min(distances.len());
+        distances.select_nth_unstable_by(k - 1, |a, b| a.total_cmp(b));
+        distances[..k].iter().sum::<f32>() / k as f32
+    }
+
+    fn archive_pending(&mut self) {
+        self.archive.append(&mut self.pending);
+        if let Some(max) = self.max_archive_size {
+            if self.archive.len() > max {
+                let excess = self.archive.len() - max;
+                self.archive.drain(..excess);
+            }
+        }
+    }
+
+    fn select_for_archive(&self) -> Vec<Vec<f32>> {
+        match self.policy {
+            ArchivePolicy::Never => Vec::new(),
+            ArchivePolicy::Threshold(threshold) => self
+                .population
+                .iter()
+                .filter(|b| self.novelty(b, true) >= threshold)
+                .cloned()
+                .collect(),
+            ArchivePolicy::Random(chance) => {
+                let mut rng = rand::rng();
+                self.population
+                    .iter()
+                    .filter(|_| rng.random_bool(chance as f64))
+                    .cloned()
+                    .collect()
+            }
+            ArchivePolicy::MostNovel(count) => {
+                let mut scored: Vec<(&Vec<f32>, f32)> = self
+                    .population
+                    .iter()
+                    .map(|b| (b, self.novelty(b, true)))
+                    .collect();
+                scored.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+                scored
+                    .into_iter()
+                    .take(count)
+                    .map(|(b, _)| b.clone())
+                    .collect()
+            }
+        }
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    fn snapshot(&self, genomes: &[G]) -> Vec<Vec<f32>> {
+        genomes
+            .iter()
+            .map(|g| self.behavior_fn.behavior(g))
+            .collect()
+    }
+
+    #[cfg(feature = "rayon")]
+    fn snapshot(&self, genomes: &[G]) -> Vec<Vec<f32>> {
+        genomes
+            .par_iter()
+            .map(|g| self.behavior_fn.behavior(g))
+            .collect()
+    }
+}
+
+impl<B, G> FitnessFn<G> for NoveltyFitness<B, G>
+where
+    B: FeatureBoundedBehaviorFn<G>,
+    G: FeatureBoundedGenome,
+{
+    fn fitness(&self, genome: &G) -> f32 {
+        let behavior = self.behavior_fn.behavior(genome);
+        self.novelty(&behavior, true)
+    }
+
+    fn prepare(&mut self, genomes: &[G]) {
+        self.archive_pending();
+        self.population = self.snapshot(genomes);
+        self.pending = self.select_for_archive();
+    }
+}
+
+/// A fitness source that combines two other fitness sources with a weighted sum.
+/// The score is `weight * first + (1.0 - weight) * second`.
+/// This is mostly used to mix novelty with an objective (see [`NoveltyFitness::blend`]),
+/// but it works with any pair of fitness functions.
+pub struct BlendedFitness<A: FitnessFn<G>, B: FitnessFn<G>, G> {
+    /// The first fitness source.
+    pub first: A,
+
+    /// The second fitness source.
+    pub second: B,
+
+    /// The weight of the first fitness source. Must be between 0.0 and 1.0.
+    pub weight: f32,
+
+    _marker: std::marker::PhantomData<G>,
+}
+
+impl<A, B, G> BlendedFitness<A, B, G>
+where
+    A: FitnessFn<G>,
+    B: FitnessFn<G>,
+{
+    /// Creates a new [`BlendedFitness`].
+    /// Panics if the weight is not between 0.0 and 1.0.
+    pub fn new(first: A, second: B, weight: f32) -> Self {
+        if !(0.0..=1.0).contains(&weight) {
+            panic!("Weight must be between 0.0 and 1.0");
+        }
+        Self {
+            first,
+            second,
+            weight,
+            _marker: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<A, B, G> FitnessFn<G> for BlendedFitness<A, B, G>
+where
+    A: FitnessFn<G>,
+    B: FitnessFn<G>,
+{
+    fn fitness(&self, genome: &G) -> f32 {
+        self.weight * self.first.fitness(genome) + (1.0 - self.weight) * self.second.fitness(genome)
+    }
+
+    fn prepare(&mut self, genomes: &[G]) {
+        self.first.prepare(genomes);
+        self.second.prepare(genomes);
+    }
+}
@@ -5,7 +5,63 @@ pub use crate::*;
 #[cfg(feature = "builtin")]
 pub use crate::builtin::{eliminator::*, repopulator::*};
 
+#[cfg(feature = "impls")]
+pub use crate::builtin::impls::{FloatMutation, IntegerMutation};
+
 #[cfg(feature = "speciation")]
 pub use crate::speciation::Speciated;
 
+#[cfg(feature = "novelty")]
+pub use crate::novelty::{ArchivePolicy, BehaviorFn, BlendedFitness, NoveltyFitness};
+
+#[cfg(feature = "mapelites")]
+pub use crate::map_elites::{GridDimension, MapElites};
+
+#[cfg(feature = "alps")]
+pub use crate::alps::{AgeLayers, Aged, AlpsEliminator, AlpsRepopulator};
+
+#[cfg(feature = "de")]
+pub use crate::differential_evolution::{DeParams, DeStrategy, DifferentialEvolution, JdeSettings};
+
+#[cfg(feature = "cmaes")]
+pub use crate::cma_es::{CmaEs, CmaTermination, RestartStrategy};
+
+#[cfg(feature = "eda")]
+pub use crate::eda::{EdaModel, EdaRepopulator};
+
+#[cfg(feature = "genomes")]
+pub use crate::genomes::bits::{BitCrossover, BitOperators, BitString};
+
+#[cfg(feature = "genomes")]
+pub use crate::genomes::discrete::{
+    Alphabets, CategoricalVector, DiscreteMutation, IntegerRanges, IntegerVector,
+};
+
+#[cfg(feature = "genomes")]
+pub use crate::genomes::permutation::{
+    Permutation, PermutationCrossover, PermutationMutation, PermutationOperators,
+};
+
+#[cfg(feature = "genomes")]
+pub use crate::genomes::real::{
+    BoundaryHandling, RealBounds, RealCrossover, RealMutation, RealOperators, RealVector,
+};
+
+#[cfg(feature = "gp")]
+pub use crate::gp::cartesian::{
+    Cartesian, CartesianDag, CartesianMutation, CartesianNode, FunctionSet,
+};
+
+#[cfg(feature = "gp")]
+pub use crate::gp::grammar::{
+    CodonCrossover, CodonOperators, Codons, Derivation, Grammar, GrammarError, GrammarFitness,
+    MappingError,
+};
+
+#[cfg(feature = "gp")]
+pub use crate::gp::linear::{Instruction, InstructionSet, LinearOperators, Program};
+
+#[cfg(feature = "gp")]
+pub use crate::gp::tree::{Parsimony, Primitive, PrimitiveSet, Tree, TreeMutation, TreeOperators};
+
 pub use rand::prelude::*;
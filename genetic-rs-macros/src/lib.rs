@@ -10,7 +10,7 @@ use quote::quote_spanned;
 use quote::ToTokens;
 use syn::parse_quote;
 use syn::spanned::Spanned;
-use syn::{parse_macro_input, Data, DeriveInput, Fields};
+use syn::{parse_macro_input, Data, DataEnum, DeriveInput, Fields};
 
 /// Determines the context handling strategy for a derive macro.
 enum ContextKind {
@@ -32,7 +32,9 @@ struct ContextInfo {
     kind: ContextKind,
 }
 
+// other settings can share the attribute, so unknown fields are ignored here
 #[derive(FromMeta)]
+#[darling(allow_unknown_fields)]
 struct ContextArgs {
     with_context: Option<syn::Path>,
     create_context: Option<CreateContext>,
@@ -57,12 +59,115 @@ struct MitosisSettings {
     _with_context: Option<syn::Path>,
 }
 
+#[cfg(feature = "crossover")]
+#[derive(FromAttributes)]
+#[darling(attributes(n_crossover))]
+struct NParentCrossoverSettings {
+    use_randmut: Option<bool>,
+}
+
+#[cfg(feature = "speciation")]
+#[derive(FromAttributes)]
+#[darling(attributes(speciated))]
+struct SpeciatedSettings {
+    /// Divide the weighted sum by the total weight.
+    #[darling(default)]
+    normalize: bool,
+
+    #[darling(rename = "create_context")]
+    _create_context: Option<CreateContext>,
+
+    #[darling(rename = "with_context")]
+    _with_context: Option<syn::Path>,
+}
+
+/// Field-level `#[speciated(...)]` settings.
+#[cfg(feature = "speciation")]
+#[derive(FromAttributes)]
+#[darling(attributes(speciated))]
+struct SpeciatedFieldSettings {
+    /// Leave the field out of the divergence.
+    #[darling(default)]
+    skip: bool,
+
+    /// Multiplies the field's divergence. Defaults to 1.
+    weight: Option<NonNegative>,
+}
+
+#[cfg(feature = "speciation")]
+impl SpeciatedFieldSettings {
+    fn of(field: &syn::Field) -> Self {
+        let settings = Self::from_attributes(&field.attrs).unwrap();
+        if settings.skip && settings.weight.is_some() {
+            panic!("a skipped field cannot also have a `weight`");
+        }
+        settings
+    }
+
+    fn uses_context(field: &syn::Field) -> bool {
+        !Self::of(field).skip
+    }
+}
+
+/// The weighted sum of field divergences, given the field and `&` expressions for it in each genome and its context.
+/// If `normalize` is set, the sum is divided by the total weight.
+#[cfg(feature = "speciation")]
+fn weighted_divergence<'a>(
+    fields: impl Iterator<Item = (&'a syn::Field, TokenStream2, TokenStream2, TokenStream2)>,
+    normalize: bool,
+) -> TokenStream2 {
+    let mut total = 0.0;
+    let mut terms = Vec::new();
+    let mut skipped = Vec::new();
+    for (field, a, b, ctx) in fields {
+        let settings = SpeciatedFieldSettings::of(field);
+        if settings.skip {
+            skipped.push(quote! { let _ = (#a, #b); });
+            continue;
+        }
+        let ty = &field.ty;
+        let divergence = quote_spanned! {ty.span()=>
+            <#ty as genetic_rs_common::prelude::Speciated>::divergence(#a, #b, #ctx)
+        };
+        match settings.weight {
+            Some(NonNegative(weight)) => {
+                total += weight;
+                let weight = weight as f32;
+                terms.push(quote! { #weight * #divergence });
+            }
+            None => {
+                total += 1.0;
+                terms.push(divergence);
+            }
+        }
+    }
+
+    let sum = if terms.is_empty() {
+        quote! { 0.0 }
+    } else {
+        quote! { #(#terms)+* }
+    };
+    let sum = if normalize && total > 0.0 {
+        let total = total as f32;
+        quote! { (#sum) / #total }
+    } else {
+        sum
+    };
+    quote! {
+        #(#skipped)*
+        #sum
+    }
+}
+
 /// Resolves the context info from the attribute on an AST node.
 ///
 /// `trait_name` is the trait whose `Context` associated type is used when
 /// generating a per-field context struct (i.e., for `create_context`).
 /// `attr_path` is the path of the attribute to look for (e.g. `randmut`).
 ///
+/// `include` decides which fields get a context. Fields it rejects, such as skipped ones,
+/// are left out of a generated context struct (or given `()` in a tuple struct, to keep positions).
+///
 /// If the attribute is absent, or if it does not contain `create_context` or
 /// `with_context`, returns `None` and the caller should fall back to inferring
 /// the context type from the first struct field.
@@ -71,6 +176,7 @@ fn resolve_context(
     trait_name: syn::Ident,
     attr_path: syn::Path,
     fallback_ctx: TokenStream2,
+    include: &dyn Fn(&syn::Field) -> bool,
 ) -> ContextInfo {
     let name = &ast.ident;
     let vis = ast.vis.to_token_stream();
@@ -102,6 +208,13 @@ fn resolve_context(
                             .map(|field| {
                                 let ty = &field.ty;
                                 let ty_span = ty.span();
+                                if !include(field) {
+                                    return if field.ident.is_some() {
+                                        quote! {}
+                                    } else {
+                                        quote! { #vis (), }
+                                    };
+                                }
                                 if let Some(field_name) = &field.ident {
                                     quote_spanned! {ty_span=>
                                         #vis #field_name: <#ty as genetic_rs_common::prelude::#trait_name>::Context,
@@ -122,7 +235,31 @@ fn resolve_context(
                             quote! { #doc #derives #vis struct #ident { #fields_ts } }
                         }
                     }
-                    Data::Enum(_) => panic!("enums not supported"),
+                    Data::Enum(e) => {
+                        // the names join the variant and field, so `AB { c }` and `A { b_c }` would both be `a_b_c`.
+                        let mut seen = std::collections::HashMap::new();
+                        let fields: TokenStream2 = e
+                            .variants
+                            .iter()
+                            .flat_map(|v| v.fields.iter().enumerate().map(move |(i, f)| (v, i, f)))
+                            .filter(|(_, _, field)| include(field))
+                            .map(|(variant, i, field)| {
+                                let ty = &field.ty;
+                                let ty_span = ty.span();
+                                let field_name = enum_context_field(variant, i, field);
+                                if let Some(other) = seen.insert(field_name.to_string(), &variant.ident) {
+                                    panic!(
+                                        "variants `{}` and `{}` both generate the context field `{}`; rename one of their fields",
+                                        other, variant.ident, field_name
+                                    );
+                                }
+                                quote_spanned! {ty_span=>
+                                    #vis #field_name: <#ty as genetic_rs_common::prelude::#trait_name>::Context,
+                                }
+                            })
+                            .collect();
+                        quote! { #doc #derives #vis struct #ident { #fields } }
+                    }
                     Data::Union(_) => panic!("unions not supported"),
                 };
 
@@ -150,29 +287,314 @@ fn resolve_context(
     }
 }
 
+/// Converts a variant name like `LeakyRelu` to `leaky_relu`.
+fn snake_case(ident: &syn::Ident) -> String {
+    let mut out = String::new();
+    for (i, c) in ident.to_string().chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(c.to_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// The name of the per-field context field for a field of an enum variant,
+/// such as `dense_units` for the `units` field of `Dense`, or `dropout_0` for the first field of `Dropout(f32)`.
+fn enum_context_field(variant: &syn::Variant, i: usize, field: &syn::Field) -> syn::Ident {
+    let variant_name = snake_case(&variant.ident);
+    match &field.ident {
+        Some(field_name) => quote::format_ident!("{}_{}", variant_name, field_name),
+        None => quote::format_ident!("{}_{}", variant_name, i),
+    }
+}
+
+/// The context passed to a field of an enum variant.
+fn enum_field_ctx(
+    kind: &ContextKind,
+    variant: &syn::Variant,
+    i: usize,
+    field: &syn::Field,
+) -> TokenStream2 {
+    match kind {
+        ContextKind::PerField => {
+            let field_name = enum_context_field(variant, i, field);
+            quote! { &ctx.#field_name }
+        }
+        ContextKind::Shared => quote! { ctx },
+    }
+}
+
+/// The context type of the first field of any variant that `include` accepts, or `()` if there is none.
+fn enum_fallback_ctx(
+    e: &DataEnum,
+    trait_name: TokenStream2,
+    include: &dyn Fn(&syn::Field) -> bool,
+) -> TokenStream2 {
+    e.variants
+        .iter()
+        .flat_map(|v| v.fields.iter())
+        .find(|f| include(f))
+        .map_or_else(
+            || quote! { () },
+            |f| {
+                let ty = &f.ty;
+                quote! { <#ty as genetic_rs_common::prelude::#trait_name>::Context }
+            },
+        )
+}
+
+/// A pattern matching `variant` that binds each of its fields to `{prefix}{index}`, along with those bindings.
+fn variant_pattern(variant: &syn::Variant, prefix: &str) -> (TokenStream2, Vec<syn::Ident>) {
+    let ident = &variant.ident;
+    let bindings: Vec<syn::Ident> = (0..variant.fields.len())
+        .map(|i| quote::format_ident!("{}{}", prefix, i))
+        .collect();
+    let pattern = match &variant.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|f| &f.ident);
+            quote! { Self::#ident { #(#names: #bindings),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self::#ident(#(#bindings),*) },
+        Fields::Unit => quote! { Self::#ident },
+    };
+    (pattern, bindings)
+}
+
+/// Constructs `variant` with one expression per field.
+fn variant_construct(variant: &syn::Variant, values: Vec<TokenStream2>) -> TokenStream2 {
+    let ident = &variant.ident;
+    match &variant.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|f| &f.ident);
+            quote! { Self::#ident { #(#names: #values),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self::#ident(#(#values),*) },
+        Fields::Unit => quote! { Self::#ident },
+    }
+}
+
+/// Constructs `variant` with a randomly generated value for each field.
+fn variant_random(variant: &syn::Variant) -> TokenStream2 {
+    let values = variant
+        .fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            quote_spanned! {ty.span()=>
+                <#ty as genetic_rs_common::prelude::GenerateRandom>::gen_random(rng)
+            }
+        })
+        .collect();
+    variant_construct(variant, values)
+}
+
+/// A non-negative number, such as a variant weight, which can be written as either an integer or a float.
+struct NonNegative(f64);
+
+impl FromMeta for NonNegative {
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        let weight: f64 = match value {
+            syn::Lit::Float(f) => f.base10_parse()?,
+            syn::Lit::Int(i) => i.base10_parse()?,
+            _ => return Err(darling::Error::unexpected_lit_type(value)),
+        };
+        if !(weight >= 0.0 && weight.is_finite()) {
+            return Err(
+                darling::Error::custom("expected a finite, non-negative number").with_span(value),
+            );
+        }
+        Ok(Self(weight))
+    }
+}
+
+#[derive(FromAttributes)]
+#[darling(attributes(randmut))]
+struct RandMutVariantSettings {
+    weight: Option<NonNegative>,
+}
+
+/// `name` for a named field, or its index for a tuple field.
+fn field_access(i: usize, field: &syn::Field) -> TokenStream2 {
+    match &field.ident {
+        Some(name) => name.to_token_stream(),
+        None => syn::Index::from(i).to_token_stream(),
+    }
+}
+
+/// Field-level `#[randmut(...)]` settings.
+#[derive(FromAttributes)]
+#[darling(attributes(randmut))]
+struct RandMutFieldSettings {
+    /// Never mutate the field.
+    #[darling(default)]
+    skip: bool,
+
+    /// Scales the mutation rate passed to the field.
+    rate: Option<NonNegative>,
+
+    /// Mutates the field with `fn(&mut Field, &Context, f32, &mut impl Rng)` instead of its `RandomlyMutable` implementation.
+    /// It receives the context of the whole type.
+    with: Option<syn::Path>,
+}
+
+impl RandMutFieldSettings {
+    fn of(field: &syn::Field) -> Self {
+        let settings = Self::from_attributes(&field.attrs).unwrap();
+        if settings.skip && (settings.rate.is_some() || settings.with.is_some()) {
+            panic!("a skipped field cannot also have `rate` or `with`");
+        }
+        settings
+    }
+
+    /// Whether the field mutates through its own `RandomlyMutable` implementation, and so needs a context.
+    fn uses_context(field: &syn::Field) -> bool {
+        let settings = Self::of(field);
+        !settings.skip && settings.with.is_none()
+    }
+
+    /// Mutates `target`, a `&mut` expression for the field.
+    fn mutate(&self, field: &syn::Field, target: TokenStream2, ctx: TokenStream2) -> TokenStream2 {
+        let ty = &field.ty;
+        let rate = match &self.rate {
+            Some(scale) => {
+                let scale = scale.0 as f32;
+                quote! { rate * #scale }
+            }
+            None => quote! { rate },
+        };
+        match (&self.with, self.skip) {
+            (_, true) => quote! {},
+            (Some(path), _) => quote_spanned! {ty.span()=>
+                #path(#target, ctx, #rate, rng);
+            },
+            (None, _) => quote_spanned! {ty.span()=>
+                <#ty as genetic_rs_common::prelude::RandomlyMutable>::mutate(#target, #ctx, #rate, rng);
+            },
+        }
+    }
+}
+
+/// Field-level `#[crossover(...)]` settings.
+#[cfg(feature = "crossover")]
+#[derive(FromAttributes)]
+#[darling(attributes(crossover))]
+struct CrossoverFieldSettings {
+    /// Always take the field from the first parent. Shorthand for `take_from = "first"`.
+    #[darling(default)]
+    skip: bool,
+
+    /// Take the field from the `"first"`, `"second"` or a `"random"` parent instead of crossing it over.
+    take_from: Option<String>,
+}
+
+#[cfg(feature = "crossover")]
+impl CrossoverFieldSettings {
+    /// Which parent the field is cloned from, or `None` if it is crossed over.
+    fn source(field: &syn::Field) -> Option<String> {
+        let settings = Self::from_attributes(&field.attrs).unwrap();
+        match (settings.skip, settings.take_from) {
+            (true, Some(_)) => panic!("a skipped field cannot also have `take_from`"),
+            (true, None) => Some("first".to_string()),
+            (false, Some(source)) if ["first", "second", "random"].contains(&source.as_str()) => {
+                Some(source)
+            }
+            (false, Some(source)) => panic!(
+                "unknown `take_from` value {source:?}, expected \"first\", \"second\" or \"random\""
+            ),
+            (false, None) => None,
+        }
+    }
+
+    fn uses_context(field: &syn::Field) -> bool {
+        Self::source(field).is_none()
+    }
+
+    /// The child's value for the field, given `&` expressions for the field in each parent.
+    fn child(
+        field: &syn::Field,
+        a: TokenStream2,
+        b: TokenStream2,
+        ctx: TokenStream2,
+    ) -> TokenStream2 {
+        let ty = &field.ty;
+        match Self::source(field).as_deref() {
+            None => quote_spanned! {ty.span()=>
+                <#ty as genetic_rs_common::prelude::Crossover>::crossover(#a, #b, #ctx, rate, rng)
+            },
+            Some("first") => quote_spanned! {ty.span()=>
+                <#ty as ::core::clone::Clone>::clone(#a)
+            },
+            Some("second") => quote_spanned! {ty.span()=>
+                <#ty as ::core::clone::Clone>::clone(#b)
+            },
+            Some(_) => quote_spanned! {ty.span()=>
+                <#ty as ::core::clone::Clone>::clone(if rand::RngExt::random_bool(rng, 0.5) { #a } else { #b })
+            },
+        }
+    }
+}
+
+#[cfg(feature = "genrand")]
+#[derive(FromAttributes)]
+#[darling(attributes(genrand))]
+struct GenRandVariantSettings {
+    weight: Option<NonNegative>,
+}
+
+/// Code that picks a variant index from the `__WEIGHTS` constant, skipping `current` and zero weights.
+/// Falls back to the last eligible variant in case of rounding error. Expects a local `total` weight.
+fn weighted_pick(current: TokenStream2) -> TokenStream2 {
+    quote! {
+        let mut pick = rand::RngExt::random_range(rng, 0.0..total);
+        let mut chosen = 0usize;
+        for (i, w) in __WEIGHTS.iter().enumerate() {
+            if #current == ::core::option::Option::Some(i) || *w <= 0.0 {
+                continue;
+            }
+            chosen = i;
+            if pick < *w {
+                break;
+            }
+            pick -= *w;
+        }
+    }
+}
+
 #[proc_macro_derive(RandomlyMutable, attributes(randmut))]
 pub fn randmut_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let name = &ast.ident;
 
-    let Data::Struct(s) = &ast.data else {
-        panic!("enums and unions not yet supported");
+    let s = match &ast.data {
+        Data::Struct(s) => s,
+        Data::Enum(e) => return randmut_enum(&ast, e).into(),
+        Data::Union(_) => panic!("unions not supported"),
     };
 
-    // Determine the fallback context type from the first field (if any).
-    let fallback_ctx = s.fields.iter().next().map_or_else(
-        || quote! { () },
-        |f| {
-            let ty = &f.ty;
-            quote! { <#ty as genetic_rs_common::prelude::RandomlyMutable>::Context }
-        },
-    );
+    // Determine the fallback context type from the first field that needs one (if any).
+    let fallback_ctx = s
+        .fields
+        .iter()
+        .find(|f| RandMutFieldSettings::uses_context(f))
+        .map_or_else(
+            || quote! { () },
+            |f| {
+                let ty = &f.ty;
+                quote! { <#ty as genetic_rs_common::prelude::RandomlyMutable>::Context }
+            },
+        );
 
     let ctx_info = resolve_context(
         &ast,
         parse_quote!(RandomlyMutable),
         parse_quote!(randmut),
         fallback_ctx,
+        &RandMutFieldSettings::uses_context,
     );
 
     let ctx_type = &ctx_info.ctx_type;
@@ -183,23 +605,12 @@ pub fn randmut_derive(input: TokenStream) -> TokenStream {
         .iter()
         .enumerate()
         .map(|(i, field)| {
-            let ty = &field.ty;
-            let span = ty.span();
-            let idx = syn::Index::from(i);
-            match (&field.ident, &ctx_info.kind) {
-                (Some(field_name), ContextKind::PerField) => quote_spanned! {span=>
-                    <#ty as genetic_rs_common::prelude::RandomlyMutable>::mutate(&mut self.#field_name, &ctx.#field_name, rate, rng);
-                },
-                (Some(field_name), _) => quote_spanned! {span=>
-                    <#ty as genetic_rs_common::prelude::RandomlyMutable>::mutate(&mut self.#field_name, ctx, rate, rng);
-                },
-                (None, ContextKind::PerField) => quote_spanned! {span=>
-                    <#ty as genetic_rs_common::prelude::RandomlyMutable>::mutate(&mut self.#idx, &ctx.#idx, rate, rng);
-                },
-                (None, _) => quote_spanned! {span=>
-                    <#ty as genetic_rs_common::prelude::RandomlyMutable>::mutate(&mut self.#idx, ctx, rate, rng);
-                },
-            }
+            let access = field_access(i, field);
+            let ctx = match &ctx_info.kind {
+                ContextKind::PerField => quote! { &ctx.#access },
+                ContextKind::Shared => quote! { ctx },
+            };
+            RandMutFieldSettings::of(field).mutate(field, quote! { &mut self.#access }, ctx)
         })
         .collect();
 
@@ -240,8 +651,10 @@ pub fn mitosis_derive(input: TokenStream) -> TokenStream {
         .into();
     }
 
-    let Data::Struct(s) = &ast.data else {
-        panic!("enums and unions not yet supported");
+    let s = match &ast.data {
+        Data::Struct(s) => s,
+        Data::Enum(e) => return mitosis_enum(&ast, e).into(),
+        Data::Union(_) => panic!("unions not supported"),
     };
 
     let fallback_ctx = s.fields.iter().next().map_or_else(
@@ -257,6 +670,7 @@ pub fn mitosis_derive(input: TokenStream) -> TokenStream {
         parse_quote!(Mitosis),
         parse_quote!(mitosis),
         fallback_ctx,
+        &|_| true,
     );
 
     let ctx_type = &ctx_info.ctx_type;
@@ -316,23 +730,30 @@ pub fn crossover_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let name = &ast.ident;
 
-    let Data::Struct(s) = &ast.data else {
-        panic!("enums and unions not yet supported");
+    let s = match &ast.data {
+        Data::Struct(s) => s,
+        Data::Enum(e) => return crossover_enum(&ast, e).into(),
+        Data::Union(_) => panic!("unions not supported"),
     };
 
-    let fallback_ctx = s.fields.iter().next().map_or_else(
-        || quote! { () },
-        |f| {
-            let ty = &f.ty;
-            quote! { <#ty as genetic_rs_common::prelude::Crossover>::Context }
-        },
-    );
+    let fallback_ctx = s
+        .fields
+        .iter()
+        .find(|f| CrossoverFieldSettings::uses_context(f))
+        .map_or_else(
+            || quote! { () },
+            |f| {
+                let ty = &f.ty;
+                quote! { <#ty as genetic_rs_common::prelude::Crossover>::Context }
+            },
+        );
 
     let ctx_info = resolve_context(
         &ast,
         parse_quote!(Crossover),
         parse_quote!(crossover),
         fallback_ctx,
+        &CrossoverFieldSettings::uses_context,
     );
 
     let ctx_type = &ctx_info.ctx_type;
@@ -345,22 +766,20 @@ pub fn crossover_derive(input: TokenStream) -> TokenStream {
         .iter()
         .enumerate()
         .map(|(i, field)| {
-            let ty = &field.ty;
-            let span = ty.span();
-            let idx = syn::Index::from(i);
-            match (&field.ident, &ctx_info.kind) {
-                (Some(field_name), ContextKind::PerField) => quote_spanned! {span=>
-                    #field_name: <#ty as genetic_rs_common::prelude::Crossover>::crossover(&self.#field_name, &other.#field_name, &ctx.#field_name, rate, rng),
-                },
-                (Some(field_name), _) => quote_spanned! {span=>
-                    #field_name: <#ty as genetic_rs_common::prelude::Crossover>::crossover(&self.#field_name, &other.#field_name, ctx, rate, rng),
-                },
-                (None, ContextKind::PerField) => quote_spanned! {span=>
-                    <#ty as genetic_rs_common::prelude::Crossover>::crossover(&self.#idx, &other.#idx, &ctx.#idx, rate, rng),
-                },
-                (None, _) => quote_spanned! {span=>
-                    <#ty as genetic_rs_common::prelude::Crossover>::crossover(&self.#idx, &other.#idx, ctx, rate, rng),
-                },
+            let access = field_access(i, field);
+            let ctx = match &ctx_info.kind {
+                ContextKind::PerField => quote! { &ctx.#access },
+                ContextKind::Shared => quote! { ctx },
+            };
+            let value = CrossoverFieldSettings::child(
+                field,
+                quote! { &self.#access },
+                quote! { &other.#access },
+                ctx,
+            );
+            match &field.ident {
+                Some(field_name) => quote! { #field_name: #value, },
+                None => quote! { #value, },
             }
         })
         .collect();
@@ -386,9 +805,9 @@ pub fn crossover_derive(input: TokenStream) -> TokenStream {
     .into()
 }
 
-#[cfg(feature = "genrand")]
-#[proc_macro_derive(GenerateRandom)]
-pub fn genrand_derive(input: TokenStream) -> TokenStream {
+#[cfg(feature = "crossover")]
+#[proc_macro_derive(NParentCrossover, attributes(n_crossover))]
+pub fn n_parent_crossover_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let name = &ast.ident;
 
@@ -396,6 +815,77 @@ pub fn genrand_derive(input: TokenStream) -> TokenStream {
         panic!("enums and unions not yet supported");
     };
 
+    let settings = NParentCrossoverSettings::from_attributes(&ast.attrs).unwrap();
+    let use_randmut = settings.use_randmut.unwrap_or(false);
+
+    let is_tuple_struct = matches!(s.fields, Fields::Unnamed(_));
+
+    // each field is cloned from a randomly chosen parent.
+    let inner: TokenStream2 = s
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let ty = &field.ty;
+            let span = ty.span();
+            let idx = syn::Index::from(i);
+            let parent = quote! { parents[rand::RngExt::random_range(rng, 0..parents.len())] };
+            if let Some(field_name) = &field.ident {
+                quote_spanned! {span=>
+                    #field_name: <#ty as ::core::clone::Clone>::clone(&#parent.#field_name),
+                }
+            } else {
+                quote_spanned! {span=>
+                    <#ty as ::core::clone::Clone>::clone(&#parent.#idx),
+                }
+            }
+        })
+        .collect();
+
+    let child = if is_tuple_struct {
+        quote! { Self(#inner) }
+    } else {
+        quote! { Self { #inner } }
+    };
+
+    let (ctx_type, finish) = if use_randmut {
+        (
+            quote! { <Self as genetic_rs_common::prelude::RandomlyMutable>::Context },
+            quote! { <Self as genetic_rs_common::prelude::RandomlyMutable>::mutate(&mut child, ctx, rate, rng); },
+        )
+    } else {
+        (quote! { () }, quote! {})
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl genetic_rs_common::prelude::NParentCrossover for #name {
+            type Context = #ctx_type;
+
+            #[allow(unused_variables)]
+            fn crossover_n(parents: &[&Self], ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+                #[allow(unused_mut)]
+                let mut child = #child;
+                #finish
+                child
+            }
+        }
+    }
+    .into()
+}
+
+#[cfg(feature = "genrand")]
+#[proc_macro_derive(GenerateRandom, attributes(genrand))]
+pub fn genrand_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+
+    let s = match &ast.data {
+        Data::Struct(s) => s,
+        Data::Enum(e) => return genrand_enum(&ast, e).into(),
+        Data::Union(_) => panic!("unions not supported"),
+    };
+
     let is_tuple_struct = matches!(s.fields, Fields::Unnamed(_));
 
     let inner: TokenStream2 = s
@@ -432,3 +922,420 @@ pub fn genrand_derive(input: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+#[cfg(feature = "speciation")]
+#[proc_macro_derive(Speciated, attributes(speciated))]
+pub fn speciated_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+
+    let s = match &ast.data {
+        Data::Struct(s) => s,
+        Data::Enum(e) => return speciated_enum(&ast, e).into(),
+        Data::Union(_) => panic!("unions not supported"),
+    };
+
+    let settings = SpeciatedSettings::from_attributes(&ast.attrs).unwrap();
+
+    let fallback_ctx = s
+        .fields
+        .iter()
+        .find(|f| SpeciatedFieldSettings::uses_context(f))
+        .map_or_else(
+            || quote! { () },
+            |f| {
+                let ty = &f.ty;
+                quote! { <#ty as genetic_rs_common::prelude::Speciated>::Context }
+            },
+        );
+
+    let ctx_info = resolve_context(
+        &ast,
+        parse_quote!(Speciated),
+        parse_quote!(speciated),
+        fallback_ctx,
+        &SpeciatedFieldSettings::uses_context,
+    );
+
+    let ctx_type = &ctx_info.ctx_type;
+    let ctx_def = &ctx_info.ctx_def;
+
+    let body = weighted_divergence(
+        s.fields.iter().enumerate().map(|(i, field)| {
+            let access = field_access(i, field);
+            let ctx = match &ctx_info.kind {
+                ContextKind::PerField => quote! { &ctx.#access },
+                ContextKind::Shared => quote! { ctx },
+            };
+            (
+                field,
+                quote! { &self.#access },
+                quote! { &other.#access },
+                ctx,
+            )
+        }),
+        settings.normalize,
+    );
+
+    quote! {
+        #ctx_def
+
+        #[automatically_derived]
+        impl genetic_rs_common::prelude::Speciated for #name {
+            type Context = #ctx_type;
+
+            fn divergence(&self, other: &Self, ctx: &Self::Context) -> f32 {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+/// Derives [`RandomlyMutable`] for an enum. With probability equal to the mutation rate, the value switches to a
+/// different variant chosen by its `#[randmut(weight = ...)]` (1.0 by default), with randomly generated fields.
+/// Otherwise, the fields of the current variant are mutated.
+fn randmut_enum(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
+    let name = &ast.ident;
+    if e.variants.is_empty() {
+        panic!("enums without variants are not supported");
+    }
+
+    let ctx_info = resolve_context(
+        ast,
+        parse_quote!(RandomlyMutable),
+        parse_quote!(randmut),
+        enum_fallback_ctx(
+            e,
+            quote! { RandomlyMutable },
+            &RandMutFieldSettings::uses_context,
+        ),
+        &RandMutFieldSettings::uses_context,
+    );
+    let ctx_type = &ctx_info.ctx_type;
+    let ctx_def = &ctx_info.ctx_def;
+
+    let weights: Vec<f64> = e
+        .variants
+        .iter()
+        .map(|v| {
+            let settings = RandMutVariantSettings::from_attributes(&v.attrs).unwrap();
+            settings.weight.map_or(1.0, |w| w.0)
+        })
+        .collect();
+
+    let switch = if e.variants.len() > 1 {
+        let count = weights.len();
+        let indices = e.variants.iter().enumerate().map(|(i, v)| {
+            let ident = &v.ident;
+            quote! { Self::#ident { .. } => #i, }
+        });
+        // variants with no weight are never switched to, so their fields need not implement GenerateRandom.
+        let constructors = e
+            .variants
+            .iter()
+            .zip(&weights)
+            .enumerate()
+            .filter(|(_, (_, w))| **w > 0.0)
+            .map(|(i, (v, _))| {
+                let value = variant_random(v);
+                quote! { #i => #value, }
+            });
+        let pick = weighted_pick(quote! { ::core::option::Option::Some(current) });
+
+        quote! {
+            const __WEIGHTS: [f64; #count] = [#(#weights),*];
+            if rand::RngExt::random_bool(rng, rate.clamp(0.0, 1.0) as f64) {
+                let current: usize = match self {
+                    #(#indices)*
+                };
+                let total: f64 = __WEIGHTS
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| *i != current)
+                    .map(|(_, w)| *w)
+                    .sum();
+                if total > 0.0 {
+                    #pick
+                    *self = match chosen {
+                        #(#constructors)*
+                        _ => ::core::unreachable!(),
+                    };
+                    return;
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let arms = e.variants.iter().map(|v| {
+        let (pattern, bindings) = variant_pattern(v, "__field");
+        let calls = v
+            .fields
+            .iter()
+            .zip(&bindings)
+            .enumerate()
+            .map(|(i, (field, binding))| {
+                let settings = RandMutFieldSettings::of(field);
+                if settings.skip {
+                    return quote! { let _ = #binding; };
+                }
+                let ctx = enum_field_ctx(&ctx_info.kind, v, i, field);
+                settings.mutate(field, binding.to_token_stream(), ctx)
+            });
+        quote! { #pattern => { #(#calls)* } }
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl genetic_rs_common::prelude::RandomlyMutable for #name {
+            type Context = #ctx_type;
+
+            fn mutate(&mut self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) {
+                #switch
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+
+        #ctx_def
+    }
+}
+
+/// Derives [`Mitosis`] for an enum by dividing each field of the current variant. The variant never changes.
+fn mitosis_enum(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
+    let name = &ast.ident;
+    if e.variants.is_empty() {
+        panic!("enums without variants are not supported");
+    }
+
+    let ctx_info = resolve_context(
+        ast,
+        parse_quote!(Mitosis),
+        parse_quote!(mitosis),
+        enum_fallback_ctx(e, quote! { Mitosis }, &|_| true),
+        &|_| true,
+    );
+    let ctx_type = &ctx_info.ctx_type;
+    let ctx_def = &ctx_info.ctx_def;
+
+    let arms = e.variants.iter().map(|v| {
+        let (pattern, bindings) = variant_pattern(v, "__field");
+        let values = v
+            .fields
+            .iter()
+            .zip(&bindings)
+            .enumerate()
+            .map(|(i, (field, binding))| {
+                let ty = &field.ty;
+                let ctx = enum_field_ctx(&ctx_info.kind, v, i, field);
+                quote_spanned! {ty.span()=>
+                    <#ty as genetic_rs_common::prelude::Mitosis>::divide(#binding, #ctx, rate, rng)
+                }
+            })
+            .collect();
+        let child = variant_construct(v, values);
+        quote! { #pattern => #child, }
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl genetic_rs_common::prelude::Mitosis for #name {
+            type Context = #ctx_type;
+
+            fn divide(&self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+
+        #ctx_def
+    }
+}
+
+/// Derives [`Crossover`] for an enum. When both parents have the same variant, each field is crossed over.
+/// Otherwise, the child is a clone of either parent with equal probability.
+#[cfg(feature = "crossover")]
+fn crossover_enum(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
+    let name = &ast.ident;
+    if e.variants.is_empty() {
+        panic!("enums without variants are not supported");
+    }
+
+    let ctx_info = resolve_context(
+        ast,
+        parse_quote!(Crossover),
+        parse_quote!(crossover),
+        enum_fallback_ctx(
+            e,
+            quote! { Crossover },
+            &CrossoverFieldSettings::uses_context,
+        ),
+        &CrossoverFieldSettings::uses_context,
+    );
+    let ctx_type = &ctx_info.ctx_type;
+    let ctx_def = &ctx_info.ctx_def;
+
+    let arms = e.variants.iter().map(|v| {
+        let (pattern_a, bindings_a) = variant_pattern(v, "__a");
+        let (pattern_b, bindings_b) = variant_pattern(v, "__b");
+        let values = v
+            .fields
+            .iter()
+            .zip(bindings_a.iter().zip(&bindings_b))
+            .enumerate()
+            .map(|(i, (field, (a, b)))| {
+                let ctx = enum_field_ctx(&ctx_info.kind, v, i, field);
+                CrossoverFieldSettings::child(field, a.to_token_stream(), b.to_token_stream(), ctx)
+            })
+            .collect();
+        let child = variant_construct(v, values);
+        quote! { (#pattern_a, #pattern_b) => #child, }
+    });
+
+    let mismatch = if e.variants.len() > 1 {
+        quote! {
+            _ => {
+                if rand::RngExt::random_bool(rng, 0.5) {
+                    ::core::clone::Clone::clone(self)
+                } else {
+                    ::core::clone::Clone::clone(other)
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #ctx_def
+
+        #[automatically_derived]
+        impl genetic_rs_common::prelude::Crossover for #name {
+            type Context = #ctx_type;
+
+            fn crossover(&self, other: &Self, ctx: &Self::Context, rate: f32, rng: &mut impl rand::Rng) -> Self {
+                match (self, other) {
+                    #(#arms)*
+                    #mismatch
+                }
+            }
+        }
+    }
+}
+
+/// Derives [`GenerateRandom`] for an enum, choosing each variant with probability proportional to its
+/// `#[genrand(weight = ...)]` (1.0 by default) and generating its fields randomly.
+#[cfg(feature = "genrand")]
+fn genrand_enum(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
+    let name = &ast.ident;
+
+    let weights: Vec<f64> = e
+        .variants
+        .iter()
+        .map(|v| {
+            let settings = GenRandVariantSettings::from_attributes(&v.attrs).unwrap();
+            settings.weight.map_or(1.0, |w| w.0)
+        })
+        .collect();
+    if !weights.iter().any(|w| *w > 0.0) {
+        panic!("at least one variant must have a positive weight");
+    }
+
+    let count = weights.len();
+    let constructors = e
+        .variants
+        .iter()
+        .zip(&weights)
+        .enumerate()
+        .filter(|(_, (_, w))| **w > 0.0)
+        .map(|(i, (v, _))| {
+            let value = variant_random(v);
+            quote! { #i => #value, }
+        });
+    let pick = weighted_pick(quote! { ::core::option::Option::<usize>::None });
+
+    quote! {
+        #[automatically_derived]
+        impl genetic_rs_common::prelude::GenerateRandom for #name {
+            fn gen_random(rng: &mut impl rand::Rng) -> Self {
+                const __WEIGHTS: [f64; #count] = [#(#weights),*];
+                let total: f64 = __WEIGHTS.iter().sum();
+                #pick
+                match chosen {
+                    #(#constructors)*
+                    _ => ::core::unreachable!(),
+                }
+            }
+        }
+    }
+}
+
+/// Derives [`Speciated`] for an enum. Values of the same variant diverge by the weighted sum of their fields'
+/// divergences, and values of different variants have a divergence of 1.
+#[cfg(feature = "speciation")]
+fn speciated_enum(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
+    let name = &ast.ident;
+    let settings = SpeciatedSettings::from_attributes(&ast.attrs).unwrap();
+
+    let ctx_info = resolve_context(
+        ast,
+        parse_quote!(Speciated),
+        parse_quote!(speciated),
+        enum_fallback_ctx(
+            e,
+            quote! { Speciated },
+            &SpeciatedFieldSettings::uses_context,
+        ),
+        &SpeciatedFieldSettings::uses_context,
+    );
+
+    let ctx_type = &ctx_info.ctx_type;
+    let ctx_def = &ctx_info.ctx_def;
+
+    let arms = e.variants.iter().map(|v| {
+        let (pattern_a, bindings_a) = variant_pattern(v, "__a");
+        let (pattern_b, bindings_b) = variant_pattern(v, "__b");
+        let divergence = weighted_divergence(
+            v.fields
+                .iter()
+                .zip(bindings_a.iter().zip(&bindings_b))
+                .enumerate()
+                .map(|(i, (field, (a, b)))| {
+                    (
+                        field,
+                        a.to_token_stream(),
+                        b.to_token_stream(),
+                        enum_field_ctx(&ctx_info.kind, v, i, field),
+                    )
+                }),
+            settings.normalize,
+        );
+        quote! { (#pattern_a, #pattern_b) => { #divergence } }
+    });
+
+    let fallback_arm = if e.variants.len() > 1 {
+        quote! { _ => 1.0, }
+    } else {
+        quote! {}
+    };
+
+    quote! {
+        #ctx_def
+
+        #[automatically_derived]
+        impl genetic_rs_common::prelude::Speciated for #name {
+            type Context = #ctx_type;
+
+            fn divergence(&self, other: &Self, ctx: &Self::Context) -> f32 {
+                match (self, other) {
+                    #(#arms)*
+                    #fallback_arm
+                }
+            }
+        }
+    }
+}
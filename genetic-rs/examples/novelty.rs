@@ -0,0 +1,76 @@
+use genetic_rs::prelude::*;
+
+#[derive(Clone, Debug)]
+struct MyGenome {
+    x: f32,
+    y: f32,
+}
+
+impl RandomlyMutable for MyGenome {
+    type Context = ();
+
+    fn mutate(&mut self, _: &(), rate: f32, rng: &mut impl Rng) {
+        self.x += rng.random_range(-rate..rate);
+        self.y += rng.random_range(-rate..rate);
+    }
+}
+
+impl Mitosis for MyGenome {
+    type Context = ();
+
+    fn divide(&self, _: &(), rate: f32, rng: &mut impl Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(&(), rate, rng);
+        child
+    }
+}
+
+impl GenerateRandom for MyGenome {
+    fn gen_random(rng: &mut impl Rng) -> Self {
+        Self {
+            x: rng.random_range(-0.1..0.1),
+            y: rng.random_range(-0.1..0.1),
+        }
+    }
+}
+
+// the behaviour of a genome is just the point it ends up at.
+// in a real problem, this could be something like the final position of a robot in a maze.
+fn behavior(genome: &MyGenome) -> Vec<f32> {
+    vec![genome.x, genome.y]
+}
+
+// a deceptive objective: the local optimum at the origin is much easier to find than the goal.
+fn objective(genome: &MyGenome) -> f32 {
+    let to_goal = ((genome.x - 5.0).powi(2) + (genome.y - 5.0).powi(2)).sqrt();
+    let to_trap = (genome.x.powi(2) + genome.y.powi(2)).sqrt();
+    (1.0 - to_trap).max(0.0) + 10.0 / (1.0 + to_goal)
+}
+
+fn main() {
+    let mut rng = rand::rng();
+
+    // mostly novelty, with a little bit of objective pressure.
+    let fitness = NoveltyFitness::new(behavior, 10, ArchivePolicy::MostNovel(2))
+        .with_max_archive_size(500)
+        .blend(objective, 0.8);
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 100),
+        FitnessEliminator::new_without_observer(fitness),
+        MitosisRepopulator::new(0.25, ()),
+    );
+
+    sim.perform_generations(100);
+
+    let best = sim
+        .genomes
+        .iter()
+        .max_by(|a, b| objective(a).total_cmp(&objective(b)))
+        .unwrap();
+    println!(
+        "archive size: {}",
+        sim.eliminator.fitness_fn.first.archive.len()
+    );
+    dbg!(best, objective(best));
+}
@@ -0,0 +1,305 @@
+//! Integration tests for [`Aged`], [`AgeLayers`], [`AlpsEliminator`] and [`AlpsRepopulator`].
+
+use genetic_rs::prelude::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shared test genome
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A genome with a single value that should be maximized.
+#[derive(Clone, Debug, PartialEq)]
+struct Genome(f32);
+
+impl GenerateRandom for Genome {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        Self(rng.random_range(-1.0..1.0))
+    }
+}
+
+impl RandomlyMutable for Genome {
+    type Context = ();
+
+    fn mutate(&mut self, _: &(), rate: f32, rng: &mut impl rand::Rng) {
+        self.0 += rng.random_range(-rate..=rate);
+    }
+}
+
+impl Mitosis for Genome {
+    type Context = ();
+
+    fn divide(&self, ctx: &(), rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl Crossover for Genome {
+    type Context = ();
+
+    fn crossover(&self, other: &Self, ctx: &(), rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = Self((self.0 + other.0) / 2.0);
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+fn fitness(g: &Aged<Genome>) -> f32 {
+    g.0
+}
+
+fn aged(value: f32, age: usize) -> Aged<Genome> {
+    Aged {
+        genome: Genome(value),
+        age,
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Aged
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn offspring_inherit_oldest_parent_age() {
+    let mut rng = rand::rng();
+    let a = aged(0.0, 3);
+    let b = aged(1.0, 7);
+
+    assert_eq!(a.divide(&(), 0.1, &mut rng).age, 3);
+    assert_eq!(a.crossover(&b, &(), 0.1, &mut rng).age, 7);
+    assert_eq!(b.crossover(&a, &(), 0.1, &mut rng).age, 7);
+}
+
+#[test]
+fn random_aged_genomes_start_at_zero() {
+    let mut rng = rand::rng();
+    let pop: Vec<Aged<Genome>> = Vec::gen_random(&mut rng, 10);
+    assert!(pop.iter().all(|g| g.age == 0));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// AgeLayers
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn layer_limits() {
+    let linear = AgeLayers::linear(5, 4);
+    assert_eq!(linear.max_ages(), &[5, 10, 15]);
+    assert_eq!(linear.len(), 4);
+
+    let poly = AgeLayers::polynomial(2, 4);
+    assert_eq!(poly.max_ages(), &[2, 8, 18]);
+
+    assert_eq!(linear.layer_of(0), 0);
+    assert_eq!(linear.layer_of(5), 0);
+    assert_eq!(linear.layer_of(6), 1);
+    assert_eq!(linear.layer_of(15), 2);
+    assert_eq!(linear.layer_of(1000), 3);
+}
+
+#[test]
+#[should_panic(expected = "strictly increasing")]
+fn layers_must_increase() {
+    AgeLayers::new(vec![5, 5]);
+}
+
+#[test]
+fn split_preserves_order() {
+    let layers = AgeLayers::new(vec![1]);
+    let split = layers.split(vec![aged(0.0, 0), aged(1.0, 5), aged(2.0, 1)]);
+    assert_eq!(split[0], vec![aged(0.0, 0), aged(2.0, 1)]);
+    assert_eq!(split[1], vec![aged(1.0, 5)]);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// AlpsEliminator
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn eliminator_competes_within_layers() {
+    let mut elim = AlpsEliminator::new(
+        FitnessEliminator::new_without_observer(fitness),
+        AgeLayers::new(vec![5]),
+    );
+
+    // the young genomes are much worse than the old ones, but should still survive in their own layer.
+    let genomes = vec![
+        aged(-10.0, 0),
+        aged(-9.0, 1),
+        aged(-8.0, 2),
+        aged(5.0, 10),
+        aged(6.0, 11),
+        aged(7.0, 12),
+    ];
+    let survivors = elim.eliminate(genomes);
+
+    assert_eq!(
+        survivors,
+        vec![aged(-8.0, 2), aged(-9.0, 1), aged(7.0, 12), aged(6.0, 11)]
+    );
+}
+
+#[test]
+fn eliminator_reports_ages_and_fitness() {
+    let mut elim = AlpsEliminator::new(
+        FitnessEliminator::new_without_observer(fitness),
+        AgeLayers::new(vec![5]),
+    );
+
+    let survivors = elim.eliminate_evaluated(vec![aged(1.0, 0), aged(2.0, 1), aged(3.0, 10)]);
+    assert_eq!(survivors.ages, Some(vec![1, 0, 10]));
+    assert_eq!(survivors.fitness, Some(vec![2.0, 1.0, 3.0]));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// AlpsRepopulator
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn repopulator_fills_target_and_ages() {
+    let mut repop = AlpsRepopulator::new(
+        MitosisRepopulator::new(0.1, ()),
+        AgeLayers::new(vec![2, 4]),
+        0,
+    );
+
+    let mut genomes = vec![aged(0.0, 0), aged(1.0, 3), aged(2.0, 10)];
+    repop.repopulate(&mut genomes, 9);
+
+    assert_eq!(genomes.len(), 9);
+    assert_eq!(repop.generation, 1);
+
+    let layers = repop.layers.clone();
+    let counts: Vec<usize> = layers.split(genomes).iter().map(Vec::len).collect();
+    assert_eq!(counts, vec![3, 3, 3]);
+}
+
+#[test]
+fn empty_layers_pass_quota_down() {
+    let mut repop = AlpsRepopulator::new(
+        MitosisRepopulator::new(0.1, ()),
+        AgeLayers::linear(10, 3),
+        0,
+    );
+
+    let mut genomes = vec![aged(0.0, 0), aged(1.0, 1)];
+    repop.repopulate(&mut genomes, 10);
+
+    assert_eq!(genomes.len(), 10);
+    assert!(genomes.iter().all(|g| g.age <= 2));
+}
+
+#[test]
+fn lone_survivors_breed_with_layer_below() {
+    let mut repop = AlpsRepopulator::new(
+        CrossoverRepopulator::new(0.1, ()),
+        AgeLayers::new(vec![5]),
+        0,
+    );
+
+    // crossover needs two parents, so the single old survivor must not be bred alone.
+    let mut genomes = vec![aged(0.0, 0), aged(1.0, 1), aged(2.0, 10)];
+    repop.repopulate(&mut genomes, 10);
+
+    assert_eq!(genomes.len(), 10);
+    assert!(genomes.iter().any(|g| g.age == 11));
+}
+
+/// Layers with a quota of 1 must not overshoot the target, even though they are bred from two parents.
+#[test]
+fn repopulator_respects_quotas_of_one() {
+    for (layers, target_size) in [(vec![2, 4], 4), (vec![2, 4, 6], 3), (vec![2, 4, 6], 2)] {
+        let mut repop = AlpsRepopulator::new(
+            CrossoverRepopulator::new(0.1, ()),
+            AgeLayers::new(layers),
+            0,
+        );
+        let mut genomes = vec![aged(0.0, 0), aged(1.0, 3), aged(2.0, 5), aged(3.0, 10)];
+        repop.repopulate(&mut genomes, target_size);
+        assert_eq!(genomes.len(), target_size);
+    }
+}
+
+/// Records the metadata each layer is repopulated with.
+struct RecordingRepopulator(Vec<EvaluatedPopulation<Aged<Genome>>>);
+
+impl Repopulator<Aged<Genome>> for RecordingRepopulator {
+    fn repopulate(&mut self, genomes: &mut Vec<Aged<Genome>>, target_size: usize) {
+        let template = genomes[0].clone();
+        genomes.resize(target_size, template);
+    }
+
+    fn repopulate_evaluated(
+        &mut self,
+        population: EvaluatedPopulation<Aged<Genome>>,
+        target_size: usize,
+    ) -> Vec<Aged<Genome>> {
+        self.0.push(population.clone());
+        let mut genomes = population.genomes;
+        self.repopulate(&mut genomes, target_size);
+        genomes
+    }
+}
+
+#[test]
+fn repopulator_forwards_layer_metadata() {
+    let layers = AgeLayers::new(vec![5]);
+    let mut elim = AlpsEliminator::new(FitnessEliminator::new(fitness, 1.0, ()), layers.clone());
+    let mut repop = AlpsRepopulator::new(RecordingRepopulator(Vec::new()), layers, 0);
+
+    let survivors = elim.eliminate_evaluated(vec![
+        aged(1.0, 0),
+        aged(2.0, 1),
+        aged(3.0, 10),
+        aged(4.0, 11),
+    ]);
+    let genomes = repop.repopulate_evaluated(survivors, 8);
+    assert_eq!(genomes.len(), 8);
+
+    let seen = &repop.inner.0;
+    assert_eq!(seen.len(), 2);
+    assert_eq!(seen[0].fitness, Some(vec![4.0, 3.0]));
+    assert_eq!(seen[0].ages, Some(vec![11, 10]));
+    assert_eq!(seen[1].fitness, Some(vec![2.0, 1.0]));
+}
+
+#[test]
+fn injection_adds_random_genomes() {
+    let mut repop =
+        AlpsRepopulator::new(MitosisRepopulator::new(0.0, ()), AgeLayers::new(vec![5]), 1);
+
+    // with a mutation rate of 0, every non-random genome is a copy of a survivor.
+    let mut genomes = vec![aged(50.0, 2), aged(100.0, 8), aged(100.0, 9)];
+    repop.repopulate(&mut genomes, 10);
+
+    assert_eq!(genomes.len(), 10);
+    let fresh = genomes.iter().filter(|g| g.age == 1).count();
+    assert_eq!(fresh, 4);
+    assert!(genomes
+        .iter()
+        .filter(|g| g.age == 1)
+        .all(|g| (-1.0..1.0).contains(&g.0)));
+    assert_eq!(genomes.iter().filter(|g| g.0 == 100.0).count(), 5);
+}
+
+#[test]
+fn alps_simulation() {
+    let mut rng = rand::rng();
+    let layers = AgeLayers::linear(5, 4);
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 100),
+        AlpsEliminator::new(
+            FitnessEliminator::new_without_observer(fitness),
+            layers.clone(),
+        ),
+        AlpsRepopulator::new(CrossoverRepopulator::new(0.25, ()), layers, 5),
+    );
+
+    sim.perform_generations(50);
+
+    assert_eq!(sim.genomes.len(), 100);
+    assert!(sim.genomes.iter().any(|g| g.age > 20));
+    assert!(sim.genomes.iter().any(|g| g.age <= 5));
+    assert!(sim.genomes.iter().map(|g| g.0).fold(f32::MIN, f32::max) > 2.0);
+}
@@ -0,0 +1,193 @@
+//! Integration tests for [`CmaEs`] and its restart strategies.
+
+use genetic_rs::prelude::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shared objectives
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The negated sphere function, with its maximum of 0.0 at (1, 2, 3, 4, 5).
+fn sphere(x: &[f64]) -> f32 {
+    -x.iter()
+        .enumerate()
+        .map(|(i, v)| (v - (i + 1) as f64).powi(2))
+        .sum::<f64>() as f32
+}
+
+/// The negated Rosenbrock function, with its maximum of 0.0 at (1, 1, 1).
+/// Its curved valley can only be followed by adapting the covariance matrix,
+/// and unlike higher dimensions it has no second local optimum to get stuck in.
+fn rosenbrock(x: &[f64; 3]) -> f32 {
+    -x.windows(2)
+        .map(|w| 100.0 * (w[1] - w[0] * w[0]).powi(2) + (1.0 - w[0]).powi(2))
+        .sum::<f64>() as f32
+}
+
+/// Stops runs quickly, so restart tests do not take long.
+fn loose_termination() -> CmaTermination {
+    CmaTermination {
+        tol_x: 1e-2,
+        ..Default::default()
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Convergence
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn sphere_converges() {
+    let mut cma = CmaEs::new_without_observer(vec![0.0; 5], 1.0, |x: &Vec<f64>| sphere(x));
+    cma.perform_steps(300);
+
+    let (best, fitness) = cma.best().unwrap();
+    assert!(fitness > -1e-4, "best fitness {fitness}");
+    assert!((best[4] - 5.0).abs() < 1e-2);
+}
+
+#[test]
+fn rosenbrock_converges() {
+    let mut cma = CmaEs::new_without_observer([0.0; 3], 0.5, rosenbrock);
+    cma.perform_steps(2000);
+
+    let (best, fitness) = cma.best().unwrap();
+    assert!(fitness > -1e-3, "best fitness {fitness}");
+    assert!(best.iter().all(|v| (v - 1.0).abs() < 0.1));
+}
+
+/// NaN samples rank below everything, so they neither steer the search nor become the best genome.
+#[test]
+fn nan_fitness_is_worst() {
+    let mut cma = CmaEs::new_without_observer(vec![0.0; 5], 1.0, |x: &Vec<f64>| {
+        if x[0] < 0.5 {
+            f32::NAN
+        } else {
+            sphere(x)
+        }
+    });
+    cma.perform_steps(300);
+
+    let (best, fitness) = cma.best().unwrap();
+    assert!(fitness > -1e-4, "best fitness {fitness}");
+    assert!((best[4] - 5.0).abs() < 1e-2);
+}
+
+struct UnitCube;
+
+impl RealBounds for UnitCube {
+    const BOUNDS: &'static [(f32, f32)] = &[(0.0, 1.0); 3];
+}
+
+/// [`RealVector`] implements [`RealParameters`], so it can be sampled directly.
+/// Samples outside the bounds are clamped, which the optimum at the lower corner relies on.
+#[test]
+fn real_vector_genome() {
+    let fitness = |g: &RealVector<UnitCube>| -g.values().iter().sum::<f32>();
+    let mut cma = CmaEs::new_without_observer(RealVector::new(vec![0.5; 3]), 0.3, fitness);
+    cma.perform_steps(200);
+
+    let (best, fitness) = cma.best().unwrap();
+    assert!(fitness > -1e-3, "best fitness {fitness}");
+    assert!(best.values().iter().all(|v| (0.0..=1.0).contains(v)));
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Observation
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn observer_sees_sorted_generations() {
+    let mut sizes = Vec::new();
+    let mut cma = CmaEs::new(
+        vec![0.0; 5],
+        1.0,
+        |x: &Vec<f64>| sphere(x),
+        |fitnesses: &[(Vec<f64>, f32)]| {
+            assert!(fitnesses.windows(2).all(|w| w[0].1 >= w[1].1));
+            sizes.push(fitnesses.len());
+        },
+    )
+    .with_population_size(12);
+    cma.perform_steps(5);
+
+    assert_eq!(cma.generation(), 5);
+    assert_eq!(cma.evaluations(), 60);
+    drop(cma);
+    assert_eq!(sizes, vec![12; 5]);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Restarts
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn without_restarts_finishes_after_one_run() {
+    let mut cma = CmaEs::new_without_observer(vec![0.0; 5], 1.0, |x: &Vec<f64>| sphere(x))
+        .with_termination(loose_termination());
+    cma.perform_steps(1000);
+
+    assert!(cma.is_finished());
+    assert_eq!(cma.restarts(), 0);
+
+    let generation = cma.generation();
+    cma.step();
+    assert_eq!(cma.generation(), generation);
+}
+
+#[test]
+fn ipop_doubles_population() {
+    let mut cma = CmaEs::new_without_observer(vec![0.0; 5], 1.0, |x: &Vec<f64>| sphere(x))
+        .with_population_size(8)
+        .with_restarts(RestartStrategy::Ipop { increase: 2.0 }, 2)
+        .with_termination(loose_termination());
+    cma.perform_steps(5000);
+
+    assert!(cma.is_finished());
+    assert_eq!(cma.restarts(), 2);
+    assert_eq!(cma.population_size(), 32);
+}
+
+#[test]
+fn bipop_alternates_regimes() {
+    let mut sizes = Vec::new();
+    let mut cma = CmaEs::new(
+        vec![0.0; 5],
+        1.0,
+        |x: &Vec<f64>| sphere(x),
+        |fitnesses: &[(Vec<f64>, f32)]| sizes.push(fitnesses.len()),
+    )
+    .with_population_size(8)
+    .with_restarts(RestartStrategy::Bipop { increase: 2.0 }, 4)
+    .with_termination(loose_termination());
+    cma.perform_steps(5000);
+
+    assert!(cma.is_finished());
+    assert_eq!(cma.restarts(), 4);
+    drop(cma);
+
+    sizes.dedup();
+    // the first restart is always large, and small runs never exceed the latest large population.
+    assert_eq!(&sizes[..2], &[8, 16]);
+    assert!(sizes.iter().all(|&s| s <= 64));
+}
+
+#[test]
+fn best_is_kept_across_restarts() {
+    let mut cma = CmaEs::new_without_observer(vec![0.0; 5], 1.0, |x: &Vec<f64>| sphere(x))
+        .with_restarts(RestartStrategy::Ipop { increase: 2.0 }, 3)
+        .with_termination(loose_termination());
+
+    let mut previous = f32::MIN;
+    while !cma.is_finished() {
+        cma.step();
+        let fitness = cma.best().unwrap().1;
+        assert!(fitness >= previous);
+        previous = fitness;
+    }
+}
+
+#[test]
+#[should_panic(expected = "Step size")]
+fn non_positive_sigma_panics() {
+    CmaEs::new_without_observer(vec![0.0; 2], 0.0, |x: &Vec<f64>| sphere(x));
+}
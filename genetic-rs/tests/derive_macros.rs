@@ -278,6 +278,61 @@ fn mitosis_tuple_struct() {
     let _child = genome.divide(&(), 0.5, &mut rng);
 }
 
+// ──────────────────────────────────────────────────────────────────────────────
+// NParentCrossover
+// ──────────────────────────────────────────────────────────────────────────────
+
+/// Named struct – each field is taken from one of the parents.
+#[derive(Clone, Debug, PartialEq, NParentCrossover)]
+struct NParentNamed {
+    a: Val,
+    b: Val,
+    c: Val,
+}
+
+#[test]
+fn n_parent_crossover_picks_fields_from_parents() {
+    let mut rng = rand::rng();
+    let parents: Vec<NParentNamed> = (0..4)
+        .map(|i| {
+            let v = Val(i as f32);
+            NParentNamed { a: v, b: v, c: v }
+        })
+        .collect();
+    let refs: Vec<&NParentNamed> = parents.iter().collect();
+
+    for _ in 0..20 {
+        let child = NParentNamed::crossover_n(&refs, &(), 0.0, &mut rng);
+        for field in [child.a, child.b, child.c] {
+            assert!(parents.iter().any(|p| p.a == field));
+        }
+    }
+}
+
+/// Tuple struct with `use_randmut`, which mutates the child after picking fields.
+#[derive(Clone, Debug, PartialEq, RandomlyMutable, NParentCrossover)]
+#[n_crossover(use_randmut = true)]
+struct NParentTupleRandMut(Val, Val);
+
+#[test]
+fn n_parent_crossover_use_randmut() {
+    let mut rng = rand::rng();
+    let parent = NParentTupleRandMut(Val(0.0), Val(0.0));
+    let child = NParentTupleRandMut::crossover_n(&[&parent], &(), 1.0, &mut rng);
+    assert!(child.0 .0.abs() <= 1.0 && child.1 .0.abs() <= 1.0);
+    assert_ne!(child, parent);
+}
+
+/// Empty struct with NParentCrossover.
+#[derive(Clone, NParentCrossover)]
+struct NParentEmpty {}
+
+#[test]
+fn n_parent_crossover_empty_struct() {
+    let mut rng = rand::rng();
+    let _child = NParentEmpty::crossover_n(&[&NParentEmpty {}], &(), 0.0, &mut rng);
+}
+
 // ──────────────────────────────────────────────────────────────────────────────
 // GenerateRandom
 // ──────────────────────────────────────────────────────────────────────────────
@@ -398,3 +453,491 @@ fn crossover_empty_struct() {
     let g2 = CrossoverEmpty {};
     let _child = g1.crossover(&g2, &(), 0.0, &mut rng);
 }
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Plain fields
+// ──────────────────────────────────────────────────────────────────────────────
+
+/// Every field has the same context, so it can be shared.
+#[derive(Clone, Debug, RandomlyMutable, Mitosis, Crossover, GenerateRandom)]
+struct PlainFloats {
+    x: f32,
+    y: f32,
+    weights: [f32; 3],
+}
+
+#[test]
+fn plain_fields_shared_context() {
+    let mut rng = rand::rng();
+    let ctx = FloatMutation {
+        sigma: 1.0,
+        min: 0.0,
+        max: 1.0,
+    };
+    let a = PlainFloats::gen_random(&mut rng);
+    let b = PlainFloats::gen_random(&mut rng);
+    let child = a
+        .crossover(&b, &ctx, 1.0, &mut rng)
+        .divide(&ctx, 1.0, &mut rng);
+    assert!([child.x, child.y]
+        .iter()
+        .chain(&child.weights)
+        .all(|v| (0.0..=1.0).contains(v)));
+}
+
+/// Mixed fields need a context per field.
+#[derive(Clone, Debug, RandomlyMutable, Mitosis, GenerateRandom)]
+#[randmut(create_context(name = PlainMixedCtx, derive(Clone, Debug, Default)))]
+#[mitosis(with_context = PlainMixedCtx)]
+struct PlainMixed {
+    rate: f64,
+    layers: u8,
+    enabled: bool,
+    bias: Option<f64>,
+}
+
+#[test]
+fn plain_fields_per_field_context() {
+    let mut rng = rand::rng();
+    let ctx = PlainMixedCtx {
+        layers: IntegerMutation {
+            step: 1,
+            min: 1,
+            max: 4,
+        },
+        ..Default::default()
+    };
+    let mut genome = PlainMixed::gen_random(&mut rng);
+    genome.mutate(&ctx, 1.0, &mut rng);
+    assert!((1..=4).contains(&genome.layers));
+    let _child = genome.divide(&ctx, 0.5, &mut rng);
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Enums
+// ──────────────────────────────────────────────────────────────────────────────
+
+/// Unit variants with weights.
+#[derive(Clone, Copy, Debug, PartialEq, RandomlyMutable, Mitosis, Crossover, GenerateRandom)]
+enum Activation {
+    #[randmut(weight = 3.0)]
+    #[genrand(weight = 0)]
+    Relu,
+    Sigmoid,
+    #[randmut(weight = 0)]
+    Tanh,
+}
+
+#[test]
+fn enum_unit_variants_switch_by_weight() {
+    let mut rng = rand::rng();
+
+    for _ in 0..100 {
+        assert_ne!(Activation::gen_random(&mut rng), Activation::Relu);
+    }
+
+    let mut relu = 0;
+    for _ in 0..1000 {
+        let mut a = Activation::Sigmoid;
+        a.mutate(&(), 1.0, &mut rng);
+        assert_ne!(a, Activation::Tanh);
+        assert_ne!(a, Activation::Sigmoid);
+        relu += usize::from(a == Activation::Relu);
+    }
+    assert_eq!(relu, 1000);
+
+    let mut tanh = Activation::Tanh;
+    tanh.mutate(&(), 0.0, &mut rng);
+    assert_eq!(tanh, Activation::Tanh);
+
+    let child = Activation::Relu.crossover(&Activation::Sigmoid, &(), 0.0, &mut rng);
+    assert!(child == Activation::Relu || child == Activation::Sigmoid);
+    assert_eq!(
+        Activation::Tanh.divide(&(), 1.0, &mut rng),
+        Activation::Tanh
+    );
+}
+
+/// Variants with fields sharing a context.
+#[derive(Clone, Debug, PartialEq, RandomlyMutable, Mitosis, Crossover, GenerateRandom)]
+enum Shape {
+    Circle {
+        radius: Val,
+    },
+    Rect(Val, Val),
+    #[randmut(weight = 0)]
+    Empty,
+}
+
+#[test]
+fn enum_fields_mutate_and_cross() {
+    let mut rng = rand::rng();
+
+    let mut circle = Shape::Circle { radius: Val(0.0) };
+    circle.mutate(&(), 0.0, &mut rng);
+    assert_eq!(circle, Shape::Circle { radius: Val(0.0) });
+
+    // when the variants match, each field is crossed over.
+    let a = Shape::Rect(Val(0.0), Val(2.0));
+    let b = Shape::Rect(Val(2.0), Val(4.0));
+    assert_eq!(
+        a.crossover(&b, &(), 0.0, &mut rng),
+        Shape::Rect(Val(1.0), Val(3.0))
+    );
+
+    // otherwise, either parent is cloned.
+    for _ in 0..20 {
+        let child = a.crossover(&circle, &(), 0.0, &mut rng);
+        assert!(child == a || child == circle);
+    }
+
+    for _ in 0..20 {
+        let mut shape = Shape::Empty;
+        shape.mutate(&(), 1.0, &mut rng);
+        assert_ne!(shape, Shape::Empty);
+        let _ = Shape::gen_random(&mut rng).divide(&(), 0.5, &mut rng);
+    }
+}
+
+/// Variants with different field types need per-field contexts.
+#[derive(Clone, Debug, PartialEq, RandomlyMutable, Mitosis, Crossover)]
+#[randmut(create_context(name = LayerCtx, derive(Clone, Debug, Default)))]
+#[mitosis(with_context = LayerCtx)]
+#[crossover(with_context = LayerCtx)]
+enum Layer {
+    Dense {
+        units: u32,
+    },
+    Dropout(f32),
+    #[randmut(weight = 0)]
+    Custom(FieldA),
+}
+
+impl GenerateRandom for FieldA {
+    fn gen_random(_rng: &mut impl rand::Rng) -> Self {
+        FieldA
+    }
+}
+
+impl Mitosis for FieldA {
+    type Context = CtxA;
+    fn divide(&self, _ctx: &CtxA, _rate: f32, _rng: &mut impl Rng) -> Self {
+        FieldA
+    }
+}
+
+impl Crossover for FieldA {
+    type Context = CtxA;
+    fn crossover(&self, _other: &Self, _ctx: &CtxA, _rate: f32, _rng: &mut impl Rng) -> Self {
+        FieldA
+    }
+}
+
+impl PartialEq for FieldA {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl std::fmt::Debug for FieldA {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("FieldA")
+    }
+}
+
+#[test]
+fn enum_per_field_context() {
+    let mut rng = rand::rng();
+    let ctx = LayerCtx {
+        dense_units: IntegerMutation {
+            step: 1,
+            min: 1,
+            max: 3,
+        },
+        dropout_0: FloatMutation {
+            sigma: 1.0,
+            min: 0.0,
+            max: 0.5,
+        },
+        custom_0: CtxA,
+    };
+
+    // dividing never switches variants, so the fields stay within their contexts.
+    for _ in 0..50 {
+        let dense = Layer::Dense { units: 2 };
+        match dense.divide(&ctx, 1.0, &mut rng) {
+            Layer::Dense { units } => assert!(units == 1 || units == 3),
+            other => panic!("variant changed to {other:?}"),
+        }
+        match Layer::Dropout(0.4).divide(&ctx, 1.0, &mut rng) {
+            Layer::Dropout(p) => assert!((0.0..=0.5).contains(&p)),
+            other => panic!("variant changed to {other:?}"),
+        }
+    }
+
+    let mut layer = Layer::Dense { units: 2 };
+    for _ in 0..50 {
+        layer.mutate(&ctx, 0.5, &mut rng);
+        assert!(!matches!(layer, Layer::Custom(_)), "Custom has no weight");
+    }
+
+    let child = Layer::Dropout(0.1).crossover(&Layer::Dropout(0.1), &ctx, 0.0, &mut rng);
+    assert_eq!(child, Layer::Dropout(0.1));
+    let _ = Layer::Custom(FieldA).divide(&ctx, 1.0, &mut rng);
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Field attributes
+// ──────────────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq, RandomlyMutable, Crossover)]
+#[randmut(create_context(name = TunedCtx, derive(Clone, Debug, Default)))]
+#[crossover(create_context(name = TunedCrossCtx, derive(Clone, Debug, Default)))]
+struct Tuned {
+    #[randmut(skip)]
+    #[crossover(skip)]
+    id: u32,
+    #[randmut(rate = 0)]
+    frozen: f32,
+    #[randmut(with = count_mutations)]
+    mutations: u32,
+    bias: f32,
+    #[crossover(take_from = "second")]
+    tag: u8,
+    #[crossover(take_from = "random")]
+    flag: bool,
+}
+
+/// Gets the whole context rather than one for its field.
+fn count_mutations(count: &mut u32, _ctx: &TunedCtx, _rate: f32, _rng: &mut impl Rng) {
+    *count += 1;
+}
+
+#[test]
+fn field_attributes_randmut() {
+    let mut rng = rand::rng();
+    // `id` and `mutations` have no context field.
+    let ctx = TunedCtx {
+        frozen: FloatMutation::default(),
+        bias: FloatMutation::default(),
+        tag: IntegerMutation::default(),
+        flag: (),
+    };
+    let mut genome = Tuned {
+        id: 7,
+        frozen: 0.5,
+        mutations: 0,
+        bias: 0.5,
+        tag: 0,
+        flag: false,
+    };
+    for _ in 0..10 {
+        genome.mutate(&ctx, 1.0, &mut rng);
+    }
+    assert_eq!(genome.id, 7);
+    assert_eq!(genome.frozen, 0.5);
+    assert_eq!(genome.mutations, 10);
+    assert_ne!(genome.bias, 0.5);
+}
+
+#[test]
+fn field_attributes_crossover() {
+    let mut rng = rand::rng();
+    // only the crossed-over fields have a context.
+    let ctx = TunedCrossCtx {
+        frozen: FloatMutation::default(),
+        mutations: IntegerMutation::default(),
+        bias: FloatMutation::default(),
+    };
+    let a = Tuned {
+        id: 1,
+        frozen: 0.0,
+        mutations: 0,
+        bias: 0.0,
+        tag: 1,
+        flag: true,
+    };
+    let b = Tuned {
+        id: 2,
+        frozen: 0.0,
+        mutations: 0,
+        bias: 0.0,
+        tag: 2,
+        flag: false,
+    };
+    let mut flags = [0; 2];
+    for _ in 0..100 {
+        let child = a.crossover(&b, &ctx, 0.0, &mut rng);
+        assert_eq!(child.id, 1);
+        assert_eq!(child.tag, 2);
+        flags[usize::from(child.flag)] += 1;
+    }
+    assert!(flags.iter().all(|&n| n > 0), "{flags:?}");
+}
+
+/// Skipped tuple fields keep their position in a created context.
+#[derive(Clone, Debug, PartialEq, RandomlyMutable)]
+#[randmut(create_context(name = TaggedCtx, derive(Default)))]
+struct Tagged(#[randmut(skip)] u32, f32);
+
+#[test]
+fn field_attributes_tuple_struct() {
+    let mut rng = rand::rng();
+    let ctx = TaggedCtx((), FloatMutation::default());
+    let mut genome = Tagged(3, 0.0);
+    genome.mutate(&ctx, 1.0, &mut rng);
+    assert_eq!(genome.0, 3);
+}
+
+#[derive(Clone, Debug, PartialEq, RandomlyMutable, Crossover)]
+enum Gene {
+    Connection {
+        #[randmut(skip)]
+        #[crossover(skip)]
+        innovation: u32,
+        weight: Val,
+    },
+    Bias(#[crossover(take_from = "second")] Val),
+}
+
+#[test]
+fn field_attributes_enum() {
+    let mut rng = rand::rng();
+
+    // the context comes from `weight`, since `innovation` is skipped.
+    let a = Gene::Connection {
+        innovation: 1,
+        weight: Val(0.0),
+    };
+    let b = Gene::Connection {
+        innovation: 2,
+        weight: Val(2.0),
+    };
+    assert_eq!(
+        a.crossover(&b, &(), 0.0, &mut rng),
+        Gene::Connection {
+            innovation: 1,
+            weight: Val(1.0),
+        }
+    );
+    assert_eq!(
+        Gene::Bias(Val(0.0)).crossover(&Gene::Bias(Val(2.0)), &(), 0.0, &mut rng),
+        Gene::Bias(Val(2.0))
+    );
+
+    let mut gene = a.clone();
+    gene.mutate(&(), 0.0, &mut rng);
+    assert_eq!(gene, a);
+}
+
+// ──────────────────────────────────────────────────────────────────────────────
+// Speciated
+// ──────────────────────────────────────────────────────────────────────────────
+
+impl Speciated for Val {
+    type Context = ();
+    fn divergence(&self, other: &Self, _ctx: &()) -> f32 {
+        (self.0 - other.0).abs()
+    }
+}
+
+/// Divergence divided by a scale given in the context.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Scaled(f32);
+
+impl Speciated for Scaled {
+    type Context = f32;
+    fn divergence(&self, other: &Self, scale: &f32) -> f32 {
+        (self.0 - other.0).abs() / scale
+    }
+}
+
+/// `fitness` is skipped, so it doesn't need to implement [`Speciated`].
+#[derive(Clone, Debug, Speciated)]
+struct Species {
+    #[speciated(weight = 3)]
+    shape: Val,
+    size: Val,
+    #[speciated(skip)]
+    fitness: f32,
+}
+
+#[test]
+fn speciated_weighted_sum() {
+    let a = Species {
+        shape: Val(0.0),
+        size: Val(0.0),
+        fitness: 1.0,
+    };
+    let b = Species {
+        shape: Val(1.0),
+        size: Val(0.5),
+        fitness: 100.0,
+    };
+    assert_eq!(a.divergence(&b, &()), 3.5);
+    assert_eq!(b.divergence(&a, &()), 3.5);
+    assert_eq!(a.divergence(&a, &()), 0.0);
+}
+
+#[derive(Clone, Debug, Speciated)]
+#[speciated(normalize, create_context(name = MixedSpeciesCtx, derive(Default)))]
+struct MixedSpecies {
+    val: Val,
+    #[speciated(weight = 0.5)]
+    scaled: Scaled,
+    #[speciated(skip)]
+    id: u32,
+}
+
+#[test]
+fn speciated_normalized_per_field_context() {
+    let ctx = MixedSpeciesCtx {
+        val: (),
+        scaled: 2.0,
+    };
+    let a = MixedSpecies {
+        val: Val(0.0),
+        scaled: Scaled(0.0),
+        id: 1,
+    };
+    let b = MixedSpecies {
+        val: Val(0.25),
+        scaled: Scaled(1.0),
+        id: 2,
+    };
+    // (0.25 + 0.5 * 0.5) / 1.5
+    assert!((a.divergence(&b, &ctx) - 1.0 / 3.0).abs() < 1e-6);
+}
+
+#[derive(Clone, Debug, Speciated)]
+#[speciated(normalize)]
+enum Part {
+    Joint {
+        angle: Val,
+        #[speciated(skip)]
+        id: u32,
+    },
+    Limb(Val, #[speciated(weight = 3)] Val),
+    Empty,
+}
+
+#[test]
+fn speciated_enum() {
+    let joint = |angle, id| Part::Joint {
+        angle: Val(angle),
+        id,
+    };
+    assert_eq!(joint(0.0, 1).divergence(&joint(0.5, 2), &()), 0.5);
+    assert_eq!(
+        Part::Limb(Val(0.0), Val(0.0)).divergence(&Part::Limb(Val(1.0), Val(0.0)), &()),
+        0.25
+    );
+    assert_eq!(Part::Empty.divergence(&Part::Empty, &()), 0.0);
+
+    // different variants are maximally divergent.
+    assert_eq!(joint(0.0, 1).divergence(&Part::Empty, &()), 1.0);
+    assert_eq!(
+        Part::Limb(Val(0.0), Val(0.0)).divergence(&joint(0.0, 1), &()),
+        1.0
+    );
+}
@@ -0,0 +1,228 @@
+//! Integration tests for [`DifferentialEvolution`] and its strategies.
+
+use genetic_rs::prelude::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shared test genome
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A point in 3D space.
+#[derive(Clone, Debug, PartialEq)]
+struct Point([f64; 3]);
+
+impl RealParameters for Point {
+    fn to_parameters(&self) -> Vec<f64> {
+        self.0.to_vec()
+    }
+
+    fn from_parameters(parameters: Vec<f64>) -> Self {
+        Self(parameters.try_into().unwrap())
+    }
+}
+
+impl GenerateRandom for Point {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        Self([
+            rng.random_range(-5.0..5.0),
+            rng.random_range(-5.0..5.0),
+            rng.random_range(-5.0..5.0),
+        ])
+    }
+}
+
+/// The negated sphere function, with its maximum of 0.0 at (1, 2, 3).
+fn sphere(p: &Point) -> f32 {
+    let target = [1.0, 2.0, 3.0];
+    -p.0.iter()
+        .zip(target)
+        .map(|(x, t)| (x - t) * (x - t))
+        .sum::<f64>() as f32
+}
+
+fn optimizer(strategy: DeStrategy) -> DifferentialEvolution<Point, fn(&Point) -> f32> {
+    let mut rng = rand::rng();
+    DifferentialEvolution::new_random(
+        &mut rng,
+        30,
+        sphere as fn(&Point) -> f32,
+        strategy,
+        DeParams::default(),
+    )
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Convergence
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn rand1bin_converges() {
+    let mut de = optimizer(DeStrategy::Rand1Bin);
+    de.perform_steps(200);
+    assert!(de.best().1 > -1e-3, "best fitness {}", de.best().1);
+}
+
+#[test]
+fn best1bin_converges() {
+    let mut de = optimizer(DeStrategy::Best1Bin);
+    de.perform_steps(200);
+    assert!(de.best().1 > -1e-3, "best fitness {}", de.best().1);
+}
+
+#[test]
+fn current_to_best_converges() {
+    let mut de = optimizer(DeStrategy::CurrentToBest1Bin);
+    de.perform_steps(200);
+    assert!(de.best().1 > -1e-3, "best fitness {}", de.best().1);
+}
+
+#[test]
+fn self_adaptation_converges_and_varies_params() {
+    let mut de = optimizer(DeStrategy::Rand1Bin).with_self_adaptation(JdeSettings::default());
+    de.perform_steps(200);
+
+    assert!(de.best().1 > -1e-3, "best fitness {}", de.best().1);
+    let first = de.params()[0];
+    assert!(de.params().iter().any(|p| *p != first));
+    for p in de.params() {
+        assert!((0.1..=1.0).contains(&p.f));
+        assert!((0.0..=1.0).contains(&p.cr));
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Invariants
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn fitness_never_decreases() {
+    let mut de = optimizer(DeStrategy::Rand1Bin);
+    let mut previous = de.fitness().to_vec();
+    for _ in 0..20 {
+        de.step();
+        for (new, old) in de.fitness().iter().zip(&previous) {
+            assert!(new >= old);
+        }
+        previous = de.fitness().to_vec();
+    }
+    assert_eq!(de.generation(), 20);
+}
+
+#[test]
+fn bounds_are_respected() {
+    let mut rng = rand::rng();
+    let population: Vec<Point> = (0..20)
+        .map(|_| Point([rng.random_range(-0.5..=0.5); 3]))
+        .collect();
+    let mut de = DifferentialEvolution::new(
+        population,
+        sphere,
+        DeStrategy::Rand1Bin,
+        DeParams::default(),
+    )
+    .with_bounds(-0.5, 0.5);
+    de.perform_steps(50);
+
+    // the optimum is outside the bounds, so the population must pile up against them.
+    for p in de.population() {
+        assert!(p.0.iter().all(|v| (-0.5..=0.5).contains(v)));
+    }
+    assert!((de.best().0 .0[2] - 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn fixed_params_without_adaptation() {
+    let mut de = optimizer(DeStrategy::Best1Bin);
+    de.perform_steps(10);
+    assert!(de.params().iter().all(|p| *p == DeParams::default()));
+}
+
+/// NaN members are replaced by any trial, and never steer the best-based strategies.
+#[test]
+fn nan_fitness_is_worst() {
+    let fitness = |p: &Point| if p.0[0] < 0.0 { f32::NAN } else { sphere(p) };
+    let mut rng = rand::rng();
+    let mut de = DifferentialEvolution::new_random(
+        &mut rng,
+        30,
+        fitness,
+        DeStrategy::Best1Bin,
+        DeParams::default(),
+    );
+    de.perform_steps(200);
+
+    assert!(de.fitness().iter().all(|f| !f.is_nan()));
+    assert!(de.best().1 > -1e-3, "best fitness {}", de.best().1);
+}
+
+#[test]
+#[should_panic(expected = "between 0.0 and 1.0")]
+fn invalid_self_adaptation_panics() {
+    optimizer(DeStrategy::Rand1Bin).with_self_adaptation(JdeSettings {
+        tau_f: 1.5,
+        ..Default::default()
+    });
+}
+
+#[test]
+#[should_panic(expected = "at least 4")]
+fn small_population_panics() {
+    let population = vec![Point([0.0; 3]); 3];
+    DifferentialEvolution::new(
+        population,
+        sphere,
+        DeStrategy::Rand1Bin,
+        DeParams::default(),
+    );
+}
+
+#[test]
+fn vec_real_vector() {
+    let mut rng = rand::rng();
+    let population: Vec<Vec<f64>> = (0..10)
+        .map(|_| (0..4).map(|_| rng.random_range(-1.0..1.0)).collect())
+        .collect();
+    let mut de = DifferentialEvolution::new(
+        population,
+        |v: &Vec<f64>| -v.iter().map(|x| x * x).sum::<f64>() as f32,
+        DeStrategy::CurrentToBest1Bin,
+        DeParams { f: 0.7, cr: 0.5 },
+    );
+    let before = de.best().1;
+    de.perform_steps(50);
+    assert!(de.best().1 >= before);
+    assert_eq!(de.into_population().len(), 10);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Built-in genomes
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct UnitCube;
+
+impl RealBounds for UnitCube {
+    const BOUNDS: &'static [(f32, f32)] = &[(0.0, 1.0); 3];
+}
+
+/// [`RealVector`] implements [`RealParameters`], and clamps trials into its own bounds.
+#[test]
+fn real_vector_genome() {
+    let fitness =
+        |g: &RealVector<UnitCube>| -g.values().iter().map(|v| (v - 0.25).powi(2)).sum::<f32>();
+    let mut rng = rand::rng();
+    let mut de = DifferentialEvolution::new_random(
+        &mut rng,
+        20,
+        fitness,
+        DeStrategy::Rand1Bin,
+        DeParams::default(),
+    );
+    de.perform_steps(100);
+
+    let (best, fitness) = de.best();
+    assert!(fitness > -1e-3, "best fitness {fitness}");
+    assert!(de
+        .population()
+        .iter()
+        .all(|g| g.values().iter().all(|v| (0.0..=1.0).contains(v))));
+    assert!(best.values().iter().all(|v| (v - 0.25).abs() < 0.05));
+}
@@ -0,0 +1,136 @@
+//! Integration tests for [`EdaRepopulator`] and its models.
+
+use genetic_rs::prelude::*;
+
+/// The number of `true` bits.
+fn one_max(g: &[bool]) -> f32 {
+    g.iter().filter(|b| **b).count() as f32
+}
+
+fn simulate(model: EdaModel, generations: usize) -> f32 {
+    let mut rng = rand::rng();
+    let genomes = (0..50)
+        .map(|_| (0..30).map(|_| rng.random_bool(0.5)).collect())
+        .collect();
+
+    let mut sim = GeneticSim::new(
+        genomes,
+        FitnessEliminator::new_without_observer(|g: &Vec<bool>| one_max(g)),
+        EdaRepopulator::new(model, 30).with_margin(1.0 / 30.0),
+    );
+    sim.perform_generations(generations);
+
+    sim.genomes.iter().map(|g| one_max(g)).fold(0.0, f32::max)
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Models
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn umda_frequencies() {
+    let mut rng = rand::rng();
+    let mut eda = EdaRepopulator::new(EdaModel::Umda, 3);
+    eda.fit(
+        &[
+            vec![true, false, true],
+            vec![true, false, false],
+            vec![true, true, false],
+            vec![true, false, false],
+        ],
+        &mut rng,
+    );
+    assert_eq!(eda.probabilities, vec![1.0, 0.25, 0.25]);
+}
+
+#[test]
+fn pbil_moves_towards_survivors() {
+    let mut rng = rand::rng();
+    let mut eda = EdaRepopulator::new(
+        EdaModel::Pbil {
+            learning_rate: 0.5,
+            mutation_probability: 0.0,
+            mutation_shift: 0.0,
+        },
+        2,
+    );
+    eda.fit(&[vec![true, false], vec![true, false]], &mut rng);
+    assert_eq!(eda.probabilities, vec![0.75, 0.25]);
+}
+
+#[test]
+fn compact_ga_moves_towards_winner() {
+    let mut rng = rand::rng();
+    let mut eda = EdaRepopulator::new(
+        EdaModel::CompactGa {
+            virtual_population: 10.0,
+        },
+        3,
+    );
+    // the first survivor wins against the last, so only the bits where they disagree move.
+    eda.fit(
+        &[vec![true, false, true], vec![false, false, false]],
+        &mut rng,
+    );
+    assert_eq!(eda.probabilities, vec![0.6, 0.5, 0.6]);
+}
+
+#[test]
+fn margin_keeps_bits_open() {
+    let mut rng = rand::rng();
+    let mut eda = EdaRepopulator::new(EdaModel::Umda, 2).with_margin(0.1);
+    eda.fit(&[vec![true, false]], &mut rng);
+    assert_eq!(eda.probabilities, vec![0.9, 0.1]);
+}
+
+#[test]
+fn evaluated_survivors_are_ranked_by_fitness() {
+    let mut eda = EdaRepopulator::new(
+        EdaModel::CompactGa {
+            virtual_population: 10.0,
+        },
+        1,
+    );
+
+    // the worse genome comes first, but the fitness puts it last.
+    let population =
+        EvaluatedPopulation::new(vec![vec![false], vec![true]]).with_fitness(vec![0.0, 1.0]);
+    let genomes = eda.repopulate_evaluated(population, 5);
+
+    assert_eq!(genomes.len(), 5);
+    assert_eq!(eda.probabilities, vec![0.6]);
+}
+
+#[test]
+#[should_panic(expected = "does not match")]
+fn wrong_length_panics() {
+    let mut eda = EdaRepopulator::new(EdaModel::Umda, 3);
+    eda.repopulate(&mut vec![vec![true]], 5);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Simulation
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn umda_solves_one_max() {
+    assert!(simulate(EdaModel::Umda, 30) >= 28.0);
+}
+
+#[test]
+fn pbil_solves_one_max() {
+    let model = EdaModel::Pbil {
+        learning_rate: 0.3,
+        mutation_probability: 0.02,
+        mutation_shift: 0.05,
+    };
+    assert!(simulate(model, 50) >= 28.0);
+}
+
+#[test]
+fn compact_ga_solves_one_max() {
+    let model = EdaModel::CompactGa {
+        virtual_population: 20.0,
+    };
+    assert!(simulate(model, 50) >= 28.0);
+}
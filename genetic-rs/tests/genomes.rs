@@ -0,0 +1,565 @@
+//! Integration tests for the built-in genome library.
+
+use genetic_rs::prelude::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// RealVector
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Box3;
+
+impl RealBounds for Box3 {
+    const BOUNDS: &'static [(f32, f32)] = &[(-1.0, 1.0), (0.0, 10.0), (5.0, 6.0)];
+}
+
+type Real = RealVector<Box3>;
+
+fn in_bounds(g: &Real) -> bool {
+    g.values()
+        .iter()
+        .zip(Real::bounds())
+        .all(|(v, (min, max))| (*min..=*max).contains(v))
+}
+
+fn operators(mutation: RealMutation, crossover: RealCrossover) -> RealOperators {
+    RealOperators {
+        mutation,
+        crossover,
+        boundary: BoundaryHandling::Clamp,
+    }
+}
+
+const MUTATIONS: [RealMutation; 4] = [
+    RealMutation::Gaussian { sigma: 0.5 },
+    RealMutation::Uniform,
+    RealMutation::Polynomial { eta: 20.0 },
+    RealMutation::Cauchy { scale: 0.5 },
+];
+
+const CROSSOVERS: [RealCrossover; 5] = [
+    RealCrossover::Sbx { eta: 2.0 },
+    RealCrossover::BlendAlpha { alpha: 0.5 },
+    RealCrossover::Arithmetic,
+    RealCrossover::Uniform,
+    RealCrossover::OnePoint,
+];
+
+#[test]
+fn real_random_within_bounds() {
+    let mut rng = rand::rng();
+    let genomes: Vec<Real> = Vec::gen_random(&mut rng, 100);
+    assert!(genomes.iter().all(in_bounds));
+}
+
+#[test]
+fn real_new_clamps() {
+    let g = Real::new(vec![-5.0, 5.0, 50.0]);
+    assert_eq!(g.values(), &[-1.0, 5.0, 6.0]);
+}
+
+#[test]
+#[should_panic(expected = "does not match")]
+fn real_new_wrong_length_panics() {
+    Real::new(vec![0.0]);
+}
+
+#[test]
+fn real_operators_respect_bounds() {
+    let mut rng = rand::rng();
+    for mutation in MUTATIONS {
+        for crossover in CROSSOVERS {
+            let ctx = operators(mutation, crossover);
+            let a = Real::gen_random(&mut rng);
+            let b = Real::gen_random(&mut rng);
+            for _ in 0..50 {
+                assert!(in_bounds(&a.divide(&ctx, 1.0, &mut rng)));
+                assert!(in_bounds(&a.crossover(&b, &ctx, 1.0, &mut rng)));
+            }
+        }
+    }
+}
+
+#[test]
+fn real_zero_rate_does_not_mutate() {
+    let mut rng = rand::rng();
+    let g = Real::gen_random(&mut rng);
+    for mutation in MUTATIONS {
+        let ctx = operators(mutation, RealCrossover::Uniform);
+        assert_eq!(g.divide(&ctx, 0.0, &mut rng), g);
+    }
+}
+
+#[test]
+fn real_crossover_stays_between_parents() {
+    let mut rng = rand::rng();
+    let a = Real::new(vec![-1.0, 0.0, 5.0]);
+    let b = Real::new(vec![1.0, 10.0, 6.0]);
+
+    for crossover in [
+        RealCrossover::Arithmetic,
+        RealCrossover::Uniform,
+        RealCrossover::OnePoint,
+    ] {
+        let ctx = operators(RealMutation::Uniform, crossover);
+        for _ in 0..20 {
+            let child = a.crossover(&b, &ctx, 0.0, &mut rng);
+            for ((c, x), y) in child.values().iter().zip(a.values()).zip(b.values()) {
+                assert!(x.min(*y) <= *c && *c <= x.max(*y));
+            }
+        }
+    }
+}
+
+#[test]
+fn one_point_takes_prefix_and_suffix() {
+    let mut rng = rand::rng();
+    let a = Real::new(vec![-1.0, 0.0, 5.0]);
+    let b = Real::new(vec![1.0, 10.0, 6.0]);
+    let ctx = operators(RealMutation::Uniform, RealCrossover::OnePoint);
+
+    for _ in 0..20 {
+        let child = a.crossover(&b, &ctx, 0.0, &mut rng);
+        let cut = child
+            .values()
+            .iter()
+            .zip(a.values())
+            .take_while(|(c, x)| c == x)
+            .count();
+        assert_eq!(&child.values()[cut..], &b.values()[cut..]);
+    }
+}
+
+#[test]
+fn boundary_handling() {
+    assert_eq!(BoundaryHandling::Clamp.repair(1.5, 0.0, 1.0), 1.0);
+    assert_eq!(BoundaryHandling::Reflect.repair(1.25, 0.0, 1.0), 0.75);
+    assert_eq!(BoundaryHandling::Reflect.repair(-0.25, 0.0, 1.0), 0.25);
+    assert_eq!(BoundaryHandling::Wrap.repair(1.25, 0.0, 1.0), 0.25);
+    assert_eq!(BoundaryHandling::Wrap.repair(-0.25, 0.0, 1.0), 0.75);
+    assert_eq!(BoundaryHandling::Wrap.repair(0.5, 0.0, 1.0), 0.5);
+}
+
+#[test]
+fn real_vector_simulation() {
+    let mut rng = rand::rng();
+    // maximized at the upper corner of the box.
+    let fitness = |g: &Real| g.values().iter().sum::<f32>();
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 50),
+        FitnessEliminator::new_without_observer(fitness),
+        CrossoverRepopulator::new(0.2, RealOperators::default()),
+    );
+    sim.perform_generations(100);
+
+    let best = sim.genomes.iter().map(fitness).fold(f32::MIN, f32::max);
+    assert!(best > 16.5, "best fitness {best}");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// BitString
+// ─────────────────────────────────────────────────────────────────────────────
+
+type Bits = BitString<100>;
+
+fn bit_operators(crossover: BitCrossover) -> BitOperators {
+    BitOperators { crossover }
+}
+
+#[test]
+fn bits_basic_operations() {
+    let mut bits = Bits::zeros();
+    assert_eq!(bits.len(), 100);
+    assert_eq!(bits.words().len(), 2);
+
+    bits.set(3, true);
+    bits.flip(99);
+    assert!(bits.get(3) && bits.get(99) && !bits.get(4));
+    assert_eq!(bits.count_ones(), 2);
+
+    assert_eq!(Bits::ones().count_ones(), 100);
+    assert_eq!(Bits::ones().hamming_distance(&Bits::zeros()), 100);
+
+    let bools: Vec<bool> = (0..100).map(|i| i % 3 == 0).collect();
+    let from = Bits::from_bools(&bools);
+    assert_eq!(from.iter().collect::<Vec<_>>(), bools);
+}
+
+#[test]
+fn bits_random_keeps_padding_clear() {
+    let mut rng = rand::rng();
+    for _ in 0..20 {
+        let bits = Bits::gen_random(&mut rng);
+        assert_eq!(bits.words()[1] >> 36, 0);
+    }
+}
+
+#[test]
+fn bits_mutation_rate() {
+    let mut rng = rand::rng();
+    let ctx = BitOperators::default();
+    let bits = Bits::zeros();
+
+    assert_eq!(bits.divide(&ctx, 0.0, &mut rng), bits);
+    assert_eq!(bits.divide(&ctx, 1.0, &mut rng), Bits::ones());
+}
+
+#[test]
+fn bits_crossover_takes_bits_from_parents() {
+    let mut rng = rand::rng();
+    let (zeros, ones) = (Bits::zeros(), Bits::ones());
+
+    for crossover in [
+        BitCrossover::OnePoint,
+        BitCrossover::TwoPoint,
+        BitCrossover::Uniform,
+    ] {
+        let child = zeros.crossover(&ones, &bit_operators(crossover), 0.0, &mut rng);
+        assert_eq!(child.words()[1] >> 36, 0);
+
+        // the set bits of a point crossover form contiguous runs.
+        let runs = child
+            .iter()
+            .collect::<Vec<_>>()
+            .windows(2)
+            .filter(|w| w[0] != w[1])
+            .count();
+        match crossover {
+            BitCrossover::OnePoint => assert!(runs <= 1),
+            BitCrossover::TwoPoint => assert!(runs <= 2),
+            BitCrossover::Uniform => {}
+        }
+    }
+}
+
+#[test]
+fn one_point_bits_keep_prefix() {
+    let mut rng = rand::rng();
+    let ctx = bit_operators(BitCrossover::OnePoint);
+    for _ in 0..20 {
+        let child = Bits::zeros().crossover(&Bits::ones(), &ctx, 0.0, &mut rng);
+        let cut = child.iter().take_while(|b| !b).count();
+        assert!(child.iter().skip(cut).all(|b| b));
+    }
+}
+
+#[test]
+fn bits_divergence_is_normalized_hamming() {
+    let mut a = Bits::zeros();
+    for i in 0..25 {
+        a.set(i, true);
+    }
+    assert_eq!(a.divergence(&Bits::zeros(), &()), 0.25);
+    assert_eq!(a.divergence(&a, &()), 0.0);
+}
+
+#[test]
+fn bits_one_max_simulation() {
+    let mut rng = rand::rng();
+    let fitness = |g: &Bits| g.count_ones() as f32;
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 50),
+        FitnessEliminator::new_without_observer(fitness),
+        CrossoverRepopulator::new(0.01, BitOperators::default()),
+    );
+    sim.perform_generations(100);
+
+    let best = sim.genomes.iter().map(fitness).fold(0.0, f32::max);
+    assert!(best >= 95.0, "best fitness {best}");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Permutation
+// ─────────────────────────────────────────────────────────────────────────────
+
+type Perm = Permutation<12>;
+
+const PERMUTATION_MUTATIONS: [PermutationMutation; 5] = [
+    PermutationMutation::Swap,
+    PermutationMutation::Insert,
+    PermutationMutation::Scramble,
+    PermutationMutation::Inversion,
+    PermutationMutation::TwoOpt,
+];
+
+const PERMUTATION_CROSSOVERS: [PermutationCrossover; 5] = [
+    PermutationCrossover::Pmx,
+    PermutationCrossover::Order,
+    PermutationCrossover::Cycle,
+    PermutationCrossover::EdgeRecombination,
+    PermutationCrossover::PositionBased,
+];
+
+fn is_valid(p: &Perm) -> bool {
+    let mut sorted = p.order().to_vec();
+    sorted.sort();
+    sorted == Perm::identity().into_order()
+}
+
+#[test]
+fn permutation_operators_stay_valid() {
+    let mut rng = rand::rng();
+    for mutation in PERMUTATION_MUTATIONS {
+        for crossover in PERMUTATION_CROSSOVERS {
+            let ctx = PermutationOperators {
+                mutation,
+                crossover,
+            };
+            for _ in 0..50 {
+                let a = Perm::gen_random(&mut rng);
+                let b = Perm::gen_random(&mut rng);
+                assert!(is_valid(&a.divide(&ctx, 0.3, &mut rng)));
+                assert!(is_valid(&a.crossover(&b, &ctx, 0.3, &mut rng)));
+            }
+        }
+    }
+}
+
+#[test]
+#[should_panic(expected = "not a permutation")]
+fn permutation_new_rejects_duplicates() {
+    Permutation::<3>::new(vec![0, 1, 1]);
+}
+
+#[test]
+fn identical_parents_produce_identical_child() {
+    let mut rng = rand::rng();
+    let a = Perm::gen_random(&mut rng);
+    // edge recombination only preserves the tour, which may come out reversed.
+    for crossover in PERMUTATION_CROSSOVERS
+        .into_iter()
+        .filter(|c| *c != PermutationCrossover::EdgeRecombination)
+    {
+        let ctx = PermutationOperators {
+            crossover,
+            ..Default::default()
+        };
+        assert_eq!(a.crossover(&a, &ctx, 0.0, &mut rng), a);
+    }
+}
+
+#[test]
+fn cycle_crossover_keeps_positions() {
+    let mut rng = rand::rng();
+    let ctx = PermutationOperators {
+        crossover: PermutationCrossover::Cycle,
+        ..Default::default()
+    };
+    for _ in 0..20 {
+        let a = Perm::gen_random(&mut rng);
+        let b = Perm::gen_random(&mut rng);
+        let child = a.crossover(&b, &ctx, 0.0, &mut rng);
+        for i in 0..12 {
+            assert!(child.order()[i] == a.order()[i] || child.order()[i] == b.order()[i]);
+        }
+    }
+}
+
+#[test]
+fn edge_recombination_uses_parent_edges() {
+    let mut rng = rand::rng();
+    let ctx = PermutationOperators {
+        crossover: PermutationCrossover::EdgeRecombination,
+        ..Default::default()
+    };
+    let a = Perm::identity();
+    let b = Perm::new(vec![0, 2, 4, 6, 8, 10, 1, 3, 5, 7, 9, 11]);
+
+    let edges = |p: &Perm| -> Vec<(usize, usize)> {
+        let o = p.order();
+        (0..12)
+            .map(|i| {
+                let (x, y) = (o[i], o[(i + 1) % 12]);
+                (x.min(y), x.max(y))
+            })
+            .collect()
+    };
+    let parent_edges: Vec<_> = edges(&a).into_iter().chain(edges(&b)).collect();
+
+    for _ in 0..20 {
+        let child = a.crossover(&b, &ctx, 0.0, &mut rng);
+        let inherited = edges(&child)
+            .iter()
+            .filter(|e| parent_edges.contains(e))
+            .count();
+        // a dead end can force a foreign edge, but most edges come from the parents.
+        assert!(inherited >= 9, "only {inherited} inherited edges");
+    }
+}
+
+#[test]
+fn zero_rate_does_not_permute() {
+    let mut rng = rand::rng();
+    let a = Perm::gen_random(&mut rng);
+    for mutation in PERMUTATION_MUTATIONS {
+        let ctx = PermutationOperators {
+            mutation,
+            ..Default::default()
+        };
+        assert_eq!(a.divide(&ctx, 0.0, &mut rng), a);
+    }
+}
+
+#[test]
+fn kendall_tau_divergence() {
+    let identity = Permutation::<4>::identity();
+    let reversed = Permutation::<4>::new(vec![3, 2, 1, 0]);
+    let one_swap = Permutation::<4>::new(vec![1, 0, 2, 3]);
+
+    assert_eq!(identity.kendall_tau_distance(&reversed), 6);
+    assert_eq!(identity.kendall_tau_distance(&one_swap), 1);
+    assert_eq!(identity.divergence(&reversed, &()), 1.0);
+    assert_eq!(identity.divergence(&identity, &()), 0.0);
+    assert_eq!(
+        one_swap.divergence(&reversed, &()),
+        reversed.divergence(&one_swap, &())
+    );
+}
+
+#[test]
+fn permutation_sorting_simulation() {
+    let mut rng = rand::rng();
+    // the fraction of pairs in the right order, maximized by the identity.
+    let fitness = |p: &Perm| 1.0 - p.divergence(&Perm::identity(), &());
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 50),
+        FitnessEliminator::new_without_observer(fitness),
+        CrossoverRepopulator::new(0.05, PermutationOperators::default()),
+    );
+    sim.perform_generations(100);
+
+    let best = sim.genomes.iter().map(fitness).fold(0.0, f32::max);
+    assert!(best > 0.95, "best fitness {best}");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// IntegerVector and CategoricalVector
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Hyperparameters;
+
+impl IntegerRanges for Hyperparameters {
+    const RANGES: &'static [(i64, i64)] = &[(1, 8), (16, 512), (-3, 3)];
+}
+
+type Ints = IntegerVector<Hyperparameters>;
+
+struct Choices;
+
+impl Alphabets for Choices {
+    const SIZES: &'static [usize] = &[3, 2, 5];
+}
+
+type Cats = CategoricalVector<Choices>;
+
+fn ints_in_range(g: &Ints) -> bool {
+    g.genes()
+        .iter()
+        .zip(Ints::ranges())
+        .all(|(g, (min, max))| (*min..=*max).contains(g))
+}
+
+fn cats_in_range(g: &Cats) -> bool {
+    g.genes()
+        .iter()
+        .zip(Cats::sizes())
+        .all(|(g, size)| g < size)
+}
+
+#[test]
+fn discrete_operators_stay_in_range() {
+    let mut rng = rand::rng();
+    for ctx in [
+        DiscreteMutation::Creep { step: 100 },
+        DiscreteMutation::RandomReset,
+    ] {
+        for _ in 0..50 {
+            let (a, b) = (Ints::gen_random(&mut rng), Ints::gen_random(&mut rng));
+            assert!(ints_in_range(&a));
+            assert!(ints_in_range(&a.divide(&ctx, 1.0, &mut rng)));
+            assert!(ints_in_range(&a.crossover(&b, &ctx, 1.0, &mut rng)));
+
+            let (a, b) = (Cats::gen_random(&mut rng), Cats::gen_random(&mut rng));
+            assert!(cats_in_range(&a));
+            assert!(cats_in_range(&a.divide(&ctx, 1.0, &mut rng)));
+            assert!(cats_in_range(&a.crossover(&b, &ctx, 1.0, &mut rng)));
+        }
+    }
+}
+
+#[test]
+fn creep_moves_by_at_most_step() {
+    let mut rng = rand::rng();
+    let ctx = DiscreteMutation::Creep { step: 2 };
+    let g = Ints::new(vec![4, 100, 0]);
+    for _ in 0..50 {
+        let child = g.divide(&ctx, 1.0, &mut rng);
+        for (c, p) in child.genes().iter().zip(g.genes()) {
+            assert!((1..=2).contains(&(c - p).abs()));
+        }
+    }
+}
+
+#[test]
+fn integer_new_clamps() {
+    assert_eq!(Ints::new(vec![0, 1000, 3]).genes(), &[1, 512, 3]);
+}
+
+#[test]
+#[should_panic(expected = "outside of its alphabet")]
+fn categorical_new_rejects_out_of_alphabet() {
+    Cats::new(vec![0, 2, 0]);
+}
+
+#[test]
+#[should_panic(expected = "Creep step must be at least 1, got 0")]
+fn creep_rejects_zero_step() {
+    let mut g = Ints::new(vec![1, 2, 3]);
+    g.mutate(&DiscreteMutation::Creep { step: 0 }, 1.0, &mut rand::rng());
+}
+
+#[test]
+fn discrete_uniform_crossover_takes_parent_genes() {
+    let mut rng = rand::rng();
+    let ctx = DiscreteMutation::default();
+    let (a, b) = (Cats::new(vec![0, 0, 0]), Cats::new(vec![2, 1, 4]));
+    for _ in 0..20 {
+        let child = a.crossover(&b, &ctx, 0.0, &mut rng);
+        for i in 0..3 {
+            assert!(child.genes()[i] == a.genes()[i] || child.genes()[i] == b.genes()[i]);
+        }
+    }
+}
+
+#[test]
+fn categorical_decode() {
+    let optimizers = ["sgd", "adam", "rmsprop"];
+    let activations = ["relu", "tanh"];
+    let layers = ["1", "2", "3", "4", "5"];
+
+    let g = Cats::new(vec![1, 0, 4]);
+    let decoded = g.decode(&[&optimizers, &activations, &layers]);
+    assert_eq!(decoded, vec![&"adam", &"relu", &"5"]);
+}
+
+#[test]
+fn integer_vector_simulation() {
+    let mut rng = rand::rng();
+    // maximized at (8, 256, -3).
+    let fitness = |g: &Ints| {
+        let [a, b, c] = [g.genes()[0], g.genes()[1], g.genes()[2]];
+        (a - (b - 256).abs() - c) as f32
+    };
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 50),
+        FitnessEliminator::new_without_observer(fitness),
+        CrossoverRepopulator::new(0.3, DiscreteMutation::Creep { step: 16 }),
+    );
+    sim.perform_generations(100);
+
+    let best = sim.genomes.iter().map(fitness).fold(f32::MIN, f32::max);
+    assert!(best >= 0.0, "best fitness {best}");
+}
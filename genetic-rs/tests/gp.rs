@@ -0,0 +1,827 @@
+//! Integration tests for the genetic programming module.
+
+use genetic_rs::prelude::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shared primitive sets
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Arith {
+    Add,
+    Sub,
+    Mul,
+    X,
+    One,
+}
+
+impl std::fmt::Display for Arith {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+struct Arithmetic;
+
+impl PrimitiveSet for Arithmetic {
+    type Type = ();
+    type Op = Arith;
+
+    const ROOT: () = ();
+    const PRIMITIVES: &'static [Primitive<Arith, ()>] = &[
+        Primitive {
+            op: Arith::Add,
+            returns: (),
+            args: &[(), ()],
+        },
+        Primitive {
+            op: Arith::Sub,
+            returns: (),
+            args: &[(), ()],
+        },
+        Primitive {
+            op: Arith::Mul,
+            returns: (),
+            args: &[(), ()],
+        },
+        Primitive {
+            op: Arith::X,
+            returns: (),
+            args: &[],
+        },
+        Primitive {
+            op: Arith::One,
+            returns: (),
+            args: &[],
+        },
+    ];
+}
+
+type ArithTree = Tree<Arithmetic>;
+
+fn eval_arith(tree: &ArithTree, x: f32) -> f32 {
+    tree.eval(|op, args| match op {
+        Arith::Add => args[0] + args[1],
+        Arith::Sub => args[0] - args[1],
+        Arith::Mul => args[0] * args[1],
+        Arith::X => x,
+        Arith::One => 1.0,
+    })
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Num,
+    Bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Logic {
+    If,
+    Less,
+    Add,
+    X,
+    Const(f32),
+    True,
+}
+
+#[derive(Debug)]
+enum Value {
+    Num(f32),
+    Bool(bool),
+}
+
+struct Typed;
+
+impl PrimitiveSet for Typed {
+    type Type = Kind;
+    type Op = Logic;
+
+    const ROOT: Kind = Kind::Num;
+    const PRIMITIVES: &'static [Primitive<Logic, Kind>] = &[
+        Primitive {
+            op: Logic::If,
+            returns: Kind::Num,
+            args: &[Kind::Bool, Kind::Num, Kind::Num],
+        },
+        Primitive {
+            op: Logic::Less,
+            returns: Kind::Bool,
+            args: &[Kind::Num, Kind::Num],
+        },
+        Primitive {
+            op: Logic::Add,
+            returns: Kind::Num,
+            args: &[Kind::Num, Kind::Num],
+        },
+        Primitive {
+            op: Logic::X,
+            returns: Kind::Num,
+            args: &[],
+        },
+        Primitive {
+            op: Logic::Const(0.0),
+            returns: Kind::Num,
+            args: &[],
+        },
+        Primitive {
+            op: Logic::True,
+            returns: Kind::Bool,
+            args: &[],
+        },
+    ];
+
+    fn instantiate(op: &Logic, rng: &mut impl rand::Rng) -> Logic {
+        match op {
+            Logic::Const(_) => Logic::Const(rng.random_range(-10.0..10.0)),
+            op => op.clone(),
+        }
+    }
+}
+
+/// Evaluates a typed tree, panicking if any node receives a value of the wrong type.
+fn eval_typed(tree: &Tree<Typed>, x: f32) -> f32 {
+    let value = tree.eval(|op, args| match (op, args.as_slice()) {
+        (Logic::If, [Value::Bool(c), Value::Num(a), Value::Num(b)]) => {
+            Value::Num(if *c { *a } else { *b })
+        }
+        (Logic::Less, [Value::Num(a), Value::Num(b)]) => Value::Bool(a < b),
+        (Logic::Add, [Value::Num(a), Value::Num(b)]) => Value::Num(a + b),
+        (Logic::X, []) => Value::Num(x),
+        (Logic::Const(c), []) => Value::Num(*c),
+        (Logic::True, []) => Value::Bool(true),
+        (op, args) => panic!("ill-typed node {op:?} with {args:?}"),
+    });
+    match value {
+        Value::Num(n) => n,
+        Value::Bool(_) => panic!("root is not a number"),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Construction
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn full_and_grow_respect_depth() {
+    let mut rng = rand::rng();
+    for depth in 0..5 {
+        for _ in 0..20 {
+            let full = ArithTree::full(depth, &mut rng);
+            assert_eq!(full.depth(), depth);
+            assert_eq!(full.len(), 2usize.pow(depth as u32 + 1) - 1);
+            assert!(ArithTree::grow(depth, &mut rng).depth() <= depth);
+        }
+    }
+}
+
+#[test]
+fn ramped_half_and_half_spreads_depths() {
+    let mut rng = rand::rng();
+    let trees = ArithTree::ramped_half_and_half(50, &mut rng);
+    assert_eq!(trees.len(), 50);
+    for depth in 2..=6 {
+        assert!(trees.iter().any(|t| t.depth() == depth));
+    }
+    assert!(trees.iter().all(|t| t.depth() <= 6));
+}
+
+#[test]
+fn eval_and_display() {
+    let mut rng = rand::rng();
+    for _ in 0..20 {
+        let tree = ArithTree::grow(3, &mut rng);
+        let text = tree.to_string();
+        assert_eq!(text.matches('(').count(), text.matches(')').count());
+        assert_eq!(tree.ops().count(), tree.len());
+    }
+
+    let single = ArithTree::full(0, &mut rng);
+    assert!(single.to_string() == "X" || single.to_string() == "One");
+    assert!(eval_arith(&single, 3.0) == 3.0 || eval_arith(&single, 3.0) == 1.0);
+}
+
+#[test]
+fn subtree_of_root_is_whole_tree() {
+    let mut rng = rand::rng();
+    let tree = ArithTree::full(3, &mut rng);
+    assert_eq!(tree.subtree(0), tree);
+    assert_eq!(tree.subtree(tree.len() - 1).len(), 1);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Typing and operators
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn typed_operators_stay_well_typed() {
+    let mut rng = rand::rng();
+    for mutation in [
+        TreeMutation::Subtree,
+        TreeMutation::Point,
+        TreeMutation::Hoist,
+    ] {
+        let ctx = TreeOperators {
+            mutation,
+            max_depth: 8,
+            ..Default::default()
+        };
+        let mut trees: Vec<Tree<Typed>> = Vec::gen_random(&mut rng, 20);
+        for _ in 0..20 {
+            trees = (0..20)
+                .map(|i| {
+                    let child = trees[i].crossover(&trees[(i + 1) % 20], &ctx, 0.5, &mut rng);
+                    child.divide(&ctx, 0.5, &mut rng)
+                })
+                .collect();
+        }
+
+        for tree in &trees {
+            eval_typed(tree, 1.0);
+            assert!(tree.depth() <= 8);
+        }
+    }
+}
+
+#[test]
+fn hoist_shrinks() {
+    let mut rng = rand::rng();
+    let ctx = TreeOperators {
+        mutation: TreeMutation::Hoist,
+        ..Default::default()
+    };
+    for _ in 0..20 {
+        let tree = ArithTree::full(4, &mut rng);
+        let hoisted = tree.divide(&ctx, 1.0, &mut rng);
+        assert!(hoisted.len() < tree.len());
+    }
+}
+
+#[test]
+fn point_mutation_keeps_shape() {
+    let mut rng = rand::rng();
+    let ctx = TreeOperators {
+        mutation: TreeMutation::Point,
+        ..Default::default()
+    };
+    let tree = ArithTree::full(3, &mut rng);
+    let mutant = tree.divide(&ctx, 1.0, &mut rng);
+    assert_eq!(mutant.len(), tree.len());
+    assert_eq!(mutant.depth(), tree.depth());
+    assert_eq!(
+        tree.ops().zip(mutant.ops()).filter(|(a, b)| a != b).count(),
+        1
+    );
+}
+
+#[test]
+fn crossover_respects_depth_limit() {
+    let mut rng = rand::rng();
+    let ctx = TreeOperators {
+        max_depth: 5,
+        ..Default::default()
+    };
+    let a = ArithTree::full(5, &mut rng);
+    let b = ArithTree::full(5, &mut rng);
+    for _ in 0..50 {
+        assert!(a.crossover(&b, &ctx, 0.0, &mut rng).depth() <= 5);
+    }
+}
+
+#[test]
+fn parsimony_penalizes_size() {
+    let mut rng = rand::rng();
+    let parsimony = Parsimony::new(|_: &ArithTree| 10.0, 0.5);
+    let tree = ArithTree::full(2, &mut rng);
+    assert_eq!(parsimony.fitness(&tree), 10.0 - 0.5 * 7.0);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Simulation
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[test]
+fn symbolic_regression() {
+    let mut rng = rand::rng();
+    // x^2 + x + 1
+    let error = |tree: &ArithTree| -> f32 {
+        (-5..=5)
+            .map(|i| {
+                let x = i as f32;
+                (eval_arith(tree, x) - (x * x + x + 1.0)).abs().min(1000.0)
+            })
+            .sum()
+    };
+
+    let mut sim = GeneticSim::new(
+        ArithTree::ramped_half_and_half(200, &mut rng),
+        FitnessEliminator::new_without_observer(Parsimony::new(
+            move |t: &ArithTree| -error(t),
+            0.01,
+        )),
+        CrossoverRepopulator::new(0.1, TreeOperators::default()),
+    );
+    sim.perform_generations(50);
+
+    let best = sim.genomes.iter().map(error).fold(f32::MAX, f32::min);
+    assert!(best < 1e-3, "best error {best}");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Linear GP
+// ─────────────────────────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Reg {
+    Add,
+    Sub,
+    Mul,
+    Neg,
+}
+
+struct Machine;
+
+impl InstructionSet for Machine {
+    type Op = Reg;
+
+    const OPS: &'static [Reg] = &[Reg::Add, Reg::Sub, Reg::Mul, Reg::Neg];
+    const REGISTERS: usize = 4;
+    const INPUTS: usize = 2;
+    const LENGTH: (usize, usize) = (1, 32);
+
+    fn arity(op: &Reg) -> usize {
+        match op {
+            Reg::Neg => 1,
+            _ => 2,
+        }
+    }
+
+    fn execute(op: &Reg, args: &[f32]) -> f32 {
+        match op {
+            Reg::Add => args[0] + args[1],
+            Reg::Sub => args[0] - args[1],
+            Reg::Mul => args[0] * args[1],
+            Reg::Neg => -args[0],
+        }
+    }
+}
+
+type Prog = Program<Machine>;
+
+fn ins(op: Reg, dest: usize, sources: &[usize]) -> Instruction<Reg> {
+    Instruction {
+        op,
+        dest,
+        sources: sources.to_vec(),
+    }
+}
+
+/// r0 = x * y + x, with two introns.
+fn sample_program() -> Prog {
+    Prog::new(vec![
+        ins(Reg::Mul, 1, &[4, 5]),
+        ins(Reg::Neg, 2, &[4]),
+        ins(Reg::Add, 0, &[1, 4]),
+        ins(Reg::Sub, 3, &[0, 2]),
+    ])
+}
+
+#[test]
+fn program_runs() {
+    assert_eq!(sample_program().run(&[2.0, 3.0]), vec![8.0]);
+}
+
+#[test]
+fn intron_analysis() {
+    let program = sample_program();
+    assert_eq!(program.effective(), vec![true, false, true, false]);
+    assert_eq!(program.effective_len(), 2);
+
+    let stripped = program.without_introns();
+    assert_eq!(stripped.len(), 2);
+    assert_eq!(stripped.run(&[2.0, 3.0]), program.run(&[2.0, 3.0]));
+}
+
+#[test]
+fn overwritten_registers_are_introns() {
+    let program = Prog::new(vec![ins(Reg::Mul, 0, &[4, 5]), ins(Reg::Neg, 0, &[4])]);
+    assert_eq!(program.effective(), vec![false, true]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid instruction")]
+fn invalid_instruction_panics() {
+    Prog::new(vec![ins(Reg::Neg, 0, &[4, 5])]);
+}
+
+#[test]
+fn linear_operators_stay_valid() {
+    let mut rng = rand::rng();
+    let ctx = LinearOperators::default();
+    let mut programs: Vec<Prog> = Vec::gen_random(&mut rng, 20);
+    for _ in 0..50 {
+        programs = (0..20)
+            .map(|i| {
+                let child = programs[i].crossover(&programs[(i + 3) % 20], &ctx, 0.2, &mut rng);
+                child.divide(&ctx, 0.2, &mut rng)
+            })
+            .collect();
+    }
+
+    for program in programs {
+        assert!((1..=32).contains(&program.len()));
+        // Program::new validates every instruction.
+        let program = Prog::new(program.instructions().to_vec());
+        program.run(&[1.0, 2.0]);
+    }
+}
+
+#[test]
+fn effective_only_mutation_leaves_introns() {
+    let mut rng = rand::rng();
+    let ctx = LinearOperators {
+        macro_probability: 0.0,
+        effective_only: true,
+    };
+    let program = sample_program();
+    let mutant = program.divide(&ctx, 1.0, &mut rng);
+    assert_eq!(mutant.instructions()[1], program.instructions()[1]);
+    assert_eq!(mutant.instructions()[3], program.instructions()[3]);
+}
+
+#[test]
+fn program_divergence_is_edit_distance() {
+    let program = sample_program();
+    let mut other = program.instructions().to_vec();
+    other.remove(1);
+    let other = Prog::new(other);
+
+    assert_eq!(program.divergence(&program, &()), 0.0);
+    assert_eq!(program.divergence(&other, &()), 0.25);
+    assert_eq!(other.divergence(&program, &()), 0.25);
+}
+
+#[test]
+fn linear_regression() {
+    let mut rng = rand::rng();
+    // x * y - x
+    let error = |p: &Prog| -> f32 {
+        let mut total = 0.0;
+        for x in -3..=3 {
+            for y in -3..=3 {
+                let (x, y) = (x as f32, y as f32);
+                total += (p.run(&[x, y])[0] - (x * y - x)).abs().min(1000.0);
+            }
+        }
+        total
+    };
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 200),
+        FitnessEliminator::new_without_observer(move |p: &Prog| -error(p)),
+        CrossoverRepopulator::new(0.1, LinearOperators::default()),
+    );
+    sim.perform_generations(200);
+
+    let best = sim.genomes.iter().map(error).fold(f32::MAX, f32::min);
+    assert!(best < 1e-3, "best error {best}");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Grammatical evolution
+// ─────────────────────────────────────────────────────────────────────────────
+
+const EXPR_GRAMMAR: &str = "
+<expr> ::= <expr> <op> <expr>
+         | ( <expr> )
+         | <var>
+<op>   ::= + | - | '*'
+<var>  ::= x | 1
+";
+
+#[test]
+fn grammar_parses() {
+    let grammar = Grammar::parse(EXPR_GRAMMAR).unwrap();
+    assert_eq!(grammar.start(), "expr");
+    assert_eq!(
+        grammar.nonterminals().collect::<Vec<_>>(),
+        ["expr", "op", "var"]
+    );
+    assert_eq!(grammar.production_count("expr"), Some(3));
+    assert_eq!(grammar.production_count("op"), Some(3));
+    assert_eq!(grammar.production_count("missing"), None);
+}
+
+#[test]
+fn grammar_errors() {
+    assert_eq!(Grammar::parse("  "), Err(GrammarError::Empty));
+    assert_eq!(
+        Grammar::parse("x <a> ::= y"),
+        Err(GrammarError::ExpectedRule)
+    );
+    assert_eq!(
+        Grammar::parse("<a> ::= \"x"),
+        Err(GrammarError::UnterminatedQuote)
+    );
+    assert_eq!(
+        Grammar::parse("<a> ::= x <a> ::= y"),
+        Err(GrammarError::DuplicateRule("a".into()))
+    );
+    assert_eq!(
+        Grammar::parse("<a> ::= <b>"),
+        Err(GrammarError::UndefinedNonterminal("b".into()))
+    );
+    assert_eq!(
+        Grammar::parse("<a> ::= x | <b> <b> ::= <b> y"),
+        Err(GrammarError::NonTerminating("b".into()))
+    );
+}
+
+#[test]
+fn grammar_maps_codons() {
+    let grammar = Grammar::parse(EXPR_GRAMMAR).unwrap();
+
+    // <expr> <op> <expr> -> x * (1)
+    let codons = [0, 2, 0, 5, 1, 2, 1];
+    let derivation = grammar.map(&codons, 0).unwrap();
+    assert_eq!(derivation.to_string(), "x*(1)");
+    assert_eq!(derivation.depth(), 5);
+
+    match derivation {
+        Derivation::Nonterminal {
+            symbol,
+            production,
+            children,
+        } => {
+            assert_eq!(symbol, "expr");
+            assert_eq!(production, 0);
+            assert_eq!(children.len(), 3);
+        }
+        Derivation::Terminal(_) => panic!("root must be a nonterminal"),
+    }
+}
+
+#[test]
+fn grammar_wraps_codons() {
+    let grammar = Grammar::parse(EXPR_GRAMMAR).unwrap();
+
+    // <expr> <op> <expr> needs more codons than there are.
+    assert_eq!(grammar.map(&[0, 2], 0), Err(MappingError::OutOfCodons));
+    assert_eq!(grammar.map(&[], 10), Err(MappingError::OutOfCodons));
+
+    // the last <var> wraps around and reads the first codon again.
+    let codons = [3, 2, 0, 4, 2];
+    let derivation = grammar.map(&codons, 1).unwrap();
+    assert_eq!(derivation.to_string(), "x-1");
+    assert_eq!(grammar.map(&codons, 0), Err(MappingError::OutOfCodons));
+}
+
+/// Recursive rules fail cleanly instead of overflowing the stack when many wraps are allowed.
+#[test]
+fn grammar_limits_depth() {
+    let grammar = Grammar::parse("<e> ::= <e> + <e> | x").unwrap();
+    assert_eq!(grammar.max_depth(), 256);
+    assert_eq!(grammar.map(&[0; 16], 1_000_000), Err(MappingError::TooDeep));
+
+    let fitness = GrammarFitness {
+        max_wraps: usize::MAX,
+        ..GrammarFitness::new(grammar.clone(), |_: &Derivation| 1.0)
+    };
+    assert_eq!(fitness.fitness(&Codons::<16>::new(vec![0; 16])), f32::MIN);
+
+    // `<e> + <e>` with both sides `x` is 3 levels deep.
+    let shallow = grammar.with_max_depth(3);
+    assert_eq!(shallow.map(&[1], 0).unwrap().depth(), 2);
+    assert_eq!(shallow.map(&[0, 1, 1], 0).unwrap().depth(), 3);
+    assert_eq!(shallow.map(&[0, 0], 0), Err(MappingError::TooDeep));
+}
+
+#[test]
+fn grammar_quoting_and_empty_terminals() {
+    let grammar = Grammar::parse("<s> ::= a ' ' <t>\n<t> ::= \"|\" | \"\"").unwrap();
+    assert_eq!(grammar.map(&[0], 0).unwrap().to_string(), "a |");
+    assert_eq!(grammar.map(&[1], 0).unwrap().to_string(), "a ");
+}
+
+#[test]
+fn codon_operators_respect_max_length() {
+    let mut rng = rand::rng();
+    let ctx = CodonOperators {
+        crossover: CodonCrossover::VariableOnePoint,
+        max_length: 30,
+    };
+    let mut genomes: Vec<Codons<20>> = Vec::gen_random(&mut rng, 10);
+    for _ in 0..50 {
+        genomes = (0..10)
+            .map(|i| genomes[i].crossover(&genomes[(i + 1) % 10], &ctx, 0.1, &mut rng))
+            .collect();
+    }
+    assert!(genomes.iter().all(|g| (1..=30).contains(&g.len())));
+
+    let ctx = CodonOperators {
+        crossover: CodonCrossover::OnePoint,
+        max_length: 30,
+    };
+    let a = Codons::<20>::gen_random(&mut rng);
+    let b = Codons::<20>::gen_random(&mut rng);
+    assert_eq!(a.crossover(&b, &ctx, 0.0, &mut rng).len(), 20);
+}
+
+#[test]
+fn codon_divergence() {
+    let a = Codons::<4>::new(vec![1, 2, 3, 4]);
+    let b = Codons::<4>::new(vec![1, 9, 3]);
+    assert_eq!(a.divergence(&a, &()), 0.0);
+    assert_eq!(a.divergence(&b, &()), 0.5);
+}
+
+#[test]
+fn grammatical_evolution() {
+    let mut rng = rand::rng();
+    let grammar = Grammar::parse(
+        "<word> ::= <letter> <word> | <letter>
+         <letter> ::= a | b | c | d",
+    )
+    .unwrap();
+
+    let target = "abcdcba";
+    let mut fitness = GrammarFitness::new(grammar.clone(), move |d: &Derivation| {
+        let word = d.to_string();
+        let matches = word.chars().zip(target.chars()).filter(|(a, b)| a == b);
+        matches.count() as f32 - word.len().abs_diff(target.len()) as f32
+    });
+    fitness.max_wraps = 1;
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 100),
+        FitnessEliminator::new_without_observer(fitness),
+        CrossoverRepopulator::new(0.05, CodonOperators::default()),
+    );
+    sim.perform_generations(100);
+
+    let best = sim
+        .genomes
+        .iter()
+        .filter_map(|g: &Codons<32>| g.map(&grammar, 1).ok())
+        .map(|d| d.to_string())
+        .find(|w| w == target);
+    assert!(best.is_some(), "target was not found");
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Cartesian GP
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Grid;
+
+impl FunctionSet for Grid {
+    type Op = Reg;
+
+    const OPS: &'static [Reg] = &[Reg::Add, Reg::Sub, Reg::Mul, Reg::Neg];
+    const INPUTS: usize = 2;
+    const ROWS: usize = 2;
+    const COLUMNS: usize = 3;
+    const LEVELS_BACK: usize = 1;
+
+    fn arity(op: &Reg) -> usize {
+        Machine::arity(op)
+    }
+
+    fn execute(op: &Reg, args: &[f32]) -> f32 {
+        Machine::execute(op, args)
+    }
+}
+
+fn node(op: Reg, inputs: [usize; 2]) -> CartesianNode<Reg> {
+    CartesianNode {
+        op,
+        inputs: inputs.to_vec(),
+    }
+}
+
+/// output = (x * y) + x, with nodes 3 and 5 inactive.
+fn sample_grid() -> Cartesian<Grid> {
+    Cartesian::new(
+        vec![
+            node(Reg::Mul, [0, 1]),
+            node(Reg::Neg, [1, 0]),
+            node(Reg::Add, [2, 0]),
+            node(Reg::Sub, [3, 2]),
+            node(Reg::Neg, [4, 0]),
+            node(Reg::Mul, [5, 4]),
+        ],
+        vec![6],
+    )
+}
+
+#[test]
+fn cartesian_decodes_active_nodes() {
+    let genome = sample_grid();
+    assert_eq!(genome.active(), vec![true, false, true, false, true, false]);
+    assert_eq!(genome.active_count(), 3);
+
+    let dag = genome.decode();
+    assert_eq!(dag.len(), 3);
+    assert_eq!(dag.nodes()[1].inputs, vec![2, 0]);
+    assert_eq!(dag.nodes()[2].inputs, vec![3]);
+    assert_eq!(dag.outputs(), &[4]);
+
+    // -((x * y) + x)
+    assert_eq!(dag.eval(&[2.0, 3.0]), vec![-8.0]);
+    assert_eq!(genome.eval(&[2.0, 3.0]), vec![-8.0]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid connections for node 4")]
+fn cartesian_enforces_levels_back() {
+    let mut nodes = sample_grid().nodes().to_vec();
+    nodes[4].inputs[0] = 2;
+    Cartesian::<Grid>::new(nodes, vec![6]);
+}
+
+#[test]
+fn single_active_mutation_changes_behavior() {
+    let mut rng = rand::rng();
+    let parent = sample_grid();
+    let parent_dag = parent.decode();
+    for i in 0..100 {
+        // the rate must not matter, or children of a low rate would be exact clones.
+        let rate = i as f32 / 99.0;
+        let child = parent.divide(&CartesianMutation::SingleActive, rate, &mut rng);
+        let dag = child.decode();
+        assert!(
+            dag.nodes() != parent_dag.nodes() || dag.outputs() != parent_dag.outputs(),
+            "mutation only changed inactive genes"
+        );
+
+        // the child must still be a valid genome.
+        Cartesian::<Grid>::new(child.nodes().to_vec(), child.outputs().to_vec());
+    }
+}
+
+#[test]
+fn point_mutation_stays_valid() {
+    let mut rng = rand::rng();
+    let mut genome = Cartesian::<Grid>::gen_random(&mut rng);
+    for _ in 0..100 {
+        genome = genome.divide(&CartesianMutation::Point, 0.3, &mut rng);
+        Cartesian::<Grid>::new(genome.nodes().to_vec(), genome.outputs().to_vec());
+    }
+}
+
+#[test]
+fn cartesian_divergence() {
+    let genome = sample_grid();
+    let mut nodes = genome.nodes().to_vec();
+    nodes[3].op = Reg::Add;
+    let other = Cartesian::<Grid>::new(nodes, vec![6]);
+
+    assert_eq!(genome.divergence(&genome, &()), 0.0);
+    // 6 nodes with 3 genes each, plus 1 output.
+    assert_eq!(genome.divergence(&other, &()), 1.0 / 19.0);
+}
+
+struct Wide;
+
+impl FunctionSet for Wide {
+    type Op = Reg;
+
+    const OPS: &'static [Reg] = &[Reg::Add, Reg::Sub, Reg::Mul, Reg::Neg];
+    const INPUTS: usize = 2;
+    const COLUMNS: usize = 30;
+
+    fn arity(op: &Reg) -> usize {
+        Machine::arity(op)
+    }
+
+    fn execute(op: &Reg, args: &[f32]) -> f32 {
+        Machine::execute(op, args)
+    }
+}
+
+#[test]
+fn cartesian_regression() {
+    let mut rng = rand::rng();
+    // x * y - x
+    let error = |g: &Cartesian<Wide>| -> f32 {
+        let dag = g.decode();
+        let mut total = 0.0;
+        for x in -3..=3 {
+            for y in -3..=3 {
+                let (x, y) = (x as f32, y as f32);
+                total += (dag.eval(&[x, y])[0] - (x * y - x)).abs().min(1000.0);
+            }
+        }
+        total
+    };
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 50),
+        FitnessEliminator::new_without_observer(move |g: &Cartesian<Wide>| -error(g)),
+        MitosisRepopulator::new(1.0, CartesianMutation::SingleActive),
+    );
+    sim.perform_generations(200);
+
+    let best = sim.genomes.iter().map(error).fold(f32::MAX, f32::min);
+    assert!(best < 1e-3, "best error {best}");
+}
@@ -0,0 +1,220 @@
+use std::collections::{BTreeMap, HashMap};
+
+use genetic_rs::prelude::*;
+
+#[test]
+fn float_mutation_respects_bounds() {
+    let mut rng = rand::rng();
+    let ctx = FloatMutation {
+        sigma: 10.0,
+        min: -1.0,
+        max: 1.0,
+    };
+    let mut x = 0.0f32;
+    for _ in 0..1000 {
+        x.mutate(&ctx, 1.0, &mut rng);
+        assert!((-1.0..=1.0).contains(&x));
+    }
+
+    let mut y = 0.5f64;
+    y.mutate(&FloatMutation::default(), 0.0, &mut rng);
+    assert_eq!(y, 0.5);
+}
+
+#[test]
+#[should_panic(expected = "FloatMutation min (1) must not be greater than max (-1)")]
+fn float_mutation_inverted_bounds_panic() {
+    let ctx = FloatMutation {
+        sigma: 0.1,
+        min: 1.0,
+        max: -1.0,
+    };
+    // checked even when nothing would be mutated.
+    0.0f32.mutate(&ctx, 0.0, &mut rand::rng());
+}
+
+#[test]
+#[should_panic(expected = "IntegerMutation min (10) must not be greater than max (0)")]
+fn integer_mutation_inverted_bounds_panic() {
+    let ctx = IntegerMutation {
+        step: 1,
+        min: 10,
+        max: 0,
+    };
+    5i32.mutate(&ctx, 1.0, &mut rand::rng());
+}
+
+/// Iterators of mutable references stay mutable alongside the container implementations.
+#[test]
+fn iterators_of_mutable_references() {
+    let mut rng = rand::rng();
+    let ctx = IntegerMutation {
+        step: 1,
+        min: 1,
+        max: 1,
+    };
+
+    let mut v = vec![0i32; 4];
+    v.iter_mut().mutate(&ctx, 1.0, &mut rng);
+    assert_eq!(v, vec![1; 4]);
+
+    let mut map: BTreeMap<&str, i32> = [("a", 0), ("b", 0)].into();
+    map.values_mut().mutate(&ctx, 1.0, &mut rng);
+    assert!(map.values().all(|&x| x == 1));
+}
+
+#[test]
+fn integer_mutation_respects_step_and_bounds() {
+    let mut rng = rand::rng();
+    let ctx = IntegerMutation {
+        step: 3,
+        min: 0,
+        max: 10,
+    };
+    let mut x = 5u8;
+    for _ in 0..1000 {
+        let before = x;
+        x.mutate(&ctx, 1.0, &mut rng);
+        assert!(x <= 10);
+        assert!(x.abs_diff(before) <= 3);
+    }
+
+    // saturates instead of overflowing.
+    let mut y = i8::MAX;
+    for _ in 0..100 {
+        y.mutate(&IntegerMutation::default(), 1.0, &mut rng);
+    }
+}
+
+#[test]
+fn bool_flips() {
+    let mut rng = rand::rng();
+    let mut b = false;
+    b.mutate(&(), 1.0, &mut rng);
+    assert!(b);
+    assert!(b.divide(&(), 0.0, &mut rng));
+}
+
+#[test]
+fn scalar_crossover_picks_a_parent() {
+    let mut rng = rand::rng();
+    let ctx = IntegerMutation::default();
+    for _ in 0..20 {
+        let child = 1i32.crossover(&2, &ctx, 0.0, &mut rng);
+        assert!(child == 1 || child == 2);
+    }
+}
+
+#[test]
+fn arrays_and_vecs_are_elementwise() {
+    let mut rng = rand::rng();
+    let ctx = IntegerMutation::default();
+
+    let a = [0i64; 8];
+    let b = [10i64; 8];
+    let child = a.crossover(&b, &ctx, 0.0, &mut rng);
+    assert!(child.iter().all(|&x| x == 0 || x == 10));
+
+    let child = a.divide(&ctx, 1.0, &mut rng);
+    assert!(child.iter().all(|&x| x.abs() == 1));
+
+    let v = vec![0i64; 5];
+    let w = vec![10i64; 3];
+    let child = v.crossover(&w, &ctx, 0.0, &mut rng);
+    assert_eq!(child.len(), 5);
+    assert_eq!(&child[3..], &[0, 0]);
+
+    let mut v = vec![true; 4];
+    v.mutate(&(), 1.0, &mut rng);
+    assert_eq!(v, vec![false; 4]);
+
+    let arr: [u16; 16] = GenerateRandom::gen_random(&mut rng);
+    assert_eq!(arr.len(), 16);
+}
+
+#[test]
+fn options_keep_their_presence() {
+    let mut rng = rand::rng();
+    let ctx = IntegerMutation::default();
+
+    let mut none: Option<u32> = None;
+    none.mutate(&ctx, 1.0, &mut rng);
+    assert_eq!(none, None);
+
+    let some = Some(5u32);
+    assert_eq!(some.divide(&ctx, 0.0, &mut rng), Some(5));
+    assert_eq!(some.crossover(&Some(5), &ctx, 0.0, &mut rng), Some(5));
+
+    for _ in 0..20 {
+        let child = some.crossover(&None, &ctx, 0.0, &mut rng);
+        assert!(child == Some(5) || child.is_none());
+    }
+}
+
+#[test]
+fn tuples_take_a_context_per_element() {
+    let mut rng = rand::rng();
+    let ctx = (
+        FloatMutation {
+            sigma: 1.0,
+            min: 0.0,
+            max: 0.0,
+        },
+        IntegerMutation {
+            step: 1,
+            min: 7,
+            max: 7,
+        },
+        (),
+    );
+
+    let genome = (3.0f32, 1u8, false);
+    let child = genome.divide(&ctx, 1.0, &mut rng);
+    assert_eq!(child, (0.0, 7, true));
+
+    let other = (3.0f32, 7u8, true);
+    let child = genome.crossover(&other, &ctx, 0.0, &mut rng);
+    assert!(child.1 == 1 || child.1 == 7);
+
+    let _: (f32, u8, bool, [i8; 2]) = GenerateRandom::gen_random(&mut rng);
+}
+
+#[test]
+fn maps_cross_shared_keys() {
+    let mut rng = rand::rng();
+    let ctx = IntegerMutation::default();
+
+    let a: HashMap<&str, i32> = [("shared", 1), ("a", 2)].into_iter().collect();
+    let b: HashMap<&str, i32> = [("shared", 1), ("b", 3)].into_iter().collect();
+    for _ in 0..20 {
+        let child = a.crossover(&b, &ctx, 0.0, &mut rng);
+        assert_eq!(child.get("shared"), Some(&1));
+        assert!(child.len() <= 3);
+    }
+
+    let mut m: BTreeMap<u8, bool> = (0..4).map(|k| (k, false)).collect();
+    m.mutate(&(), 1.0, &mut rng);
+    assert!(m.values().all(|&v| v));
+    assert_eq!(m.divide(&(), 0.0, &mut rng), m);
+}
+
+#[test]
+fn plain_vector_simulation() {
+    let mut rng = rand::rng();
+    let ctx = FloatMutation {
+        sigma: 0.1,
+        min: -5.0,
+        max: 5.0,
+    };
+    let sphere = |g: &[f32; 4]| -g.iter().map(|x| x * x).sum::<f32>();
+
+    let mut sim = GeneticSim::new(
+        Vec::gen_random(&mut rng, 50),
+        FitnessEliminator::new_without_observer(sphere),
+        CrossoverRepopulator::new(0.25, ctx),
+    );
+    sim.perform_generations(100);
+
+    let best = sim.genomes.iter().map(sphere).fold(f32::MIN, f32::max);
+    assert!(best > -0.01, "best fitness {best}");
+}
@@ -0,0 +1,217 @@
+//! Integration tests for [`LexicaseEliminator`] and [`CaseFitnessFn`].
+
+use genetic_rs::prelude::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shared test genome
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A genome that directly stores its error on each of three test cases.
+#[derive(Clone, Debug, PartialEq)]
+struct Genome([f32; 3]);
+
+impl GenerateRandom for Genome {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        Self([rng.random(), rng.random(), rng.random()])
+    }
+}
+
+impl RandomlyMutable for Genome {
+    type Context = ();
+
+    fn mutate(&mut self, _: &(), rate: f32, rng: &mut impl rand::Rng) {
+        for e in &mut self.0 {
+            *e = (*e + rng.random_range(-rate..=rate)).max(0.0);
+        }
+    }
+}
+
+impl Mitosis for Genome {
+    type Context = ();
+
+    fn divide(&self, ctx: &(), rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+fn case_errors(g: &Genome) -> Vec<f32> {
+    g.0.to_vec()
+}
+
+/// Three specialists (each perfect on one case), one generalist, and one genome
+/// that is dominated on every case.
+fn specialists() -> Vec<Genome> {
+    vec![
+        Genome([0.0, 9.0, 9.0]),
+        Genome([9.0, 0.0, 9.0]),
+        Genome([9.0, 9.0, 0.0]),
+        Genome([1.0, 1.0, 1.0]),
+        Genome([10.0, 10.0, 10.0]),
+    ]
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Selection behaviour
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The eliminator must select `floor(n * threshold) + 1` genomes.
+#[test]
+fn lexicase_selects_expected_count() {
+    let genomes: Vec<Genome> = (0..10).map(|i| Genome([i as f32; 3])).collect();
+    let mut elim = LexicaseEliminator::new(case_errors, 0.3, LexicaseEpsilon::Exact);
+    assert_eq!(elim.eliminate(genomes).len(), 4);
+}
+
+/// Standard lexicase must only ever pick specialists: the generalist and the
+/// dominated genome are never the best on whichever case is considered first.
+#[test]
+fn lexicase_selects_only_specialists() {
+    let mut elim = LexicaseEliminator::new(case_errors, 1.0, LexicaseEpsilon::Exact);
+    for _ in 0..20 {
+        let survivors = elim.eliminate(specialists());
+        assert!(survivors.iter().all(|g| g.0.contains(&0.0)));
+    }
+}
+
+/// With a huge epsilon every genome passes every filter, so even the
+/// dominated genome can be selected.
+#[test]
+fn lexicase_large_epsilon_allows_everyone() {
+    let elim = LexicaseEliminator::new(case_errors, 1.0, LexicaseEpsilon::Fixed(100.0));
+    let genomes = specialists();
+    let errors = elim.calculate_errors(&genomes);
+    let epsilons = elim.case_epsilons(&errors);
+
+    let mut rng = rand::rng();
+    let picked_dominated = (0..1000).any(|_| elim.select(&errors, &epsilons, &mut rng) == 4);
+    assert!(picked_dominated);
+}
+
+/// With a small fixed epsilon, genomes within epsilon of the best still pass.
+#[test]
+fn lexicase_fixed_epsilon_keeps_near_best() {
+    let elim = LexicaseEliminator::new(case_errors, 1.0, LexicaseEpsilon::Fixed(0.5));
+    let genomes = vec![Genome([0.0, 0.0, 0.0]), Genome([0.4, 0.4, 0.4])];
+    let errors = elim.calculate_errors(&genomes);
+    let epsilons = elim.case_epsilons(&errors);
+
+    let mut rng = rand::rng();
+    let picks: Vec<usize> = (0..200)
+        .map(|_| elim.select(&errors, &epsilons, &mut rng))
+        .collect();
+    assert!(picks.contains(&0));
+    assert!(picks.contains(&1));
+}
+
+/// NaN errors are infinitely bad, and a case where every candidate has NaN must not empty the candidate set.
+#[test]
+fn lexicase_nan_errors() {
+    let elim = LexicaseEliminator::new(case_errors, 1.0, LexicaseEpsilon::Exact);
+    let errors = vec![vec![f32::NAN, 1.0], vec![0.0, f32::NAN]];
+    let mut rng = rand::rng();
+    for _ in 0..50 {
+        elim.select(&errors, &[0.0, 0.0], &mut rng);
+    }
+
+    let errors = vec![vec![f32::NAN, 1.0], vec![f32::NAN, 0.0]];
+    for _ in 0..50 {
+        assert_eq!(elim.select(&errors, &[0.0, 0.0], &mut rng), 1);
+    }
+
+    // MAD epsilons of NaN cases are NaN.
+    let mut elim = LexicaseEliminator::new(
+        |g: &Vec<f32>| g.clone(),
+        1.0,
+        LexicaseEpsilon::MedianAbsoluteDeviation,
+    );
+    elim.eliminate(vec![vec![f32::NAN]; 4]);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Epsilon calculation
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Automatic epsilons are the median absolute deviation of each case.
+///
+/// Case 0 errors: [1, 2, 3, 4, 100] → median 3, deviations [2, 1, 0, 1, 97] → MAD 1.
+/// Case 1 errors: all 5 → MAD 0.
+#[test]
+fn lexicase_mad_epsilons() {
+    let elim = LexicaseEliminator::new(
+        |g: &Vec<f32>| g.clone(),
+        0.5,
+        LexicaseEpsilon::MedianAbsoluteDeviation,
+    );
+    let errors = vec![
+        vec![1.0, 5.0],
+        vec![2.0, 5.0],
+        vec![3.0, 5.0],
+        vec![4.0, 5.0],
+        vec![100.0, 5.0],
+    ];
+    assert_eq!(elim.case_epsilons(&errors), vec![1.0, 0.0]);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Simulation and edge cases
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The population size must remain constant in a lexicase-driven simulation.
+#[test]
+fn lexicase_sim_population_size_preserved() {
+    let mut rng = rand::rng();
+    let initial_size = 30;
+    let mut sim = GeneticSim::new(
+        Vec::<Genome>::gen_random(&mut rng, initial_size),
+        LexicaseEliminator::new(case_errors, 0.5, LexicaseEpsilon::MedianAbsoluteDeviation),
+        MitosisRepopulator::new(0.05, ()),
+    );
+    sim.perform_generations(20);
+    assert_eq!(sim.genomes.len(), initial_size);
+}
+
+/// Survivors are reported with the negated mean of their case errors, so fitness-aware repopulators can use them.
+#[test]
+fn lexicase_reports_mean_case_fitness() {
+    let mut elim = LexicaseEliminator::new(case_errors, 1.0, LexicaseEpsilon::Exact);
+    let population = vec![
+        Genome([0.0, 0.5, 1.0]),
+        Genome([1.0, 0.0, 0.5]),
+        Genome([0.5, 1.0, f32::NAN]),
+    ];
+    let evaluated = elim.eliminate_evaluated(population);
+
+    let fitness = evaluated.fitness.expect("lexicase must report fitness");
+    assert_eq!(fitness.len(), evaluated.genomes.len());
+    for (genome, fitness) in evaluated.genomes.iter().zip(fitness) {
+        if genome.0[2].is_nan() {
+            assert_eq!(fitness, f32::NEG_INFINITY);
+        } else {
+            assert_eq!(fitness, -0.5);
+        }
+    }
+}
+
+/// An empty population must stay empty instead of panicking.
+#[test]
+fn lexicase_empty_population() {
+    let mut elim = LexicaseEliminator::new_with_default(case_errors);
+    assert!(elim.eliminate(Vec::new()).is_empty());
+}
+
+/// [`LexicaseEliminator::new`] must panic when the threshold is outside [0, 1].
+#[test]
+#[should_panic]
+fn lexicase_invalid_threshold_panics() {
+    LexicaseEliminator::new(case_errors, -0.1, LexicaseEpsilon::Exact);
+}
+
+/// Genomes with different numbers of cases must panic with a clear message, even in release builds.
+#[test]
+#[should_panic(expected = "same number of cases")]
+fn lexicase_mismatched_case_counts_panic() {
+    let mut elim = LexicaseEliminator::new_with_default(|g: &Vec<f32>| g.clone());
+    elim.eliminate(vec![vec![0.0, 1.0], vec![0.0]]);
+}
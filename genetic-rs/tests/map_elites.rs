@@ -0,0 +1,214 @@
+//! Integration tests for [`MapElites`] and [`GridDimension`].
+
+use genetic_rs::map_elites::MapElitesStats;
+use genetic_rs::prelude::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shared test genome
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A point in the unit square. Its behaviour is its position.
+#[derive(Clone, Debug, PartialEq)]
+struct Genome {
+    x: f32,
+    y: f32,
+}
+
+impl GenerateRandom for Genome {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        Self {
+            x: rng.random(),
+            y: rng.random(),
+        }
+    }
+}
+
+impl RandomlyMutable for Genome {
+    type Context = ();
+
+    fn mutate(&mut self, _: &(), rate: f32, rng: &mut impl rand::Rng) {
+        self.x = (self.x + rng.random_range(-rate..=rate)).clamp(0.0, 1.0);
+        self.y = (self.y + rng.random_range(-rate..=rate)).clamp(0.0, 1.0);
+    }
+}
+
+impl Mitosis for Genome {
+    type Context = ();
+
+    fn divide(&self, ctx: &(), rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+impl Crossover for Genome {
+    type Context = ();
+
+    fn crossover(&self, other: &Self, ctx: &(), rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = Self {
+            x: self.x,
+            y: other.y,
+        };
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+fn fitness(g: &Genome) -> f32 {
+    g.x + g.y
+}
+
+fn behavior(g: &Genome) -> Vec<f32> {
+    vec![g.x, g.y]
+}
+
+fn grid() -> Vec<GridDimension> {
+    vec![
+        GridDimension::new(0.0, 1.0, 4),
+        GridDimension::new(0.0, 1.0, 4),
+    ]
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Grid layout
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Out-of-range values must be clamped into the first and last bins.
+#[test]
+fn grid_dimension_bins_and_clamps() {
+    let dim = GridDimension::new(0.0, 1.0, 4);
+    assert_eq!(dim.bin(-5.0), 0);
+    assert_eq!(dim.bin(0.0), 0);
+    assert_eq!(dim.bin(0.3), 1);
+    assert_eq!(dim.bin(0.99), 3);
+    assert_eq!(dim.bin(1.0), 3);
+    assert_eq!(dim.bin(7.0), 3);
+}
+
+/// Flat indices and grid coordinates must round-trip.
+#[test]
+fn cell_index_and_coords_round_trip() {
+    let map = MapElites::new(grid(), fitness, behavior, MitosisRepopulator::new(0.1, ()));
+    let index = map.cell_index(&[0.3, 0.8]);
+    assert_eq!(index, 4 + 3);
+    assert_eq!(map.cell_coords(index), vec![1, 3]);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Elitism
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A cell must only ever be replaced by a fitter genome.
+#[test]
+fn cell_keeps_best_occupant() {
+    let mut map = MapElites::new(grid(), fitness, behavior, MitosisRepopulator::new(0.1, ()));
+
+    assert!(map.insert_evaluated(Genome { x: 0.1, y: 0.1 }, 1.0, vec![0.1, 0.1]));
+    assert!(!map.insert_evaluated(Genome { x: 0.2, y: 0.2 }, 0.5, vec![0.2, 0.2]));
+    assert!(map.insert_evaluated(Genome { x: 0.0, y: 0.0 }, 2.0, vec![0.0, 0.0]));
+
+    let elites: Vec<_> = map.elites().collect();
+    assert_eq!(elites.len(), 1);
+    assert_eq!(elites[0].fitness, 2.0);
+    assert_eq!(elites[0].genome, Genome { x: 0.0, y: 0.0 });
+}
+
+/// Coverage and QD-score must reflect the filled cells.
+#[test]
+fn stats_report_coverage_and_qd_score() {
+    let mut map = MapElites::new(grid(), fitness, behavior, MitosisRepopulator::new(0.1, ()));
+    map.insert_all(vec![
+        Genome { x: 0.1, y: 0.1 },
+        Genome { x: 0.9, y: 0.9 },
+        Genome { x: 0.95, y: 0.95 },
+    ]);
+
+    let MapElitesStats {
+        filled_cells,
+        total_cells,
+        coverage,
+        qd_score,
+        max_fitness,
+    } = map.stats();
+    assert_eq!(filled_cells, 2);
+    assert_eq!(total_cells, 16);
+    assert!((coverage - 0.125).abs() < 1e-6);
+    assert!((qd_score - 2.1).abs() < 1e-5);
+    assert!((max_fitness.unwrap() - 1.9).abs() < 1e-6);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Stepping
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Stepping with mitosis must spread elites into more of the grid.
+#[test]
+fn step_with_mitosis_increases_coverage() {
+    let mut map = MapElites::new(grid(), fitness, behavior, MitosisRepopulator::new(0.3, ()));
+    map.insert_all(vec![Genome { x: 0.5, y: 0.5 }]);
+    let before = map.coverage();
+
+    map.perform_steps(50, 20);
+    assert!(map.coverage() > before);
+    assert!(map.elites().all(|e| e.fitness == fitness(&e.genome)));
+}
+
+/// Stepping with crossover must work as well, even from a single elite.
+#[test]
+fn step_with_crossover() {
+    let mut rng = rand::rng();
+    let mut map = MapElites::new(
+        grid(),
+        fitness,
+        behavior,
+        CrossoverRepopulator::new(0.3, ()),
+    );
+    map.seed_random(&mut rng, 1);
+    map.perform_steps(20, 10);
+    assert!(map.stats().filled_cells > 1);
+}
+
+/// Stepping an empty archive has no elites to choose from and must panic.
+#[test]
+#[should_panic]
+fn step_empty_archive_panics() {
+    let mut map = MapElites::new(grid(), fitness, behavior, MitosisRepopulator::new(0.1, ()));
+    map.step(10);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Export
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Exported entries and the fitness grid must describe the same cells.
+#[test]
+fn export_matches_fitness_grid() {
+    let mut map = MapElites::new(grid(), fitness, behavior, MitosisRepopulator::new(0.1, ()));
+    map.insert_all(vec![Genome { x: 0.1, y: 0.6 }, Genome { x: 0.9, y: 0.0 }]);
+
+    let exported = map.export();
+    assert_eq!(exported.len(), 2);
+    assert_eq!(exported[0].coords, vec![0, 2]);
+    assert_eq!(exported[1].coords, vec![3, 0]);
+
+    let grid = map.fitness_grid();
+    assert_eq!(grid.iter().flatten().count(), 2);
+    assert!((grid[2].unwrap() - 0.7).abs() < 1e-6);
+}
+
+/// The CSV export must contain a header and one row per elite.
+#[test]
+fn write_csv_rows() {
+    let mut map = MapElites::new(grid(), fitness, behavior, MitosisRepopulator::new(0.1, ()));
+    map.insert_all(vec![Genome { x: 0.0, y: 1.0 }]);
+
+    let mut out = Vec::new();
+    map.write_csv(&mut out).unwrap();
+    let csv = String::from_utf8(out).unwrap();
+
+    assert_eq!(
+        csv,
+        "cell_0,cell_1,behavior_0,behavior_1,fitness\n0,3,0,1,1\n"
+    );
+}
@@ -0,0 +1,202 @@
+//! Integration tests for [`NoveltyFitness`], [`ArchivePolicy`], and [`BlendedFitness`].
+
+use genetic_rs::prelude::*;
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Shared test genome
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// A genome whose behaviour is just its value.
+#[derive(Clone, Debug, PartialEq)]
+struct Genome(f32);
+
+impl GenerateRandom for Genome {
+    fn gen_random(rng: &mut impl rand::Rng) -> Self {
+        Self(rng.random())
+    }
+}
+
+impl RandomlyMutable for Genome {
+    type Context = ();
+
+    fn mutate(&mut self, _: &(), rate: f32, rng: &mut impl rand::Rng) {
+        self.0 += rng.random_range(-rate..=rate);
+    }
+}
+
+impl Mitosis for Genome {
+    type Context = ();
+
+    fn divide(&self, ctx: &(), rate: f32, rng: &mut impl rand::Rng) -> Self {
+        let mut child = self.clone();
+        child.mutate(ctx, rate, rng);
+        child
+    }
+}
+
+fn behavior(g: &Genome) -> Vec<f32> {
+    vec![g.0]
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Novelty scores
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Novelty is the mean distance to the k nearest neighbours, excluding the genome itself.
+///
+/// Population behaviours: [0, 1, 3, 10]
+/// - genome 0.0:  nearest two are 1 and 3   → (1 + 3) / 2 = 2.0
+/// - genome 10.0: nearest two are 3 and 1   → (7 + 9) / 2 = 8.0
+#[test]
+fn novelty_is_mean_knn_distance() {
+    let genomes = vec![Genome(0.0), Genome(1.0), Genome(3.0), Genome(10.0)];
+    let mut novelty = NoveltyFitness::new(behavior, 2, ArchivePolicy::Never);
+    novelty.prepare(&genomes);
+
+    assert!((novelty.fitness(&genomes[0]) - 2.0).abs() < 1e-6);
+    assert!((novelty.fitness(&genomes[3]) - 8.0).abs() < 1e-6);
+}
+
+/// The most isolated genome must survive a [`FitnessEliminator`] driven by novelty.
+#[test]
+fn novelty_eliminator_keeps_outlier() {
+    let mut genomes: Vec<Genome> = (0..9).map(|i| Genome(i as f32 * 0.01)).collect();
+    genomes.push(Genome(100.0));
+
+    let mut eliminator = FitnessEliminator::new(
+        NoveltyFitness::new(behavior, 3, ArchivePolicy::Never),
+        0.0,
+        (),
+    );
+    let survivors = eliminator.eliminate(genomes);
+
+    assert_eq!(survivors, vec![Genome(100.0)]);
+}
+
+/// [`KnockoutEliminator`] must prepare the novelty snapshot, otherwise every genome scores 0.
+#[test]
+fn novelty_knockout_eliminator_prepares_snapshot() {
+    let genomes = vec![Genome(0.0), Genome(100.0), Genome(0.1), Genome(0.2)];
+
+    let mut eliminator = KnockoutEliminator::new(
+        FitnessKnockoutFn::new(NoveltyFitness::new(behavior, 1, ArchivePolicy::Never)),
+        ActionIfOdd::Panic,
+    );
+    eliminator.eliminate(genomes);
+
+    let novelty = &eliminator.knockout_fn.fitness_fn;
+    assert_eq!(novelty.population_behaviors().len(), 4);
+    assert!(novelty.fitness(&Genome(100.0)) > 0.0);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Archive
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// `ArchivePolicy::Never` must leave the archive empty.
+#[test]
+fn archive_policy_never_keeps_archive_empty() {
+    let genomes: Vec<Genome> = (0..10).map(|i| Genome(i as f32)).collect();
+    let mut novelty = NoveltyFitness::new(behavior, 3, ArchivePolicy::Never);
+    novelty.prepare(&genomes);
+    novelty.prepare(&genomes);
+    assert!(novelty.archive.is_empty());
+}
+
+/// Behaviours selected in one generation are archived at the start of the next.
+#[test]
+fn archive_policy_most_novel_adds_next_generation() {
+    let genomes = vec![Genome(0.0), Genome(0.1), Genome(0.2), Genome(50.0)];
+    let mut novelty = NoveltyFitness::new(behavior, 1, ArchivePolicy::MostNovel(1));
+
+    novelty.prepare(&genomes);
+    assert!(novelty.archive.is_empty());
+
+    novelty.prepare(&genomes);
+    assert_eq!(novelty.archive, vec![vec![50.0]]);
+}
+
+/// Only behaviours at or above the threshold are archived.
+#[test]
+fn archive_policy_threshold() {
+    let genomes = vec![Genome(0.0), Genome(1.0), Genome(10.0)];
+    let mut novelty = NoveltyFitness::new(behavior, 1, ArchivePolicy::Threshold(5.0));
+
+    novelty.prepare(&genomes);
+    novelty.prepare(&[]);
+    assert_eq!(novelty.archive, vec![vec![10.0]]);
+}
+
+/// The archive must never grow past its maximum size, dropping the oldest entries first.
+#[test]
+fn archive_respects_max_size() {
+    let mut novelty =
+        NoveltyFitness::new(behavior, 1, ArchivePolicy::Random(1.0)).with_max_archive_size(3);
+
+    for gen in 0..5 {
+        let genomes = vec![Genome(gen as f32), Genome(gen as f32 + 0.5)];
+        novelty.prepare(&genomes);
+        assert!(novelty.archive.len() <= 3);
+    }
+
+    assert_eq!(novelty.archive, vec![vec![2.5], vec![3.0], vec![3.5]]);
+}
+
+/// [`NoveltyFitness::new`] must panic when the archive probability is outside [0, 1].
+#[test]
+#[should_panic]
+fn archive_policy_random_invalid_chance_panics() {
+    NoveltyFitness::new(behavior, 1, ArchivePolicy::Random(1.5));
+}
+
+/// Archived behaviours count as neighbours when scoring new genomes.
+#[test]
+fn archive_contributes_to_novelty() {
+    let mut novelty = NoveltyFitness::new(behavior, 1, ArchivePolicy::Never);
+    novelty.archive.push(vec![5.0]);
+    novelty.prepare(&[Genome(0.0), Genome(10.0)]);
+
+    assert!((novelty.fitness(&Genome(4.0)) - 1.0).abs() < 1e-6);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// BlendedFitness
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The blended score must be the weighted sum of both sources.
+#[test]
+fn blended_fitness_weighted_sum() {
+    let genomes = vec![Genome(0.0), Genome(2.0)];
+    let mut blended =
+        NoveltyFitness::new(behavior, 1, ArchivePolicy::Never).blend(|g: &Genome| g.0 * 10.0, 0.25);
+    blended.prepare(&genomes);
+
+    // novelty = 2.0, objective = 20.0 → 0.25 * 2 + 0.75 * 20 = 15.5
+    assert!((blended.fitness(&genomes[1]) - 15.5).abs() < 1e-6);
+}
+
+/// [`BlendedFitness::new`] must panic when the weight is outside [0, 1].
+#[test]
+#[should_panic]
+fn blended_fitness_invalid_weight_panics() {
+    BlendedFitness::new(|g: &Genome| g.0, |g: &Genome| g.0, 1.5);
+}
+
+/// The population size must remain constant in a novelty-driven simulation.
+#[test]
+fn novelty_sim_population_size_preserved() {
+    let mut rng = rand::rng();
+    let initial_size = 30;
+    let mut sim = GeneticSim::new(
+        Vec::<Genome>::gen_random(&mut rng, initial_size),
+        FitnessEliminator::new_without_observer(NoveltyFitness::new(
+            behavior,
+            5,
+            ArchivePolicy::MostNovel(1),
+        )),
+        MitosisRepopulator::new(0.1, ()),
+    );
+    sim.perform_generations(20);
+    assert_eq!(sim.genomes.len(), initial_size);
+    assert_eq!(sim.eliminator.fitness_fn.archive.len(), 19);
+}
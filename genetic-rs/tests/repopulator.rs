@@ -1,5 +1,5 @@
-//! Integration tests for [`MitosisRepopulator`], [`CrossoverRepopulator`],
-//! and the [`FromParent`] helper.
+//! Integration tests for [`MitosisRepopulator`], [`CrossoverRepopulator`], the [`ParentSelector`]s,
+//! [`NParentCrossoverRepopulator`], [`HybridRepopulator`], [`RandomImmigrantsRepopulator`], and the [`FromParent`] helper.
 
 use genetic_rs::prelude::*;
 
@@ -132,6 +132,271 @@ fn population_size_constant_with_crossover_sim() {
     assert_eq!(sim.genomes.len(), initial_size);
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// ParentSelector
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Every selector must avoid picking the first parent as its own mate when there are other candidates.
+#[test]
+fn selectors_never_pick_first_parent() {
+    let genomes = vec![Genome(5.0), Genome(1.0), Genome(3.0)];
+    let candidates = [0, 1, 2];
+    let fitness: Vec<f32> = genomes.iter().map(fitness).collect();
+    let mut rng = rand::rng();
+
+    for _ in 0..100 {
+        assert_ne!(
+            UniformSelector.select_mate(&genomes, None, 0, &candidates, &mut rng),
+            0
+        );
+        assert_ne!(
+            FitnessWeightedSelector.select_mate(&genomes, Some(&fitness), 0, &candidates, &mut rng),
+            0
+        );
+        assert_ne!(
+            TournamentSelector::new(3).select_mate(
+                &genomes,
+                Some(&fitness),
+                0,
+                &candidates,
+                &mut rng
+            ),
+            0
+        );
+    }
+}
+
+/// With a single candidate, the first parent is crossed with itself.
+#[test]
+fn selectors_fall_back_to_self() {
+    let genomes = vec![Genome(5.0)];
+    let mut rng = rand::rng();
+    assert_eq!(
+        UniformSelector.select_mate(&genomes, None, 0, &[0], &mut rng),
+        0
+    );
+}
+
+/// A large tournament almost always picks the fittest candidate, using real fitness when it is given.
+#[test]
+fn tournament_selector_prefers_fittest() {
+    let genomes = vec![Genome(0.0), Genome(1.0), Genome(9.0), Genome(2.0)];
+    let candidates = [0, 1, 2, 3];
+    let fitness: Vec<f32> = genomes.iter().map(fitness).collect();
+    let mut rng = rand::rng();
+
+    let mut selector = TournamentSelector::new(50);
+    for _ in 0..20 {
+        assert_eq!(
+            selector.select_mate(&genomes, Some(&fitness), 0, &candidates, &mut rng),
+            2
+        );
+    }
+
+    // without fitness, earlier survivors are treated as fitter.
+    assert_eq!(
+        selector.select_mate(&genomes, None, 3, &candidates, &mut rng),
+        0
+    );
+}
+
+/// The fitness-weighted selector must pick fitter mates more often.
+#[test]
+fn fitness_weighted_selector_prefers_fitter() {
+    let genomes = vec![Genome(0.0), Genome(1.0), Genome(10.0)];
+    let candidates = [0, 1, 2];
+    let fitness: Vec<f32> = genomes.iter().map(fitness).collect();
+    let mut rng = rand::rng();
+
+    let picks_of_best = (0..1000)
+        .filter(|_| {
+            FitnessWeightedSelector.select_mate(&genomes, Some(&fitness), 0, &candidates, &mut rng)
+                == 2
+        })
+        .count();
+    assert!(picks_of_best > 800, "best picked {picks_of_best} times");
+}
+
+/// Non-finite scores must not make the fitness-weighted selector panic.
+#[test]
+fn fitness_weighted_selector_handles_non_finite_fitness() {
+    let genomes = vec![Genome(0.0); 4];
+    let candidates = [0, 1, 2, 3];
+    let mut rng = rand::rng();
+
+    for fitness in [
+        [1.0, 2.0, 3.0, f32::MIN],
+        [1.0, f32::INFINITY, f32::NEG_INFINITY, f32::NAN],
+        [f32::NAN; 4],
+        [f32::MAX, f32::MIN, f32::MAX, f32::MIN],
+    ] {
+        for _ in 0..50 {
+            let mate = FitnessWeightedSelector.select_mate(
+                &genomes,
+                Some(&fitness),
+                0,
+                &candidates,
+                &mut rng,
+            );
+            assert_ne!(mate, 0);
+        }
+    }
+
+    // the invalid genome should almost never be picked.
+    let fitness = [1.0, 2.0, 3.0, f32::MIN];
+    let picks_of_invalid = (0..1000)
+        .filter(|_| {
+            FitnessWeightedSelector.select_mate(&genomes, Some(&fitness), 0, &candidates, &mut rng)
+                == 3
+        })
+        .count();
+    assert!(
+        picks_of_invalid < 10,
+        "invalid picked {picks_of_invalid} times"
+    );
+}
+
+/// A crossover repopulator with a custom selector must still fill the population.
+#[test]
+fn crossover_repopulator_with_selector_fills_to_target() {
+    let mut rng = rand::rng();
+    let mut genomes: Vec<Genome> = Vec::gen_random(&mut rng, 5);
+    let mut repop = CrossoverRepopulator::new(0.1, ()).with_selector(TournamentSelector::default());
+    repop.repopulate(&mut genomes, 20);
+    assert_eq!(genomes.len(), 20);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// NParentCrossoverRepopulator
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Deterministic N-parent crossover: the child is the average of all of its parents.
+impl NParentCrossover for Genome {
+    type Context = ();
+
+    fn crossover_n(parents: &[&Self], _: &(), _rate: f32, _rng: &mut impl rand::Rng) -> Self {
+        Self(parents.iter().map(|p| p.0).sum::<f32>() / parents.len() as f32)
+    }
+}
+
+/// The N-parent repopulator must grow the population back to the target size.
+#[test]
+fn n_parent_repopulator_fills_to_target() {
+    let mut rng = rand::rng();
+    let mut genomes: Vec<Genome> = Vec::gen_random(&mut rng, 5);
+    NParentCrossoverRepopulator::new(3, 0.1, ()).repopulate(&mut genomes, 20);
+    assert_eq!(genomes.len(), 20);
+}
+
+/// When there are exactly N survivors, every child must combine all of them.
+#[test]
+fn n_parent_repopulator_uses_distinct_parents() {
+    let mut genomes = vec![Genome(0.0), Genome(3.0), Genome(6.0)];
+    NParentCrossoverRepopulator::new(3, 0.0, ()).repopulate(&mut genomes, 10);
+    assert!(genomes[3..].iter().all(|g| (g.0 - 3.0).abs() < 1e-6));
+}
+
+/// Asking for more parents than there are survivors must reuse survivors instead of failing.
+#[test]
+fn n_parent_repopulator_more_parents_than_survivors() {
+    let mut genomes = vec![Genome(1.0), Genome(2.0)];
+    NParentCrossoverRepopulator::new(5, 0.0, ()).repopulate(&mut genomes, 6);
+    assert_eq!(genomes.len(), 6);
+    assert!(genomes.iter().all(|g| (1.0..=2.0).contains(&g.0)));
+}
+
+/// Zero parents per child must be rejected.
+#[test]
+#[should_panic(expected = "Parents per child must be greater than 0")]
+fn n_parent_repopulator_zero_parents_panics() {
+    NParentCrossoverRepopulator::<Genome>::new(0, 0.0, ());
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// HybridRepopulator
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// The hybrid repopulator must grow the population back to the target size and count every child.
+#[test]
+fn hybrid_repopulator_fills_to_target() {
+    let mut rng = rand::rng();
+    let mut genomes: Vec<Genome> = Vec::gen_random(&mut rng, 5);
+    let mut repop = HybridRepopulator::new(0.5, 0.1, false, ());
+    repop.repopulate(&mut genomes, 20);
+
+    assert_eq!(genomes.len(), 20);
+    assert_eq!(repop.last_stats.total(), 15);
+    assert_eq!(repop.last_stats.mutated, 0);
+}
+
+/// Probabilities of 0.0 and 1.0 must use only mitosis and only crossover respectively.
+#[test]
+fn hybrid_repopulator_extreme_probabilities() {
+    let mut genomes = vec![Genome(2.0), Genome(4.0)];
+    let mut repop = HybridRepopulator::new(1.0, 0.0, false, ());
+    repop.repopulate(&mut genomes, 10);
+    assert_eq!(repop.last_stats.crossover, 8);
+    assert_eq!(repop.last_stats.crossover_fraction(), Some(1.0));
+    // deterministic crossover of 2.0 and 4.0 always gives 3.0.
+    assert!(genomes[2..].iter().all(|g| g.0 == 3.0));
+
+    let mut genomes = vec![Genome(2.0), Genome(4.0)];
+    let mut repop = HybridRepopulator::new(0.0, 0.0, false, ());
+    repop.repopulate(&mut genomes, 10);
+    assert_eq!(repop.last_stats.mitosis, 8);
+    assert!(genomes.iter().all(|g| g.0 == 2.0 || g.0 == 4.0));
+}
+
+/// A single survivor cannot be crossed over, so mitosis is used instead.
+#[test]
+fn hybrid_repopulator_single_parent_uses_mitosis() {
+    let mut genomes = vec![Genome(1.0)];
+    let mut repop = HybridRepopulator::new(1.0, 0.1, true, ());
+    repop.repopulate(&mut genomes, 5);
+
+    assert_eq!(genomes.len(), 5);
+    assert_eq!(repop.last_stats.mitosis, 4);
+    assert_eq!(repop.last_stats.crossover, 0);
+}
+
+/// Crossover children are mutated when requested, and stats accumulate across generations.
+#[test]
+fn hybrid_repopulator_mutates_and_accumulates_stats() {
+    let mut repop = HybridRepopulator::new(1.0, 0.5, true, ());
+
+    for _ in 0..3 {
+        let mut genomes = vec![Genome(2.0), Genome(4.0)];
+        repop.repopulate(&mut genomes, 10);
+        assert_eq!(repop.last_stats.mutated, 8);
+    }
+    assert_eq!(repop.total_stats.crossover, 24);
+    assert_eq!(repop.total_stats.mutated, 24);
+
+    repop.reset_stats();
+    assert_eq!(repop.total_stats, OperatorStats::default());
+    assert_eq!(repop.total_stats.crossover_fraction(), None);
+}
+
+/// The hybrid repopulator must pick crossover mates with its selector, using the fitness passed by the eliminator.
+#[test]
+fn hybrid_repopulator_uses_selector_and_fitness() {
+    let mut repop =
+        HybridRepopulator::new(1.0, 0.0, false, ()).with_selector(TournamentSelector::new(50));
+
+    // the survivors are not sorted, so only the reported fitness identifies the best mate.
+    let population = EvaluatedPopulation::new(vec![Genome(0.0), Genome(8.0), Genome(2.0)])
+        .with_fitness(vec![0.0, 8.0, 2.0]);
+    let genomes = repop.repopulate_evaluated(population, 12);
+
+    assert_eq!(genomes.len(), 12);
+    assert_eq!(repop.last_stats.crossover, 9);
+    // genome 0 and genome 2 are always crossed with genome 1, and genome 1 with genome 2.
+    for (i, child) in genomes[3..].iter().enumerate() {
+        let expected = [4.0, 5.0, 5.0][i % 3];
+        assert_eq!(child.0, expected);
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // FromParent
 // ─────────────────────────────────────────────────────────────────────────────
@@ -154,3 +419,65 @@ fn from_parent_includes_original_parent() {
         "the first element must be the original parent"
     );
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// RandomImmigrantsRepopulator
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn spread(genomes: &[Genome]) -> f32 {
+    let max = genomes.iter().map(|g| g.0).fold(f32::MIN, f32::max);
+    let min = genomes.iter().map(|g| g.0).fold(f32::MAX, f32::min);
+    max - min
+}
+
+/// The configured fraction of the new population must be random immigrants.
+#[test]
+fn immigrants_fill_fraction_of_population() {
+    // mutation rate 0 makes every child an exact copy of the single survivor.
+    let mut genomes = vec![Genome(10.0)];
+    let mut repop = RandomImmigrantsRepopulator::new(MitosisRepopulator::new(0.0, ()), 0.25);
+    repop.repopulate(&mut genomes, 20);
+
+    assert_eq!(genomes.len(), 20);
+    assert_eq!(genomes.iter().filter(|g| g.0 == 10.0).count(), 15);
+    assert_eq!(genomes.iter().filter(|g| g.0 < 1.0).count(), 5);
+}
+
+/// Survivors are never discarded to make room for immigrants.
+#[test]
+fn immigrants_never_replace_survivors() {
+    let mut genomes = vec![Genome(10.0); 18];
+    let mut repop = RandomImmigrantsRepopulator::new(MitosisRepopulator::new(0.0, ()), 0.5);
+    repop.repopulate(&mut genomes, 20);
+
+    assert_eq!(genomes.len(), 20);
+    assert_eq!(genomes.iter().filter(|g| g.0 == 10.0).count(), 18);
+}
+
+/// With a diversity trigger, immigrants are only added when the survivors are too similar.
+#[test]
+fn immigrants_triggered_by_low_diversity() {
+    let mut repop = RandomImmigrantsRepopulator::on_low_diversity(
+        MitosisRepopulator::new(0.0, ()),
+        0.5,
+        spread,
+        1.0,
+    );
+
+    let mut diverse = vec![Genome(10.0), Genome(20.0)];
+    repop.repopulate(&mut diverse, 10);
+    assert_eq!(repop.last_diversity, Some(10.0));
+    assert!(diverse.iter().all(|g| g.0 >= 10.0));
+
+    let mut converged = vec![Genome(10.0), Genome(10.5)];
+    repop.repopulate(&mut converged, 10);
+    assert_eq!(repop.last_diversity, Some(0.5));
+    assert_eq!(converged.iter().filter(|g| g.0 < 1.0).count(), 5);
+}
+
+/// An invalid fraction must be rejected.
+#[test]
+#[should_panic(expected = "Fraction must be between 0.0 and 1.0")]
+fn immigrants_reject_invalid_fraction() {
+    RandomImmigrantsRepopulator::<_, Genome>::new(MitosisRepopulator::new(0.0, ()), 1.5);
+}
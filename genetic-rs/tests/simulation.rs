@@ -1,5 +1,5 @@
 //! Integration tests for [`GeneticSim`] core invariants, [`FitnessEliminator`] behaviour,
-//! and helper types such as [`FitnessObserver`].
+//! the [`EvaluatedPopulation`] hand-off, and helper types such as [`FitnessObserver`].
 
 use genetic_rs::prelude::*;
 
@@ -231,3 +231,131 @@ fn observer_called_once_per_generation() {
         "observer must be called once per generation"
     );
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// EvaluatedPopulation hand-off
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Metadata must line up with the genomes, and appending must drop metadata that only one side has.
+#[test]
+fn evaluated_population_metadata() {
+    let mut pop = EvaluatedPopulation::new(vec![Genome(1.0), Genome(2.0)])
+        .with_fitness(vec![1.0, 2.0])
+        .with_ages(vec![3, 4]);
+
+    let second = pop.get(1).unwrap();
+    assert_eq!(second.fitness, Some(2.0));
+    assert_eq!(second.age, Some(4));
+    assert_eq!(second.species, None);
+    assert!(pop.get(2).is_none());
+
+    pop.append(EvaluatedPopulation::new(vec![Genome(3.0)]).with_fitness(vec![3.0]));
+    assert_eq!(pop.len(), 3);
+    assert_eq!(pop.fitness, Some(vec![1.0, 2.0, 3.0]));
+    assert_eq!(pop.ages, None);
+    assert_eq!(pop.iter().count(), 3);
+}
+
+/// Appending an empty population must keep the metadata, and species ids must not collide.
+#[test]
+fn evaluated_population_append_keeps_metadata_and_separates_species() {
+    let mut pop = EvaluatedPopulation::new(vec![Genome(1.0), Genome(2.0)])
+        .with_fitness(vec![1.0, 2.0])
+        .with_species(vec![0, 1]);
+
+    pop.append(EvaluatedPopulation::new(Vec::new()));
+    assert_eq!(pop.fitness, Some(vec![1.0, 2.0]));
+    assert_eq!(pop.species, Some(vec![0, 1]));
+
+    pop.append(
+        EvaluatedPopulation::new(vec![Genome(3.0), Genome(4.0)])
+            .with_fitness(vec![3.0, 4.0])
+            .with_species(vec![0, 0]),
+    );
+    assert_eq!(pop.species, Some(vec![0, 1, 2, 2]));
+    assert_eq!(pop.fitness, Some(vec![1.0, 2.0, 3.0, 4.0]));
+}
+
+/// Metadata vectors must match the number of genomes.
+#[test]
+#[should_panic(expected = "Fitness length mismatch")]
+fn evaluated_population_length_mismatch_panics() {
+    EvaluatedPopulation::new(vec![Genome(1.0)]).with_fitness(vec![1.0, 2.0]);
+}
+
+/// [`FitnessEliminator`] must report the fitness of every survivor, sorted descending.
+#[test]
+fn fitness_eliminator_reports_fitness() {
+    let genomes: Vec<Genome> = (0..10).map(|i| Genome(i as f32)).collect();
+    let mut eliminator = FitnessEliminator::new_without_observer(fitness);
+    let survivors = eliminator.eliminate_evaluated(genomes);
+
+    let fitness = survivors
+        .fitness
+        .as_ref()
+        .expect("fitness must be reported");
+    assert_eq!(fitness.len(), survivors.len());
+    assert_eq!(fitness[0], 9.0);
+    for entry in survivors.iter() {
+        assert_eq!(entry.fitness, Some(entry.genome.0));
+    }
+}
+
+/// An eliminator that only implements [`Eliminator::eliminate`].
+struct KeepFirstHalf;
+
+impl Eliminator<Genome> for KeepFirstHalf {
+    fn eliminate(&mut self, mut genomes: Vec<Genome>) -> Vec<Genome> {
+        genomes.truncate(genomes.len() / 2);
+        genomes
+    }
+}
+
+/// A repopulator that records what it received through [`Repopulator::repopulate_evaluated`].
+#[derive(Default)]
+struct RecordingRepopulator {
+    saw_fitness: Vec<bool>,
+}
+
+impl Repopulator<Genome> for RecordingRepopulator {
+    fn repopulate(&mut self, genomes: &mut Vec<Genome>, target_size: usize) {
+        while genomes.len() < target_size {
+            genomes.push(genomes[0].clone());
+        }
+    }
+
+    fn repopulate_evaluated(
+        &mut self,
+        population: EvaluatedPopulation<Genome>,
+        target_size: usize,
+    ) -> Vec<Genome> {
+        self.saw_fitness.push(population.fitness.is_some());
+        let mut genomes = population.genomes;
+        self.repopulate(&mut genomes, target_size);
+        genomes
+    }
+}
+
+/// [`GeneticSim`] must pass fitness from the eliminator to the repopulator when the eliminator provides it,
+/// and existing eliminators without metadata must keep working.
+#[test]
+fn sim_passes_fitness_to_repopulator() {
+    let mut rng = rand::rng();
+
+    let mut sim = GeneticSim::new(
+        Vec::<Genome>::gen_random(&mut rng, 10),
+        FitnessEliminator::new_without_observer(fitness),
+        RecordingRepopulator::default(),
+    );
+    sim.perform_generations(3);
+    assert_eq!(sim.repopulator.saw_fitness, vec![true; 3]);
+
+    let mut sim = GeneticSim::new(
+        Vec::<Genome>::gen_random(&mut rng, 10),
+        KeepFirstHalf,
+        RecordingRepopulator::default(),
+    );
+    sim.perform_generations(3);
+    assert_eq!(sim.repopulator.saw_fitness, vec![false; 3]);
+    assert_eq!(sim.genomes.len(), 10);
+}
@@ -1,5 +1,5 @@
 //! Integration tests for [`SpeciatedPopulation`], [`SpeciatedFitnessEliminator`],
-//! and [`SpeciatedCrossoverRepopulator`].
+//! [`ClearingEliminator`], [`SpeciatedCrossoverRepopulator`], and [`AssortativeSelector`].
 
 use genetic_rs::prelude::*;
 use genetic_rs::speciation::SpeciatedPopulation;
@@ -175,13 +175,13 @@ fn round_robin_covers_all_genomes() {
     // 3 classes × 2 genomes each = 6 genomes, 3 species of equal size.
     let genomes: Vec<Genome> = (0..6)
         .map(|i| Genome {
-            class: (i % 3) as i32,
+            class: i % 3,
             val: i as f32,
         })
         .collect();
     let pop = SpeciatedPopulation::from_genomes(&genomes, 0.5, &());
 
-    let mut seen = vec![false; 6];
+    let mut seen = [false; 6];
     for idx in pop.round_robin().take(6) {
         seen[idx] = true;
     }
@@ -196,7 +196,7 @@ fn round_robin_covers_all_genomes() {
 fn round_robin_enumerate_species_index_is_valid() {
     let genomes: Vec<Genome> = (0..6)
         .map(|i| Genome {
-            class: (i % 3) as i32,
+            class: i % 3,
             val: i as f32,
         })
         .collect();
@@ -369,3 +369,202 @@ fn observer_receives_pre_division_fitness() {
         *scores,
     );
 }
+
+// ─────────────────────────────────────────────────────────────────────────────
+// ClearingEliminator — niche capacity
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// With a capacity of 1, only the best genome of each niche keeps its fitness,
+/// so the winner of a weak niche must outrank the runners-up of a strong one.
+///
+/// Setup:
+/// - 5 genomes of class 0 with val = 1.0 … 5.0
+/// - 1 genome  of class 1 with val = 0.1
+///
+/// With threshold = 0.2 and 6 genomes, 2 survive: the two niche winners.
+#[test]
+fn clearing_keeps_only_niche_winners() {
+    let mut genomes: Vec<Genome> = (1..=5)
+        .map(|i| Genome {
+            class: 0,
+            val: i as f32,
+        })
+        .collect();
+    genomes.push(Genome { class: 1, val: 0.1 });
+
+    let mut eliminator = ClearingEliminator::new(fitness, 0.5, 1, 0.2, (), ());
+    let survivors = eliminator.eliminate(genomes);
+
+    assert_eq!(survivors.len(), 2);
+    assert!(survivors.contains(&Genome { class: 0, val: 5.0 }));
+    assert!(survivors.contains(&Genome { class: 1, val: 0.1 }));
+}
+
+/// A capacity of k lets the k best genomes of a niche keep their fitness.
+#[test]
+fn clearing_capacity_keeps_top_k_per_niche() {
+    let mut genomes: Vec<Genome> = (1..=5)
+        .map(|i| Genome {
+            class: 0,
+            val: i as f32,
+        })
+        .collect();
+    genomes.push(Genome { class: 1, val: 0.1 });
+
+    let eliminator = ClearingEliminator::new(fitness, 0.5, 2, 0.5, (), ());
+    let sorted = eliminator.calculate_and_sort(genomes);
+
+    let kept: Vec<f32> = sorted
+        .iter()
+        .filter(|(_, f)| *f != ClearingEliminator::<fn(&Genome) -> f32, Genome>::CLEARED_FITNESS)
+        .map(|(_, f)| *f)
+        .collect();
+    assert_eq!(kept, vec![5.0, 4.0, 0.1]);
+}
+
+/// The observer must see raw fitness values, never the cleared ones.
+#[test]
+fn clearing_observer_receives_raw_fitness() {
+    use std::sync::{Arc, Mutex};
+
+    let observed: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(Vec::new()));
+    let observed_clone = Arc::clone(&observed);
+
+    let observer = move |fitnesses: &[(Genome, f32)]| {
+        let mut v = observed_clone.lock().unwrap();
+        v.extend(fitnesses.iter().map(|(_, f)| *f));
+    };
+
+    let genomes: Vec<Genome> = (1..=4)
+        .map(|i| Genome {
+            class: 0,
+            val: i as f32,
+        })
+        .collect();
+
+    let mut eliminator = ClearingEliminator::new(fitness, 0.5, 1, 0.5, observer, ());
+    eliminator.eliminate(genomes);
+
+    let scores = observed.lock().unwrap();
+    assert_eq!(*scores, vec![4.0, 3.0, 2.0, 1.0]);
+}
+
+/// The population size must remain constant when using [`ClearingEliminator`].
+#[test]
+fn clearing_sim_population_size_preserved() {
+    let mut rng = rand::rng();
+    let initial_size = 30;
+
+    let mut sim = GeneticSim::new(
+        Vec::<Genome>::gen_random(&mut rng, initial_size),
+        ClearingEliminator::new(fitness, 0.5, 2, 0.5, (), ()),
+        CrossoverRepopulator::new(0.1, ()),
+    );
+    sim.perform_generations(20);
+    assert_eq!(sim.genomes.len(), initial_size);
+}
+
+/// A niche capacity of 0 is meaningless and must panic.
+#[test]
+#[should_panic]
+fn clearing_zero_capacity_panics() {
+    ClearingEliminator::new(fitness, 0.5, 0, 0.5, (), ());
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// AssortativeSelector — divergence-based mate choice
+// ─────────────────────────────────────────────────────────────────────────────
+
+fn mixed_classes() -> Vec<Genome> {
+    vec![
+        Genome { class: 0, val: 0.0 },
+        Genome { class: 1, val: 1.0 },
+        Genome { class: 0, val: 2.0 },
+    ]
+}
+
+/// A most-similar selector must pick a mate of the same class, and a most-dissimilar one a mate of another class.
+#[test]
+fn assortative_selector_picks_by_divergence() {
+    let genomes = mixed_classes();
+    let candidates = [0, 1, 2];
+    let mut rng = rand::rng();
+
+    let mut similar = AssortativeSelector::new(Assortment::MostSimilar, ());
+    assert_eq!(
+        similar.select_mate(&genomes, None, 0, &candidates, &mut rng),
+        2
+    );
+
+    let mut dissimilar = AssortativeSelector::new(Assortment::MostDissimilar, ());
+    assert_eq!(
+        dissimilar.select_mate(&genomes, None, 0, &candidates, &mut rng),
+        1
+    );
+}
+
+/// A crossover repopulator using a most-similar selector must only cross genomes of the same class.
+#[test]
+fn assortative_crossover_repopulator_mates_within_class() {
+    let mut genomes = mixed_classes();
+    genomes.push(Genome { class: 1, val: 3.0 });
+
+    let mut repop = CrossoverRepopulator::new_with_selector(
+        0.0,
+        (),
+        AssortativeSelector::new(Assortment::MostSimilar, ()),
+    );
+    repop.repopulate(&mut genomes, 8);
+
+    // class 0 parents have vals 0.0 and 2.0, class 1 parents have vals 1.0 and 3.0.
+    for child in &genomes[4..] {
+        let expected = if child.class == 0 { 1.0 } else { 2.0 };
+        assert_eq!(child.val, expected);
+    }
+}
+
+/// A speciated crossover repopulator must accept a custom selector and still fill the population.
+#[test]
+fn speciated_repopulator_with_selector() {
+    let mut rng = rand::rng();
+    let mut genomes: Vec<Genome> = Vec::gen_random(&mut rng, 10);
+
+    let mut repop = SpeciatedCrossoverRepopulator::from_crossover(
+        CrossoverRepopulator::new_with_selector(0.1, (), TournamentSelector::new(2)),
+        0.5,
+        ActionIfIsolated::CrossoverSimilarSpecies,
+        (),
+    );
+    let fitness: Vec<f32> = genomes.iter().map(fitness).collect();
+    repop.repopulate_with_fitness(&mut genomes, Some(&fitness), 30);
+
+    assert_eq!(genomes.len(), 30);
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Evaluated populations — species metadata
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// [`SpeciatedFitnessEliminator`] must report raw fitness and a consistent species index for each survivor.
+#[test]
+fn speciated_eliminator_reports_species() {
+    let genomes: Vec<Genome> = (0..12)
+        .map(|i| Genome {
+            class: i % 3,
+            val: i as f32,
+        })
+        .collect();
+    let mut eliminator = SpeciatedFitnessEliminator::new(fitness, 0.5, 0.5, (), ());
+    let survivors = eliminator.eliminate_evaluated(genomes);
+
+    let species = survivors
+        .species
+        .as_ref()
+        .expect("species must be reported");
+    for (i, a) in survivors.genomes.iter().enumerate() {
+        assert_eq!(survivors.fitness.as_ref().unwrap()[i], a.val);
+        for (j, b) in survivors.genomes.iter().enumerate() {
+            assert_eq!(species[i] == species[j], a.class == b.class);
+        }
+    }
+}